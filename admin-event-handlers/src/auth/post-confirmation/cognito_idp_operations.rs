@@ -1,4 +1,4 @@
-use aws_config::SdkConfig;
+use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region, SdkConfig};
 use aws_sdk_cognitoidentityprovider::Client;
 use dailp::auth::UserGroup;
 
@@ -10,27 +10,155 @@ pub struct CognitoClient {
 
 impl CognitoClient {
     /// Create a new Cognito IDP Client with the provided configuration.
-    pub async fn new(config: &SdkConfig, pool_id: String) -> Result<Self, anyhow::Error> {
-        Ok(Self {
+    pub fn new(config: &SdkConfig, pool_id: String) -> Self {
+        Self {
             client: Client::new(config),
             pool_id,
-        })
+        }
     }
+
+    /// Create a client for the user pool in `DAILP_USER_POOL`, in the region
+    /// `DAILP_AWS_REGION`. If `DAILP_COGNITO_ENDPOINT` is set, requests go
+    /// there instead of to AWS, which lets us run against a local mock of the
+    /// Cognito API.
+    pub async fn from_env() -> Result<Self, anyhow::Error> {
+        let pool_id = std::env::var("DAILP_USER_POOL").map_err(|_| {
+            anyhow::format_err!("Unable to access environment variable DAILP_USER_POOL.")
+        })?;
+        let region_provider =
+            RegionProviderChain::first_try(std::env::var("DAILP_AWS_REGION").ok().map(Region::new))
+                .or_default_provider()
+                .or_else(Region::new("us-east-1"));
+        let mut config = aws_config::defaults(BehaviorVersion::latest()).region(region_provider);
+        if let Ok(endpoint) = std::env::var("DAILP_COGNITO_ENDPOINT") {
+            config = config.endpoint_url(endpoint);
+        }
+        Ok(Self::new(&config.load().await, pool_id))
+    }
+
     /// Attempts to add a user to a group.
     /// Fails if AdminAddUserToGroup fails.
     pub async fn add_user_to_group(
-        self,
-        email: String,
+        &self,
+        username: &str,
         group: UserGroup,
     ) -> Result<(), anyhow::Error> {
         self.client
             .admin_add_user_to_group()
-            .user_pool_id(self.pool_id)
-            .username(email)
+            .user_pool_id(&self.pool_id)
+            .username(username)
             .group_name(group.to_string())
             .send()
             .await
-            .map_err(|e| anyhow::Error::new(e))
+            .map_err(anyhow::Error::new)
             .map(|_x| ())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_cognitoidentityprovider::config::{Credentials, SharedCredentialsProvider};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// A request that the mock Cognito API received.
+    struct MockRequest {
+        target: String,
+        body: serde_json::Value,
+    }
+
+    /// Answer one request like the Cognito API would, with the given status
+    /// and JSON body. Returns the address to send the request to, and a handle
+    /// that gives back the request once it has been answered.
+    fn mock_cognito(status: &str, response: &str) -> (String, JoinHandle<MockRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (status, response) = (status.to_owned(), response.to_owned());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut target = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    match name.to_lowercase().as_str() {
+                        "x-amz-target" => target = value.trim().to_owned(),
+                        "content-length" => length = value.trim().parse().unwrap(),
+                        _ => {}
+                    }
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/x-amz-json-1.1\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                response.len(),
+                response
+            )
+            .unwrap();
+            MockRequest {
+                target,
+                body: serde_json::from_slice(&body).unwrap(),
+            }
+        });
+        (address, handle)
+    }
+
+    fn client(endpoint: String) -> CognitoClient {
+        let config = SdkConfig::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("us-east-1"))
+            .credentials_provider(SharedCredentialsProvider::new(Credentials::new(
+                "test", "test", None, None, "test",
+            )))
+            .endpoint_url(endpoint)
+            .build();
+        CognitoClient::new(&config, "us-east-1_test".to_owned())
+    }
+
+    #[tokio::test]
+    async fn adds_user_to_group() {
+        let (endpoint, request) = mock_cognito("200 OK", "{}");
+        client(endpoint)
+            .add_user_to_group("someone@example.com", UserGroup::Editors)
+            .await
+            .unwrap();
+
+        let request = request.join().unwrap();
+        assert_eq!(
+            request.target,
+            "AWSCognitoIdentityProviderService.AdminAddUserToGroup"
+        );
+        assert_eq!(
+            request.body,
+            serde_json::json!({
+                "UserPoolId": "us-east-1_test",
+                "Username": "someone@example.com",
+                "GroupName": "Editors",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_cognito_errors() {
+        let (endpoint, request) = mock_cognito(
+            "400 Bad Request",
+            r#"{"__type":"UserNotFoundException","message":"User does not exist."}"#,
+        );
+        let result = client(endpoint)
+            .add_user_to_group("nobody@example.com", UserGroup::Contributors)
+            .await;
+        request.join().unwrap();
+        assert!(result.is_err());
+    }
+}
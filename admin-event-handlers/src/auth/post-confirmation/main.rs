@@ -1,32 +1,29 @@
-//! A lambda event handler that adds a user to a group on confirmation if their email appears in a predefined list.
+//! A lambda event handler that adds a user to a group on confirmation if an editor invited their email.
 
 mod cognito_idp_operations;
-mod google_sheets_operations;
 
-use aws_config::{meta::region::RegionProviderChain, BehaviorVersion, Region};
 use aws_lambda_events::cognito::{
     CognitoEventUserPoolsPostConfirmation,
     CognitoEventUserPoolsPostConfirmationResponse as CognitoPostConfirmationResponse,
 };
 use cognito_idp_operations::CognitoClient;
-use google_sheets_operations::SheetInterpretation;
-use itertools::Itertools;
+use dailp::Database;
 use lambda_runtime::{service_fn, Error, LambdaEvent};
 
 /// This is the main body for the lambda function.
 /// First gets the email user attribute of the user who caused this function's invocation.
-/// Then, retrieves emails and roles about predetermined Editors and Contributors.
-/// Finally, adds the user who caused this function's invocation to the appropriate user pool group.
-/// If the user is not predetermined to be an Editor or Contributor, the final step is skipped.
+/// Then, looks for an invitation to a user group for that email in the DAILP database.
+/// Finally, adds the user who caused this function's invocation to the invited user pool group,
+/// and marks the invitation as accepted.
+/// If the user was not invited to be an Editor or Contributor, the final step is skipped.
 ///
 /// # Errors:
 /// This function errors under any of the following conditions:
 /// 1. User attributes either did not exist or did not come with the request.
 /// 2. User causing this invocation does not have an attribute named "email" or the attribute exists but has no value.
-/// 3. Google Sheets API did not return any data.
-/// 4. Multiple users on the permissions list use the same email.
-/// 5. This program is unable to access environment variables.
-/// 6. AddUserToGroup fails.
+/// 3. The database can't be reached.
+/// 4. This program is unable to access environment variables.
+/// 5. AddUserToGroup fails.
 async fn function_handler(
     event: LambdaEvent<CognitoEventUserPoolsPostConfirmation>,
 ) -> Result<CognitoPostConfirmationResponse, Error> {
@@ -36,48 +33,37 @@ async fn function_handler(
     if user_attributes.is_empty() {
         return Err("No email attribute found in event body.".into());
     }
-    let user_email_or_none = user_attributes.get("email");
-    if user_email_or_none.is_none() {
-        return Err("Email attribute does not exist or is empty.".into());
-    }
-    let user_email = user_email_or_none.unwrap().clone();
-    let user_permission_or_none = SheetInterpretation {
-        sheet: dailp::SheetResult::from_sheet("1ATTekY411Jz63k6VMDn3ISFu8_f75LYFErCGY-pxVkQ", None)
-            .await?,
-    }
-    .into_permission_list()?
-    .into_iter()
-    .filter(move |a| a.email == user_email)
-    .at_most_one()?;
-    if user_permission_or_none.is_none() {
-        // We don't want to error each time a user invoking this function is not in the list.
-        // Instead, we log that the user is not in the list, then exit successfully.
-        println!("User does not have preset permissions.");
-        return Ok(CognitoPostConfirmationResponse {});
-    }
-    let user_permission = user_permission_or_none.unwrap();
+    let user_email = match user_attributes.get("email") {
+        Some(email) if !email.is_empty() => email,
+        _ => return Err("Email attribute does not exist or is empty.".into()),
+    };
+    // Cognito knows users by this name, which is their sub when they sign in
+    // with an email address.
+    let username = event
+        .payload
+        .cognito_event_user_pools_header
+        .user_name
+        .as_deref()
+        .unwrap_or(user_email);
 
-    let region = std::env::var("DAILP_AWS_REGION")?;
-    let region_provider = RegionProviderChain::first_try(Region::new(region))
-        .or_default_provider()
-        .or_else(Region::new("us-east-1"));
-
-    let config = aws_config::defaults(BehaviorVersion::latest())
-        .region(region_provider)
-        .load()
-        .await;
-    let pool_id_or_err = std::env::var("DAILP_USER_POOL");
-    if pool_id_or_err.is_err() {
-        return Err("Unable to access environment variable DAILP_USER_POOL.".into());
-    }
-    // let cognito_action = CognitoClient::new(&config, pool_id_or_err?)
-    //     .await?
-    //     .add_user_to_group(user_permission.email, user_permission.role)
-    //     .await;
+    let db = Database::connect(Some(1))?;
+    let invitation = db.role_invitations(Some(user_email)).await?.pop();
+    let invitation = match invitation {
+        Some(invitation) => invitation,
+        None => {
+            // We don't want to error each time a user invoking this function wasn't invited.
+            // Instead, we log that the user was not invited, then exit successfully.
+            println!("User does not have preset permissions.");
+            return Ok(CognitoPostConfirmationResponse {});
+        }
+    };
 
-    // if cognito_action.is_err() {
-    //     return Err("Failed to add user to group".into());
-    // }
+    CognitoClient::from_env()
+        .await?
+        .add_user_to_group(username, invitation.role)
+        .await
+        .map_err(|e| format!("Failed to add user to group: {}", e))?;
+    db.accept_invitation(&invitation.email).await?;
 
     Ok(CognitoPostConfirmationResponse {})
 }
@@ -206,6 +206,10 @@
                 cargo run --bin dailp-graphql-schema
                 cargo run --bin dailp-graphql-local
               '')
+              (writers.writeBashBin "dev-auth" ''
+                cd $PROJECT_ROOT
+                cargo run --bin dailp-dev-auth -- serve
+              '')
               (writers.writeBashBin "dev-website" ''
                 cd $PROJECT_ROOT/website
                 yarn install
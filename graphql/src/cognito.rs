@@ -1,38 +1,97 @@
+use anyhow::Context;
 use dailp::auth::{JWTUserInfo, UserInfo};
 use jsonwebtoken::{jwk::JwkSet, Validation};
 
-/// Load the set of keys that can be used to validate Cognito identity tokens
-fn get_jwk_set() -> Result<JwkSet, serde_json::Error> {
-    // this file is pulled from:
-    // https://cognito-idp.<Region>.amazonaws.com/<userPoolId>/.well-known/jwks.json
-    serde_json::from_str::<JwkSet>(include_str!("dailp_user_pool_jwks.json"))
+/// Load the set of keys that can be used to validate identity tokens. These
+/// are the keys of our Cognito user pool, unless `DAILP_JWKS` names another
+/// JWKS file or URL, like the one served by `dailp-dev-auth`.
+pub async fn load_jwk_set() -> Result<JwkSet, anyhow::Error> {
+    match std::env::var("DAILP_JWKS") {
+        Ok(url) if url.starts_with("http://") || url.starts_with("https://") => {
+            Ok(reqwest::get(&url).await?.error_for_status()?.json().await?)
+        }
+        Ok(path) => Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+        // this file is pulled from:
+        // https://cognito-idp.<Region>.amazonaws.com/<userPoolId>/.well-known/jwks.json
+        Err(_) => Ok(serde_json::from_str(include_str!(
+            "dailp_user_pool_jwks.json"
+        ))?),
+    }
+}
+
+/// Who must have issued identity tokens and who they must be for, which is
+/// our Cognito user pool and its app client unless `DAILP_JWT_ISSUER` names
+/// another issuer.
+pub struct TokenSettings {
+    issuer: String,
+    audience: String,
+}
+
+impl TokenSettings {
+    /// Read the settings from the environment, failing if any are missing.
+    pub fn from_env() -> Result<Self, anyhow::Error> {
+        let var = |name| std::env::var(name).with_context(|| format!("{} isn't set", name));
+        let issuer = match std::env::var("DAILP_JWT_ISSUER") {
+            Ok(issuer) => issuer,
+            Err(_) => format!(
+                "https://cognito-idp.{}.amazonaws.com/{}",
+                var("DAILP_AWS_REGION")?,
+                var("DAILP_USER_POOL")?
+            ),
+        };
+        Ok(Self {
+            issuer,
+            audience: var("DAILP_USER_POOL_CLIENT")?,
+        })
+    }
 }
 
 /// Construct a jsonwebtoken valdiation scheme that is compliant with AWS JWT validation guidelines.
 /// https://docs.aws.amazon.com/cognito/latest/developerguide/amazon-cognito-user-pools-using-tokens-verifying-a-jwt.html
-fn cognito_validation() -> Result<Validation, anyhow::Error> {
-    let mut validation = Validation::new(jsonwebtoken::Algorithm::RS256);
-    validation.set_audience(&[std::env::var("DAILP_USER_POOL_CLIENT")?]); // app client ID
-    validation.set_issuer(&[format!(
-        "https://cognito-idp.{}.amazonaws.com/{}",
-        std::env::var("DAILP_AWS_REGION")?,
-        std::env::var("DAILP_USER_POOL")?
-    )]);
-    Ok(validation)
+/// Tokens must be signed with the algorithm of the key that verifies them.
+fn token_validation(
+    algorithm: jsonwebtoken::Algorithm,
+    issuer: &str,
+    audience: &str,
+) -> Validation {
+    let mut validation = Validation::new(algorithm);
+    validation.set_audience(&[audience]); // app client ID
+    validation.set_issuer(&[issuer]);
+    validation
 }
 
 const BEARER: &str = "Bearer ";
-pub fn user_info_from_authorization(auth: &str) -> Result<UserInfo, anyhow::Error> {
-    let token = auth.trim_start_matches(BEARER);
+pub fn user_info_from_authorization(
+    auth: &str,
+    keys: &JwkSet,
+    settings: &TokenSettings,
+) -> Result<UserInfo, anyhow::Error> {
+    verify_token(
+        auth.trim_start_matches(BEARER),
+        keys,
+        &settings.issuer,
+        &settings.audience,
+    )
+}
+
+fn verify_token(
+    token: &str,
+    keys: &JwkSet,
+    issuer: &str,
+    audience: &str,
+) -> Result<UserInfo, anyhow::Error> {
     let header = jsonwebtoken::decode_header(token)?;
-    let key_set = get_jwk_set()?;
 
-    match header.kid.and_then(|token_kid| key_set.find(&token_kid)) {
+    match header.kid.and_then(|token_kid| keys.find(&token_kid)) {
         Some(key) => {
+            let algorithm = key
+                .common
+                .algorithm
+                .unwrap_or(jsonwebtoken::Algorithm::RS256);
             let decoded = jsonwebtoken::decode::<JWTUserInfo>(
                 token,
                 &jsonwebtoken::DecodingKey::from_jwk(key)?,
-                &cognito_validation()?,
+                &token_validation(algorithm, issuer, audience),
             )?;
             Ok(decoded.claims.0)
         }
@@ -41,3 +100,75 @@ pub fn user_info_from_authorization(auth: &str) -> Result<UserInfo, anyhow::Erro
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dailp::auth::UserGroup;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+    /// Sign a token with a new key, like `dailp-dev-auth` does, returning the
+    /// token and the key set that verifies it.
+    fn signed_token(claims: serde_json::Value) -> (String, JwkSet) {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        let pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref()).unwrap();
+        // An uncompressed public key is 0x04 followed by the x and y coordinates.
+        let point = &pair.public_key().as_ref()[1..];
+        let keys = serde_json::from_value(serde_json::json!({
+            "keys": [{
+                "kty": "EC",
+                "crv": "P-256",
+                "alg": "ES256",
+                "use": "sig",
+                "kid": "test",
+                "x": base64::encode_config(&point[..32], base64::URL_SAFE_NO_PAD),
+                "y": base64::encode_config(&point[32..], base64::URL_SAFE_NO_PAD),
+            }]
+        }))
+        .unwrap();
+        let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::ES256);
+        header.kid = Some("test".to_owned());
+        let token = jsonwebtoken::encode(
+            &header,
+            &claims,
+            &jsonwebtoken::EncodingKey::from_ec_der(pkcs8.as_ref()),
+        )
+        .unwrap();
+        (token, keys)
+    }
+
+    fn claims(issuer: &str, audience: &str) -> serde_json::Value {
+        serde_json::json!({
+            "sub": "7c455493-8e7e-47b9-abed-5f1492eb7a9b",
+            "email": "editor@example.com",
+            "cognito:groups": ["Editors"],
+            "iss": issuer,
+            "aud": audience,
+            "exp": jsonwebtoken::get_current_timestamp() + 60,
+        })
+    }
+
+    #[test]
+    fn verifies_tokens_from_the_key_set() {
+        let (token, keys) = signed_token(claims("dailp-dev-auth", "dailp-dev"));
+        let user = verify_token(&token, &keys, "dailp-dev-auth", "dailp-dev").unwrap();
+        assert_eq!(
+            user.id,
+            "7c455493-8e7e-47b9-abed-5f1492eb7a9b".parse().unwrap()
+        );
+        assert!(user.is_in(UserGroup::Editors));
+        assert!(!user.is_in(UserGroup::Contributors));
+    }
+
+    #[test]
+    fn rejects_tokens_for_other_issuers_or_keys() {
+        let (token, keys) = signed_token(claims("someone-else", "dailp-dev"));
+        assert!(verify_token(&token, &keys, "dailp-dev-auth", "dailp-dev").is_err());
+
+        let (token, _) = signed_token(claims("dailp-dev-auth", "dailp-dev"));
+        let (_, other_keys) = signed_token(claims("dailp-dev-auth", "dailp-dev"));
+        assert!(verify_token(&token, &other_keys, "dailp-dev-auth", "dailp-dev").is_err());
+    }
+}
@@ -1,11 +1,12 @@
 mod query;
+mod subscription;
 
-use dailp::async_graphql::{EmptySubscription, Schema};
+use dailp::async_graphql::Schema;
 use std::fs::File;
 use std::io::prelude::*;
 
 fn main() -> anyhow::Result<()> {
-    let schema = Schema::build(query::Query, query::Mutation, EmptySubscription).finish();
+    let schema = Schema::build(query::Query, query::Mutation, subscription::Subscription).finish();
     let mut schema_file = File::create("graphql/schema.graphql")?;
     schema_file.write_all(schema.sdl().as_bytes())?;
     Ok(())
@@ -1,19 +1,43 @@
 //! This piece of the project exposes a GraphQL endpoint that allows one to access DAILP data in a federated manner with specific queries.
 
 use dailp::{
+    annotation::{Annotation, CreateAnnotationInput, UpdateAnnotationInput},
+    api_key::{ApiKeyAccess, CreateApiKeyInput, NewApiKey},
     auth::{AuthGuard, GroupGuard, UserGroup, UserInfo},
-    comment::{CommentParent, DeleteCommentInput, PostCommentInput},
-    slugify_ltree, AnnotatedForm, AttachAudioToWordInput, CollectionChapter, CurateWordAudioInput,
-    DeleteContributorAttribution, DocumentMetadataUpdate, DocumentParagraph,
-    UpdateContributorAttribution, Uuid,
+    comment::{
+        can_moderate, Comment, CommentParent, CommentParentType, DeleteCommentInput,
+        EditCommentInput, HideCommentInput, PostCommentInput,
+    },
+    page::{CreatePageInput, PageStatus, UpdatePageInput},
+    permission::{GrantPermissionInput, PermissionGrant, PermissionGuard, PermissionTarget},
+    slugify_ltree,
+    user::{
+        preferred_orthography, InviteUserInput, RoleInvitation, UpdateProfileInput, UserProfile,
+    },
+    AnnotatedForm, AttachAudioToWordInput, CollectionChapter, ContributorDetails,
+    CreateContributorInput, CurateWordAudioInput, DeleteContributorAttribution,
+    DocumentMetadataUpdate, DocumentParagraph, MergeContributorsInput,
+    UpdateContributorAttribution, UpdateContributorInput, Uuid,
 };
 use itertools::Itertools;
 
 use {
-    dailp::async_graphql::{self, dataloader::DataLoader, Context, FieldResult, Guard, Object},
+    dailp::async_graphql::{
+        self,
+        connection::{self, Connection, OpaqueCursor},
+        dataloader::DataLoader,
+        Context, ErrorExtensions, FieldResult, Guard, Object,
+    },
     dailp::{
-        AnnotatedDoc, AnnotatedFormUpdate, CherokeeOrthography, Database, EditedCollection,
-        MorphemeId, MorphemeReference, MorphemeTag, ParagraphUpdate, WordsInDocument,
+        graphql_error, publish_event, AddChapterInput, AddDocumentPageInput, AddParagraphInput,
+        AnnotatedDoc, AnnotatedFormUpdate, CherokeeOrthography, CreateDocumentInput,
+        CreateEditedCollectionInput, DailpError, Database, DocumentEvent, DocumentSearchResults,
+        EditTargetType, EditedCollection, FindAndReplaceInput, InsertWordInput,
+        MergeParagraphsInput, MergeWordsInput, MorphemeId, MorphemePattern, MorphemePatternMatch,
+        MorphemeReference, MorphemeTag, MoveChapterInput, PageRequest, ParagraphUpdate,
+        ProposeEditInput, ProposedEdit, Replacement, ReverseLookupEntry, SearchFilter,
+        SplitParagraphInput, SplitWordInput, UpdateChapterInput, WordConnection, WordCursor,
+        WordSearchResults, WordUpdateResult, WordsInDocument,
     },
     serde::{Deserialize, Serialize},
 };
@@ -28,11 +52,12 @@ impl Query {
         &self,
         context: &Context<'_>,
     ) -> FieldResult<Vec<EditedCollection>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .all_edited_collections()
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     // query for 1 collection based on slug, and make a collection object with all the stuff in it.
@@ -42,10 +67,11 @@ impl Query {
         slug: String,
     ) -> FieldResult<Option<EditedCollection>> {
         let slug = slugify_ltree(slug);
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::EditedCollectionDetails(slug))
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Retrieves a chapter and its contents by its collection and chapter slug.
@@ -55,42 +81,74 @@ impl Query {
         collection_slug: String,
         chapter_slug: String,
     ) -> FieldResult<Option<CollectionChapter>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .chapter(slugify_ltree(collection_slug), slugify_ltree(chapter_slug))
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
-    /// List of all the functional morpheme tags available
+    /// List of all the functional morpheme tags available, written in the given
+    /// system or the reader's preferred one
     async fn all_tags(
         &self,
         context: &Context<'_>,
-        system: CherokeeOrthography,
+        system: Option<CherokeeOrthography>,
     ) -> FieldResult<Vec<MorphemeTag>> {
-        Ok(context
+        let system = preferred_orthography(context, system).await?;
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .all_tags(system)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Listing of all documents excluding their contents by default
     async fn all_documents(&self, context: &Context<'_>) -> FieldResult<Vec<AnnotatedDoc>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
-            .all_documents()
-            .await?)
+            .all_documents(&PageRequest::default())
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Documents ordered by short name, one page at a time
+    async fn all_documents_connection(
+        &self,
+        context: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<Connection<OpaqueCursor<String>, AnnotatedDoc>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let documents = db.all_documents(&page).await.map_err(graphql_error)?;
+                Ok::<_, async_graphql::Error>(
+                    page.into_connection(documents, |doc| doc.meta.short_name.clone()),
+                )
+            },
+        )
+        .await
     }
 
-    /// List of all content pages
+    /// List of all content pages in site order. Only editors see drafts.
     async fn all_pages(&self, context: &Context<'_>) -> FieldResult<Vec<dailp::page::Page>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
-            .all_pages()
-            .await?)
+            .all_pages(is_editor(context))
+            .await
+            .map_err(graphql_error)
     }
 
     /// List of all the document collections available.
@@ -98,11 +156,12 @@ impl Query {
         &self,
         context: &Context<'_>,
     ) -> FieldResult<Vec<dailp::DocumentCollection>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .top_collections()
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     async fn collection(
@@ -110,11 +169,12 @@ impl Query {
         context: &Context<'_>,
         slug: String,
     ) -> FieldResult<dailp::DocumentCollection> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .collection(slug)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Retrieves a full document from its unique name.
@@ -123,10 +183,11 @@ impl Query {
         context: &Context<'_>,
         slug: String,
     ) -> FieldResult<Option<AnnotatedDoc>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::DocumentShortName(slug.to_ascii_uppercase()))
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Retrieves all documents that are bookmarked by the current user.
@@ -137,16 +198,18 @@ impl Query {
     ) -> FieldResult<Vec<AnnotatedDoc>> {
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         let bookmarked_ids = context
             .data::<DataLoader<Database>>()?
             .loader()
             .bookmarked_documents(&user.id)
-            .await?;
+            .await
+            .map_err(graphql_error)?;
         let annotated_docs_map = context
             .data::<DataLoader<Database>>()?
             .load_many(bookmarked_ids.iter().map(|&id| dailp::DocumentId(id)))
-            .await?;
+            .await
+            .map_err(graphql_error)?;
         Ok(annotated_docs_map.into_values().collect())
     }
 
@@ -156,22 +219,81 @@ impl Query {
         context: &Context<'_>,
         id: Uuid,
     ) -> FieldResult<Option<AnnotatedDoc>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::DocumentId(id))
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
-    /// Retrieves a full document from its unique identifier.
+    /// Details of one contributor, including every document they worked on
+    pub async fn contributor(
+        &self,
+        context: &Context<'_>,
+        id: Uuid,
+    ) -> FieldResult<Option<ContributorDetails>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .load_one(dailp::ContributorById(id))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// All contributors, ordered by full name
+    pub async fn all_contributors(
+        &self,
+        context: &Context<'_>,
+    ) -> FieldResult<Vec<ContributorDetails>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .all_contributors()
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// People who will be put in a user group when they confirm their account
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    pub async fn role_invitations(
+        &self,
+        context: &Context<'_>,
+    ) -> FieldResult<Vec<RoleInvitation>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .role_invitations(None)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Roles given on single collections or documents, either to one user or
+    /// to everyone
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    pub async fn permission_grants(
+        &self,
+        context: &Context<'_>,
+        user_id: Option<Uuid>,
+    ) -> FieldResult<Vec<PermissionGrant>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .permission_grants(user_id)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Retrieves a content page by its path. Drafts are only shown to editors.
     pub async fn page(
         &self,
         context: &Context<'_>,
         id: String,
     ) -> FieldResult<Option<dailp::page::Page>> {
-        Ok(context
+        let page = context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::PageId(id))
-            .await?)
+            .await
+            .map_err(graphql_error)?;
+        Ok(page.filter(|page| page.status == PageStatus::Published || is_editor(context)))
     }
 
     /// Lists all forms containing a morpheme with the given gloss.
@@ -185,11 +307,12 @@ impl Query {
                          ")]
         compare_by: Option<CherokeeOrthography>,
     ) -> FieldResult<Vec<MorphemeReference>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .morphemes(MorphemeId::parse(&gloss).unwrap(), compare_by)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Lists all words containing a morpheme with the given gloss.
@@ -204,8 +327,14 @@ impl Query {
             Ok(context
                 .data::<DataLoader<Database>>()?
                 .loader()
-                .connected_forms(Some(document_id), &morpheme_gloss)
-                .await?
+                .connected_forms(
+                    Some(document_id),
+                    &morpheme_gloss,
+                    None,
+                    &PageRequest::default(),
+                )
+                .await
+                .map_err(graphql_error)?
                 .into_iter()
                 .group_by(|w| w.position.document_id)
                 .into_iter()
@@ -219,11 +348,45 @@ impl Query {
             Ok(context
                 .data::<DataLoader<Database>>()?
                 .loader()
-                .words_by_doc(document_id, &morpheme_gloss)
-                .await?)
+                .words_by_doc(document_id, &morpheme_gloss, &PageRequest::default())
+                .await
+                .map_err(graphql_error)?)
         }
     }
 
+    /// Lists all words containing a morpheme with the given gloss, grouped by
+    /// the document containing them, one page of documents at a time.
+    /// Each document includes all of its matching words, so to find words in
+    /// a single document use `morphemesByDocument` instead.
+    async fn morphemes_by_document_connection(
+        &self,
+        context: &Context<'_>,
+        morpheme_gloss: String,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<Connection<OpaqueCursor<Uuid>, WordsInDocument>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let groups = db
+                    .words_by_doc(None, &morpheme_gloss, &page)
+                    .await
+                    .map_err(graphql_error)?;
+                Ok::<_, async_graphql::Error>(page.into_connection(groups, |group| {
+                    group.document_id.map(|id| id.0).unwrap_or_default()
+                }))
+            },
+        )
+        .await
+    }
+
     /// Forms containing the given morpheme gloss or related ones clustered over time.
     async fn morpheme_time_clusters(
         &self,
@@ -237,11 +400,16 @@ impl Query {
         let db = context.data::<DataLoader<Database>>()?.loader();
         let morpheme = dailp::MorphemeId::parse(&gloss).unwrap();
         let doc_id = if let Some(short_name) = morpheme.document_name {
-            db.document_id_from_name(&short_name).await?
+            db.document_id_from_name(&short_name)
+                .await
+                .map_err(graphql_error)?
         } else {
             None
         };
-        let forms = db.connected_forms(doc_id, &morpheme.gloss).await?;
+        let forms = db
+            .connected_forms(doc_id, &morpheme.gloss, None, &PageRequest::default())
+            .await
+            .map_err(graphql_error)?;
         // Cluster forms by the decade they were recorded in.
         let clusters = forms
             .into_iter()
@@ -283,12 +451,14 @@ impl Query {
         &self,
         context: &Context<'_>,
         id: String,
-        system: CherokeeOrthography,
+        system: Option<CherokeeOrthography>,
     ) -> FieldResult<Option<MorphemeTag>> {
+        let system = preferred_orthography(context, system).await?;
         Ok(context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::TagId(id, system))
-            .await?
+            .await
+            .map_err(graphql_error)?
             .unwrap_or_default()
             .into_iter()
             .next())
@@ -301,11 +471,41 @@ impl Query {
         context: &Context<'_>,
         query: String,
     ) -> FieldResult<Vec<dailp::AnnotatedForm>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
-            .search_words_any_field(query)
-            .await?)
+            .search_words_any_field(query, &PageRequest::default())
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Search for words that match any one of the given fields, one page at a
+    /// time
+    async fn word_search_connection(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<WordConnection> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let forms = db
+                    .search_words_any_field(query, &page)
+                    .await
+                    .map_err(graphql_error)?;
+                Ok::<_, async_graphql::Error>(page.into_connection(forms, WordCursor::of))
+            },
+        )
+        .await
     }
 
     /// Get a single word given the word ID
@@ -314,11 +514,12 @@ impl Query {
         context: &Context<'_>,
         id: Uuid,
     ) -> FieldResult<dailp::AnnotatedForm> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .word_by_id(&id)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Get a single paragraph given the paragraph ID
@@ -327,11 +528,12 @@ impl Query {
         context: &Context<'_>,
         id: Uuid,
     ) -> FieldResult<dailp::DocumentParagraph> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .paragraph_by_id(&id)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Search for words with the exact same syllabary string, or with very
@@ -341,11 +543,152 @@ impl Query {
         context: &Context<'_>,
         query: String,
     ) -> FieldResult<Vec<dailp::AnnotatedForm>> {
-        Ok(context
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .potential_syllabary_matches(&query, &PageRequest::default())
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Search for words with syllabary similar to the given query, one page
+    /// at a time
+    async fn syllabary_search_connection(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<WordConnection> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let forms = db
+                    .potential_syllabary_matches(&query, &page)
+                    .await
+                    .map_err(graphql_error)?;
+                Ok::<_, async_graphql::Error>(page.into_connection(forms, WordCursor::of))
+            },
+        )
+        .await
+    }
+
+    /// Search for words that match the given query, along with counts of the
+    /// results by collection, genre, decade, contributor, and audio.
+    /// The filter narrows results down to particular values of those facets.
+    async fn search_words(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        #[graphql(
+            default,
+            desc = "Match the query against similar looking syllabary characters
+                    instead of any field containing it."
+        )]
+        syllabary: bool,
+        filter: Option<SearchFilter>,
+    ) -> FieldResult<WordSearchResults> {
+        let patterns = if syllabary {
+            CherokeeOrthography::similar_syllabary_strings(&query)
+                .into_iter()
+                .map(|x| format!("%{}%", x))
+                .collect()
+        } else {
+            vec![format!("%{}%", query)]
+        };
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .search_words_faceted(patterns, filter.unwrap_or_default())
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Search for documents with a title or short name containing the given
+    /// query, along with counts of the results by collection, genre, decade,
+    /// contributor, and audio.
+    /// The filter narrows results down to particular values of those facets.
+    async fn search_documents(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        filter: Option<SearchFilter>,
+    ) -> FieldResult<DocumentSearchResults> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .search_documents_faceted(&query, filter.unwrap_or_default())
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Find words whose segmentation matches a pattern over their morpheme
+    /// glosses, like `3* - go - PST`. Each match reports which segments of
+    /// the word matched so they can be highlighted.
+    ///
+    /// Patterns are a sequence of glosses separated by spaces or hyphens.
+    /// A `*` within a gloss matches any characters, `_` matches any single
+    /// segment, `...` matches any number of segments, and `(A | B)` matches
+    /// either alternative. Add `@Morpheme`, `@Clitic`, or `@Modifier` to a
+    /// segment to require that role. Start with `^` or end with `$` to anchor
+    /// the pattern to the start or end of the word.
+    ///
+    /// Unless limited to one document, the pattern must contain at least one
+    /// exact gloss outside of any alternatives.
+    async fn morpheme_pattern_search(
+        &self,
+        context: &Context<'_>,
+        pattern: String,
+        document_id: Option<Uuid>,
+    ) -> FieldResult<Vec<MorphemePatternMatch>> {
+        let pattern =
+            MorphemePattern::parse(&pattern).map_err(|err| DailpError::Validation(err).extend())?;
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .morpheme_pattern_search(&pattern, document_id.map(dailp::DocumentId))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Look up Cherokee words by their English meaning, for learners.
+    /// English words are matched regardless of inflection, so "walked" also
+    /// finds "walking". Results are grouped by the root they're built on, with
+    /// roots whose dictionary definition matches ranked above those with only
+    /// matching attested forms.
+    async fn reverse_lookup(
+        &self,
+        context: &Context<'_>,
+        english: String,
+    ) -> FieldResult<Vec<ReverseLookupEntry>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .reverse_lookup(&english)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Suggestions on the words and paragraphs of a document that nobody has
+    /// resolved yet, oldest first.
+    async fn open_suggestions(
+        &self,
+        context: &Context<'_>,
+        document_id: Uuid,
+    ) -> FieldResult<Vec<Comment>> {
+        context
             .data::<DataLoader<Database>>()?
             .loader()
-            .potential_syllabary_matches(&query)
-            .await?)
+            .open_suggestions(&document_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)
     }
 
     /// Basic information about the currently authenticated user, if any.
@@ -366,8 +709,9 @@ impl Mutation {
         "1.0"
     }
 
-    /// Delete a comment.
-    /// Will fail if the user making the request is not the poster.
+    /// Delete a comment and its replies.
+    /// Will fail if the user making the request is not the poster, or if
+    /// others have replied and the user is not an editor.
     #[graphql(guard = "AuthGuard")]
     async fn delete_comment(
         &self,
@@ -376,7 +720,7 @@ impl Mutation {
     ) -> FieldResult<CommentParent> {
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
 
         // We could theoretically do this in one round trip, if we have ever
         // have performance issues. The query would roughly be:
@@ -385,13 +729,22 @@ impl Mutation {
 
         let db = context.data::<DataLoader<Database>>()?.loader();
 
-        let comment = db.comment_by_id(&input.comment_id).await?;
+        let comment = db
+            .comment_by_id(&input.comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)?;
 
         if comment.posted_by.id.0 != user.id.to_string() {
-            return Err("User attempted to delete another user's comment".into());
+            return Err(DailpError::Forbidden(
+                "User attempted to delete another user's comment".into(),
+            )
+            .extend());
         }
 
-        db.delete_comment(&input.comment_id).await?;
+        // Only editors may take other people's replies down with it.
+        db.delete_comment(&input.comment_id, user.is_in(UserGroup::Editors))
+            .await
+            .map_err(graphql_error)?;
 
         // We return the parent object, for GraphCache interop
         comment.parent(context).await
@@ -406,133 +759,817 @@ impl Mutation {
     ) -> FieldResult<CommentParent> {
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
 
         let db = context.data::<DataLoader<Database>>()?.loader();
 
-        db.insert_comment(
-            &user.id,
-            input.text_content,
-            &input.parent_id,
-            &input.parent_type,
-            &input.comment_type,
-        )
-        .await?;
+        let comment_id = db
+            .insert_comment(
+                &user.id,
+                input.text_content,
+                &input.parent_id,
+                &input.parent_type,
+                &input.comment_type,
+            )
+            .await
+            .map_err(graphql_error)?;
+
+        // We return the parent object, for GraphCache interop
+        let parent = input
+            .parent_type
+            .resolve(db, &input.parent_id, can_moderate(context))
+            .await?;
+        let document_id = db
+            .comment_document_id(&comment_id)
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::CommentPosted {
+                document_id: document_id.0,
+                comment_id,
+            },
+        );
+        Ok(parent)
+    }
+
+    /// Change the content of a comment.
+    /// Will fail if the user making the request is not the poster.
+    #[graphql(guard = "AuthGuard")]
+    async fn edit_comment(
+        &self,
+        context: &Context<'_>,
+        input: EditCommentInput,
+    ) -> FieldResult<Comment> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+
+        let comment = db
+            .comment_by_id(&input.comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)?;
+        if comment.posted_by.id.0 != user.id.to_string() {
+            return Err(DailpError::Forbidden(
+                "User attempted to edit another user's comment".into(),
+            )
+            .extend());
+        }
+
+        db.edit_comment(&input.comment_id, input.text_content, &input.comment_type)
+            .await
+            .map_err(graphql_error)?;
+        db.comment_by_id(&input.comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Hide a comment from readers, giving a reason for other editors.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn hide_comment(
+        &self,
+        context: &Context<'_>,
+        input: HideCommentInput,
+    ) -> FieldResult<Comment> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.set_comment_hidden(&input.comment_id, &user.id, Some(input.reason))
+            .await
+            .map_err(graphql_error)?;
+        db.comment_by_id(&input.comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Show a hidden comment to readers again.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn unhide_comment(
+        &self,
+        context: &Context<'_>,
+        comment_id: Uuid,
+    ) -> FieldResult<Comment> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.set_comment_hidden(&comment_id, &user.id, None)
+            .await
+            .map_err(graphql_error)?;
+        db.comment_by_id(&comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Mark a suggestion or question as resolved, or open it again.
+    /// Will fail unless the user making the request is the poster or an editor.
+    #[graphql(guard = "AuthGuard")]
+    async fn set_comment_resolved(
+        &self,
+        context: &Context<'_>,
+        comment_id: Uuid,
+        resolved: bool,
+    ) -> FieldResult<Comment> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+
+        let comment = db
+            .comment_by_id(&comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)?;
+        if comment.posted_by.id.0 != user.id.to_string() && !user.is_in(UserGroup::Editors) {
+            return Err(DailpError::Forbidden(
+                "Only the poster or an editor can resolve a comment".into(),
+            )
+            .extend());
+        }
+
+        db.set_comment_resolved(&comment_id, &user.id, resolved)
+            .await
+            .map_err(graphql_error)?;
+        db.comment_by_id(&comment_id, can_moderate(context))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Suggest an exact change to a word or paragraph, posted as a suggestion
+    /// comment for an editor to accept or reject.
+    #[graphql(guard = "AuthGuard")]
+    async fn propose_edit(
+        &self,
+        context: &Context<'_>,
+        input: ProposeEditInput,
+    ) -> FieldResult<CommentParent> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+
+        let (text_content, update) = input.into_parts().map_err(graphql_error)?;
+        let parent_id = update.target_id();
+        let parent_type = match update.target_type() {
+            EditTargetType::Word => CommentParentType::Word,
+            EditTargetType::Paragraph => CommentParentType::Paragraph,
+        };
+        let comment_id = db
+            .propose_edit(&user.id, text_content, update)
+            .await
+            .map_err(graphql_error)?;
 
         // We return the parent object, for GraphCache interop
-        input.parent_type.resolve(db, &input.parent_id).await
+        let parent = parent_type
+            .resolve(db, &parent_id, can_moderate(context))
+            .await?;
+        let document_id = db
+            .comment_document_id(&comment_id)
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::CommentPosted {
+                document_id: document_id.0,
+                comment_id,
+            },
+        );
+        Ok(parent)
+    }
+
+    /// Apply a proposed edit and resolve the suggestion it came with.
+    /// Fails if the word or paragraph changed since the edit was proposed.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn accept_proposed_edit(
+        &self,
+        context: &Context<'_>,
+        proposed_edit_id: Uuid,
+    ) -> FieldResult<ProposedEdit> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let proposal = db
+            .accept_proposed_edit(&proposed_edit_id, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        let event = match proposal.target_type {
+            EditTargetType::Word => DocumentEvent::WordUpdated {
+                document_id: db
+                    .word_by_id(&proposal.target_id)
+                    .await
+                    .map_err(graphql_error)?
+                    .position
+                    .document_id
+                    .0,
+                word_id: proposal.target_id,
+            },
+            EditTargetType::Paragraph => DocumentEvent::ParagraphUpdated {
+                document_id: db
+                    .paragraph_document_id(&proposal.target_id)
+                    .await
+                    .map_err(graphql_error)?
+                    .0,
+                paragraph_id: proposal.target_id,
+            },
+        };
+        publish_event(context, event);
+        Ok(proposal)
+    }
+
+    /// Turn down a proposed edit, explaining why in a note, and resolve the
+    /// suggestion it came with.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn reject_proposed_edit(
+        &self,
+        context: &Context<'_>,
+        proposed_edit_id: Uuid,
+        note: String,
+    ) -> FieldResult<ProposedEdit> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.reject_proposed_edit(&proposed_edit_id, &user.id, note)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Mutation for adding/changing contributor attributions
     #[graphql(
-        guard = "GroupGuard::new(UserGroup::Editors).or(GroupGuard::new(UserGroup::Contributors))"
+        guard = "GroupGuard::new(UserGroup::Editors).or(PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Document(contribution.document_id)))"
     )]
     async fn update_contributor_attribution(
         &self,
         context: &Context<'_>,
         contribution: UpdateContributorAttribution,
     ) -> FieldResult<Uuid> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .update_contributor_attribution(contribution)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     ///Mutation for deleting contributor attributions
     #[graphql(
-        guard = "GroupGuard::new(UserGroup::Editors).or(GroupGuard::new(UserGroup::Contributors))"
+        guard = "GroupGuard::new(UserGroup::Editors).or(PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Document(contribution.document_id)))"
     )]
     async fn delete_contributor_attribution(
         &self,
         context: &Context<'_>,
         contribution: DeleteContributorAttribution,
     ) -> FieldResult<Uuid> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .delete_contributor_attribution(contribution)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
-    /// Mutation for paragraph and translation editing
-    #[graphql(guard = "GroupGuard::new(UserGroup::Contributors)")]
-    async fn update_paragraph(
+    /// Change the profile and reading preferences of the signed-in user.
+    #[graphql(guard = "AuthGuard")]
+    async fn update_profile(
         &self,
         context: &Context<'_>,
-        paragraph: ParagraphUpdate,
-    ) -> FieldResult<DocumentParagraph> {
-        Ok(context
-            .data::<DataLoader<Database>>()?
+        input: UpdateProfileInput,
+    ) -> FieldResult<UserProfile> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let loader = context.data::<DataLoader<Database>>()?;
+        loader
             .loader()
-            .update_paragraph(paragraph)
-            .await?)
+            .update_user_profile(user.id, input)
+            .await
+            .map_err(graphql_error)?;
+        loader
+            .load_one(dailp::UserProfileById(user.id))
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That profile doesn't exist".into()).extend())
     }
 
-    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
-    async fn update_page(
+    /// Make an API key that acts as the signed-in user. The key is only shown
+    /// in the response to this mutation.
+    #[graphql(guard = "AuthGuard")]
+    async fn create_api_key(
         &self,
         context: &Context<'_>,
-        // Data encoded as JSON for now.
-        data: async_graphql::Json<dailp::page::Page>,
-    ) -> FieldResult<bool> {
+        input: CreateApiKeyInput,
+    ) -> FieldResult<NewApiKey> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        if context.data_opt::<ApiKeyAccess>().is_some() {
+            return Err(DailpError::Forbidden(
+                "API keys can't be used to make other API keys".into(),
+            )
+            .extend());
+        }
         context
             .data::<DataLoader<Database>>()?
             .loader()
-            .update_page(data.0)
-            .await?;
-        Ok(true)
+            .create_api_key(user, input)
+            .await
+            .map_err(graphql_error)
     }
 
-    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
-    async fn update_annotation(
-        &self,
-        context: &Context<'_>,
-        // Data encoded as JSON for now.
-        data: async_graphql::Json<dailp::annotation::Annotation>,
-    ) -> FieldResult<bool> {
+    /// Stop one of the signed-in user's API keys from working, returning its
+    /// ID.
+    #[graphql(guard = "AuthGuard")]
+    async fn revoke_api_key(&self, context: &Context<'_>, id: Uuid) -> FieldResult<Uuid> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         context
             .data::<DataLoader<Database>>()?
             .loader()
-            .update_annotation(data.0)
-            .await?;
-        Ok(true)
+            .revoke_api_key(id, user.id)
+            .await
+            .map_err(graphql_error)
     }
 
-    #[graphql(
-        guard = "GroupGuard::new(UserGroup::Editors).or(GroupGuard::new(UserGroup::Contributors))"
-    )]
-    async fn update_word(
+    /// Make a record for a new contributor.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn create_contributor(
         &self,
         context: &Context<'_>,
-        word: AnnotatedFormUpdate,
-    ) -> FieldResult<AnnotatedForm> {
+        input: CreateContributorInput,
+    ) -> FieldResult<ContributorDetails> {
         let database = context.data::<DataLoader<Database>>()?.loader();
-        Ok(database
-            .word_by_id(&database.update_word(word).await?)
-            .await?)
+        let id = database
+            .create_contributor(input)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .contributor_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That contributor doesn't exist".into()).extend())
     }
 
-    /// Adds a bookmark to the user's list of bookmarks.
-    #[graphql(guard = "AuthGuard")]
-    async fn add_bookmark(
+    /// Change the name or other details of a contributor.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn update_contributor(
         &self,
         context: &Context<'_>,
-        document_id: Uuid,
+        input: UpdateContributorInput,
+    ) -> FieldResult<ContributorDetails> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let id = database
+            .update_contributor(input)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .contributor_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That contributor doesn't exist".into()).extend())
+    }
+
+    /// Delete a contributor along with their attributions, returning their ID.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn delete_contributor(&self, context: &Context<'_>, id: Uuid) -> FieldResult<Uuid> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .delete_contributor(id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(id)
+    }
+
+    /// Combine duplicate records of the same person, moving all of their
+    /// attributions to the record that is kept.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn merge_contributors(
+        &self,
+        context: &Context<'_>,
+        input: MergeContributorsInput,
+    ) -> FieldResult<ContributorDetails> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let id = database
+            .merge_contributors(input)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .contributor_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That contributor doesn't exist".into()).extend())
+    }
+
+    /// Put someone in a user group once they sign up and confirm their
+    /// account with the given email, replacing any earlier invitation.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn invite_user(
+        &self,
+        context: &Context<'_>,
+        input: InviteUserInput,
+    ) -> FieldResult<RoleInvitation> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let email = database
+            .invite_user(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .role_invitations(Some(&email))
+            .await
+            .map_err(graphql_error)?
+            .pop()
+            .ok_or_else(|| DailpError::NotFound("That invitation doesn't exist".into()).extend())
+    }
+
+    /// Take back an invitation, returning its email. Anyone who already
+    /// accepted it stays in their group.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn remove_invitation(&self, context: &Context<'_>, email: String) -> FieldResult<String> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .remove_invitation(&email)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Give a user a role on one edited collection, document collection or
+    /// document, replacing any role they already had there.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn grant_permission(
+        &self,
+        context: &Context<'_>,
+        input: GrantPermissionInput,
+    ) -> FieldResult<PermissionGrant> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let user_id = input.user_id;
+        let id = database
+            .grant_permission(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .permission_grants(Some(user_id))
+            .await
+            .map_err(graphql_error)?
+            .into_iter()
+            .find(|grant| grant.id == id)
+            .ok_or_else(|| {
+                DailpError::NotFound("That permission grant doesn't exist".into()).extend()
+            })
+    }
+
+    /// Take away a role given with `grantPermission`, returning its ID.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn revoke_permission(&self, context: &Context<'_>, id: Uuid) -> FieldResult<Uuid> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .revoke_permission(id)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Mutation for paragraph and translation editing
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Paragraph(paragraph.id))"
+    )]
+    async fn update_paragraph(
+        &self,
+        context: &Context<'_>,
+        paragraph: ParagraphUpdate,
+    ) -> FieldResult<DocumentParagraph> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let paragraph = db
+            .update_paragraph(paragraph, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::ParagraphUpdated {
+                document_id: db
+                    .paragraph_document_id(&paragraph.id)
+                    .await
+                    .map_err(graphql_error)?
+                    .0,
+                paragraph_id: paragraph.id,
+            },
+        );
+        Ok(paragraph)
+    }
+
+    /// Undo the given paragraph revision, putting the translation back the
+    /// way it was before that edit.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Revision(revision_id))"
+    )]
+    async fn revert_paragraph(
+        &self,
+        context: &Context<'_>,
+        revision_id: Uuid,
+    ) -> FieldResult<DocumentParagraph> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let paragraph = db
+            .revert_paragraph(&revision_id, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::ParagraphUpdated {
+                document_id: db
+                    .paragraph_document_id(&paragraph.id)
+                    .await
+                    .map_err(graphql_error)?
+                    .0,
+                paragraph_id: paragraph.id,
+            },
+        );
+        Ok(paragraph)
+    }
+
+    /// Make a new content page, as a draft unless told otherwise.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn create_page(
+        &self,
+        context: &Context<'_>,
+        input: CreatePageInput,
+    ) -> FieldResult<dailp::page::Page> {
+        let path = input.id.clone();
+        let db = context.data::<DataLoader<Database>>()?;
+        db.loader()
+            .create_page(input)
+            .await
+            .map_err(graphql_error)?;
+        db.load_one(dailp::PageId(path))
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That page doesn't exist".into()).extend())
+    }
+
+    /// Change the title, content, or status of a content page.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn update_page(
+        &self,
+        context: &Context<'_>,
+        input: UpdatePageInput,
+    ) -> FieldResult<dailp::page::Page> {
+        let path = input.id.clone();
+        let db = context.data::<DataLoader<Database>>()?;
+        db.loader()
+            .update_page(input)
+            .await
+            .map_err(graphql_error)?;
+        db.load_one(dailp::PageId(path))
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That page doesn't exist".into()).extend())
+    }
+
+    /// Delete a content page, returning its path.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn delete_page(&self, context: &Context<'_>, id: String) -> FieldResult<String> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .delete_page(&id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(id)
+    }
+
+    /// Put the given pages first in the site order, followed by any others,
+    /// and return all pages in their new order.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn reorder_pages(
+        &self,
+        context: &Context<'_>,
+        ids: Vec<String>,
+    ) -> FieldResult<Vec<dailp::page::Page>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.reorder_pages(&ids).await.map_err(graphql_error)?;
+        db.all_pages(true).await.map_err(graphql_error)
+    }
+
+    /// Write a new annotation on a word, a page image or a region of one, or
+    /// in reply to another annotation.
+    #[graphql(guard = "PermissionGuard::all(UserGroup::Editors, input.permission_targets())")]
+    async fn create_annotation(
+        &self,
+        context: &Context<'_>,
+        input: CreateAnnotationInput,
+    ) -> FieldResult<Annotation> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let annotation_id = db
+            .create_annotation(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        db.annotation_by_id(&annotation_id)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Change the content or region of an annotation.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Annotation(input.id))"
+    )]
+    async fn update_annotation(
+        &self,
+        context: &Context<'_>,
+        input: UpdateAnnotationInput,
+    ) -> FieldResult<Annotation> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let annotation_id = input.id;
+        db.update_annotation(input).await.map_err(graphql_error)?;
+        db.annotation_by_id(&annotation_id)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Delete an annotation along with all replies to it, returning its ID.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Annotation(annotation_id))"
+    )]
+    async fn delete_annotation(
+        &self,
+        context: &Context<'_>,
+        annotation_id: Uuid,
+    ) -> FieldResult<Uuid> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .delete_annotation(&annotation_id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(annotation_id)
+    }
+
+    #[graphql(
+        guard = "GroupGuard::new(UserGroup::Editors).or(PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Word(word.id)))"
+    )]
+    async fn update_word(
+        &self,
+        context: &Context<'_>,
+        word: AnnotatedFormUpdate,
+    ) -> FieldResult<AnnotatedForm> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let word = database
+            .word_by_id(
+                &database
+                    .update_word(word, &user.id)
+                    .await
+                    .map_err(graphql_error)?,
+            )
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::WordUpdated {
+                document_id: word.position.document_id.0,
+                word_id: word.id.unwrap_or_default(),
+            },
+        );
+        Ok(word)
+    }
+
+    /// Update many words at once in one transaction, such as to fix the same
+    /// mistake across a document. Every update is checked before any are
+    /// saved, and if any of them can't be saved then none are.
+    #[graphql(
+        guard = "GroupGuard::new(UserGroup::Editors).or(PermissionGuard::all(UserGroup::Contributors, words.iter().map(|w| PermissionTarget::Word(w.id)).collect()))"
+    )]
+    async fn update_words(
+        &self,
+        context: &Context<'_>,
+        words: Vec<AnnotatedFormUpdate>,
+    ) -> FieldResult<Vec<WordUpdateResult>> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let results = database
+            .update_words(words, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        for result in &results {
+            if let Some(document_id) = result.document_id {
+                publish_event(
+                    context,
+                    DocumentEvent::WordUpdated {
+                        document_id,
+                        word_id: result.word_id,
+                    },
+                );
+            }
+        }
+        Ok(results)
+    }
+
+    /// Replace text in one field of every word in a document or collection.
+    /// Use `dryRun` to preview which words would change. Replacing across a
+    /// whole collection needs the Editors group, not just a role on it.
+    #[graphql(guard = "PermissionGuard::all(UserGroup::Editors, input.permission_targets())")]
+    async fn find_and_replace(
+        &self,
+        context: &Context<'_>,
+        input: FindAndReplaceInput,
+    ) -> FieldResult<Vec<Replacement>> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let replacements = database
+            .find_and_replace(&input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        if !input.dry_run {
+            for replacement in &replacements {
+                publish_event(
+                    context,
+                    DocumentEvent::WordUpdated {
+                        document_id: replacement.document_id,
+                        word_id: replacement.word_id,
+                    },
+                );
+            }
+        }
+        Ok(replacements)
+    }
+
+    /// Undo the given word revision, putting the word and its segments back
+    /// the way they were before that edit.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Revision(revision_id))"
+    )]
+    async fn revert_word(
+        &self,
+        context: &Context<'_>,
+        revision_id: Uuid,
+    ) -> FieldResult<AnnotatedForm> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let word = database
+            .word_by_id(
+                &database
+                    .revert_word(&revision_id, &user.id)
+                    .await
+                    .map_err(graphql_error)?,
+            )
+            .await
+            .map_err(graphql_error)?;
+        publish_event(
+            context,
+            DocumentEvent::WordUpdated {
+                document_id: word.position.document_id.0,
+                word_id: word.id.unwrap_or_default(),
+            },
+        );
+        Ok(word)
+    }
+
+    /// Adds a bookmark to the user's list of bookmarks.
+    #[graphql(guard = "AuthGuard")]
+    async fn add_bookmark(
+        &self,
+        context: &Context<'_>,
+        document_id: Uuid,
     ) -> FieldResult<AnnotatedDoc> {
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         context
             .data::<DataLoader<Database>>()?
             .loader()
             .add_bookmark(document_id, user.id)
-            .await?;
-        Ok(context
+            .await
+            .map_err(graphql_error)?;
+        context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::DocumentId(document_id))
-            .await?
-            .ok_or_else(|| anyhow::format_err!("Failed to load document"))?)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That document doesn't exist".into()).extend())
     }
 
     /// Removes a bookmark from a user's list of bookmarks
@@ -544,21 +1581,25 @@ impl Mutation {
     ) -> FieldResult<AnnotatedDoc> {
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         context
             .data::<DataLoader<Database>>()?
             .loader()
             .remove_bookmark(document_id, user.id)
-            .await?;
-        Ok(context
+            .await
+            .map_err(graphql_error)?;
+        context
             .data::<DataLoader<Database>>()?
             .load_one(dailp::DocumentId(document_id))
-            .await?
-            .ok_or_else(|| anyhow::format_err!("Failed to load document"))?)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That document doesn't exist".into()).extend())
     }
 
     /// Decide if a piece audio should be included in edited collection
-    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Word(input.word_id))"
+    )]
     async fn curate_word_audio(
         &self,
         context: &Context<'_>,
@@ -567,7 +1608,7 @@ impl Mutation {
         // TODO: should this return a typed id ie. AudioSliceId?
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         let word_id = context
             .data::<DataLoader<Database>>()?
             .loader()
@@ -577,17 +1618,24 @@ impl Mutation {
                 input.include_in_edited_collection,
                 &user.id,
             )
-            .await?;
-        Ok(context
+            .await
+            .map_err(graphql_error)?;
+        context
             .data::<DataLoader<Database>>()?
             .loader()
-            .word_by_id(&word_id.ok_or_else(|| anyhow::format_err!("Word audio not found"))?)
-            .await?)
+            .word_by_id(
+                &word_id
+                    .ok_or_else(|| DailpError::NotFound("Word audio not found".into()).extend())?,
+            )
+            .await
+            .map_err(graphql_error)
     }
 
     /// Attach audio that has already been uploaded to S3 to a particular word
     /// Assumes user requesting mutation recoreded the audio
-    #[graphql(guard = "GroupGuard::new(UserGroup::Contributors)")]
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Word(input.word_id))"
+    )]
     async fn attach_audio_to_word(
         &self,
         context: &Context<'_>,
@@ -596,31 +1644,293 @@ impl Mutation {
         // TODO: should this return a typed id ie. AudioSliceId?
         let user = context
             .data_opt::<UserInfo>()
-            .ok_or_else(|| anyhow::format_err!("User is not signed in"))?;
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
         let _media_slice_id = context
             .data::<DataLoader<Database>>()?
             .loader()
             .attach_audio_to_word(&input, &user.id)
-            .await?;
-        Ok(context
+            .await
+            .map_err(graphql_error)?;
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .word_by_id(&input.word_id)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
-    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Document(document.id))"
+    )]
     async fn update_document_metadata(
         &self,
         context: &Context<'_>,
         document: DocumentMetadataUpdate,
     ) -> FieldResult<Uuid> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .update_document_metadata(document)
-            .await?)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Make a new, empty document. Add pages to it with `addDocumentPage`.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::DocumentCollection(input.group_id))"
+    )]
+    async fn create_document(
+        &self,
+        context: &Context<'_>,
+        input: CreateDocumentInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let db = context.data::<DataLoader<Database>>()?;
+        let id = db
+            .loader()
+            .create_document(input)
+            .await
+            .map_err(graphql_error)?;
+        db.load_one(dailp::DocumentId(id))
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That document doesn't exist".into()).extend())
+    }
+
+    /// Add an empty page to the end of a document.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Document(input.document_id))"
+    )]
+    async fn add_document_page(
+        &self,
+        context: &Context<'_>,
+        input: AddDocumentPageInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .add_document_page(input)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
     }
+
+    /// Add a paragraph of new words to a page.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Page(input.page_id))"
+    )]
+    async fn add_paragraph(
+        &self,
+        context: &Context<'_>,
+        input: AddParagraphInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database.add_paragraph(input).await.map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Break a paragraph into two before the given word.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Paragraph(input.paragraph_id))"
+    )]
+    async fn split_paragraph(
+        &self,
+        context: &Context<'_>,
+        input: SplitParagraphInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .split_paragraph(input)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Join a paragraph with the one after it.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Paragraph(input.paragraph_id))"
+    )]
+    async fn merge_paragraphs(
+        &self,
+        context: &Context<'_>,
+        input: MergeParagraphsInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .merge_paragraphs(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Add a new word to a paragraph.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Paragraph(input.paragraph_id))"
+    )]
+    async fn insert_word(
+        &self,
+        context: &Context<'_>,
+        input: InsertWordInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .insert_word_in_paragraph(input)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Delete a word and any comments on it. The only word in a paragraph
+    /// can't be deleted.
+    #[graphql(guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Word(word_id))")]
+    async fn delete_word(&self, context: &Context<'_>, word_id: Uuid) -> FieldResult<AnnotatedDoc> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database.delete_word(word_id).await.map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Break a word into two.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Word(input.word_id))"
+    )]
+    async fn split_word(
+        &self,
+        context: &Context<'_>,
+        input: SplitWordInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .split_word(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Join a word with the one after it in the same paragraph.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Word(input.word_id))"
+    )]
+    async fn merge_words(
+        &self,
+        context: &Context<'_>,
+        input: MergeWordsInput,
+    ) -> FieldResult<AnnotatedDoc> {
+        let user = context
+            .data_opt::<UserInfo>()
+            .ok_or_else(|| DailpError::Forbidden("User is not signed in".into()).extend())?;
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let document_id = database
+            .merge_words(input, &user.id)
+            .await
+            .map_err(graphql_error)?;
+        restructured(context, document_id).await
+    }
+
+    /// Make a new edited collection with no chapters.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn create_edited_collection(
+        &self,
+        context: &Context<'_>,
+        input: CreateEditedCollectionInput,
+    ) -> FieldResult<EditedCollection> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .create_edited_collection(input)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Add a chapter to an edited collection. Showing a document in it also
+    /// needs rights on that document.
+    #[graphql(guard = "PermissionGuard::all(UserGroup::Editors, input.permission_targets())")]
+    async fn add_chapter(
+        &self,
+        context: &Context<'_>,
+        input: AddChapterInput,
+    ) -> FieldResult<CollectionChapter> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let id = database.add_chapter(input).await.map_err(graphql_error)?;
+        database
+            .chapter_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That chapter doesn't exist".into()).extend())
+    }
+
+    /// Rename a chapter or change its section, document or text. Showing a
+    /// different document also needs rights on that document.
+    #[graphql(guard = "PermissionGuard::all(UserGroup::Editors, input.permission_targets())")]
+    async fn update_chapter(
+        &self,
+        context: &Context<'_>,
+        input: UpdateChapterInput,
+    ) -> FieldResult<CollectionChapter> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let id = database
+            .update_chapter(input)
+            .await
+            .map_err(graphql_error)?;
+        database
+            .chapter_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That chapter doesn't exist".into()).extend())
+    }
+
+    /// Move a chapter under a different parent, or reorder it among its
+    /// siblings.
+    #[graphql(
+        guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Chapter(input.id))"
+    )]
+    async fn move_chapter(
+        &self,
+        context: &Context<'_>,
+        input: MoveChapterInput,
+    ) -> FieldResult<CollectionChapter> {
+        let database = context.data::<DataLoader<Database>>()?.loader();
+        let id = database.move_chapter(input).await.map_err(graphql_error)?;
+        database
+            .chapter_by_id(id)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("That chapter doesn't exist".into()).extend())
+    }
+
+    /// Delete a chapter along with all of its subchapters. Documents in them
+    /// are kept.
+    #[graphql(guard = "PermissionGuard::new(UserGroup::Editors, PermissionTarget::Chapter(id))")]
+    async fn delete_chapter(&self, context: &Context<'_>, id: Uuid) -> FieldResult<Uuid> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .delete_chapter(id)
+            .await
+            .map_err(graphql_error)?;
+        Ok(id)
+    }
+}
+
+/// Tell subscribers that a document changed shape, and load it as it is now.
+async fn restructured(context: &Context<'_>, document_id: Uuid) -> FieldResult<AnnotatedDoc> {
+    publish_event(context, DocumentEvent::DocumentRestructured { document_id });
+    context
+        .data::<DataLoader<Database>>()?
+        .load_one(dailp::DocumentId(document_id))
+        .await
+        .map_err(graphql_error)?
+        .ok_or_else(|| DailpError::NotFound("That document doesn't exist".into()).extend())
+}
+
+/// Whether the current user is an editor, who can see draft pages.
+fn is_editor(context: &Context<'_>) -> bool {
+    context
+        .data_opt::<UserInfo>()
+        .map_or(false, |user| user.is_in(UserGroup::Editors))
 }
 
 #[derive(async_graphql::SimpleObject)]
@@ -0,0 +1,319 @@
+//! HTTP routes served by both the lambda handler and the local server. Each of
+//! them turns its requests into plain [`http`] requests for a [`Router`] and
+//! sends back the responses it gives, so the two serve exactly the same API.
+
+use crate::{
+    cognito,
+    query::{Mutation, Query},
+    subscription::Subscription,
+};
+use dailp::{
+    api_key::API_KEY_HEADER,
+    async_graphql::{
+        self,
+        http::{playground_source, GraphQLPlaygroundConfig},
+        Schema,
+    },
+    auth::{ApiGatewayUserInfo, UserInfo},
+    metrics, DailpError, Database,
+};
+use http::{header, HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use jsonwebtoken::jwk::JwkSet;
+use log::{error, info};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Our GraphQL schema, with every query, mutation and subscription.
+pub type DailpSchema = Schema<Query, Mutation, Subscription>;
+
+/// Where a request should go.
+#[derive(Debug, PartialEq)]
+enum Route<'a> {
+    /// CORS preflight request from a browser
+    Preflight,
+    /// GraphQL Playground, for trying out queries in the browser
+    Playground,
+    /// GraphQL queries and mutations
+    GraphQL,
+    /// IIIF manifest for the document with this short name
+    Manifest(&'a str),
+    /// Whether the server is up
+    Health,
+    /// Whether the server can reach the database
+    Ready,
+    /// Prometheus metrics
+    Metrics,
+}
+
+impl<'a> Route<'a> {
+    /// Find the route for a request. Paths may start with a prefix, like the
+    /// stage name that API Gateway adds.
+    fn find(method: &Method, path: &'a str) -> Option<Self> {
+        let mut segments = path
+            .split('/')
+            .skip_while(|segment| {
+                !matches!(
+                    *segment,
+                    "graphql" | "graphql-edit" | "manifests" | "healthz" | "readyz" | "metrics"
+                )
+            })
+            .filter(|segment| !segment.is_empty());
+        let route = match (segments.next()?, segments.next()) {
+            (_, _) if method == Method::OPTIONS => Self::Preflight,
+            ("graphql" | "graphql-edit", None) if method == Method::GET => Self::Playground,
+            ("graphql" | "graphql-edit", None) if method == Method::POST => Self::GraphQL,
+            ("manifests", Some(name)) if method == Method::GET => Self::Manifest(name),
+            ("healthz", None) if method == Method::GET => Self::Health,
+            ("readyz", None) if method == Method::GET => Self::Ready,
+            ("metrics", None) if method == Method::GET => Self::Metrics,
+            _ => return None,
+        };
+        // Nothing may follow the route.
+        segments.next().is_none().then_some(route)
+    }
+}
+
+/// Handles every HTTP request to the API except for subscriptions, which
+/// need a long-lived connection.
+pub struct Router {
+    schema: DailpSchema,
+    database: Database,
+    /// Keys that identity tokens are checked against, loaded on the first
+    /// request that has one
+    keys: tokio::sync::OnceCell<JwkSet>,
+    /// Where GraphQL Playground should listen for subscriptions, if this
+    /// server has them
+    subscription_endpoint: Option<String>,
+}
+
+impl Router {
+    /// Serve the given schema, using the database to look up users and API
+    /// keys. GraphQL Playground listens for subscriptions at the given path,
+    /// if there is one.
+    pub fn new(
+        schema: DailpSchema,
+        database: Database,
+        subscription_endpoint: Option<&str>,
+    ) -> Self {
+        Self {
+            schema,
+            database,
+            keys: tokio::sync::OnceCell::new(),
+            subscription_endpoint: subscription_endpoint.map(str::to_owned),
+        }
+    }
+
+    /// Respond to one request. Requests that come through API Gateway carry
+    /// the claims it already checked about the signed-in user.
+    pub async fn handle(
+        &self,
+        req: Request<Vec<u8>>,
+        gateway_claims: Option<serde_json::Value>,
+    ) -> Response<String> {
+        info!("{} {}", req.method(), req.uri());
+        let path = req.uri().path().to_owned();
+        let result = match Route::find(req.method(), &path) {
+            Some(Route::Preflight) => Ok(Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .body(String::new())
+                .unwrap()),
+            Some(Route::Playground) => Ok(self.playground(&path)),
+            Some(Route::GraphQL) => self.graphql(req, gateway_claims).await,
+            Some(Route::Manifest(name)) => self.manifest(name, req.uri().to_string()).await,
+            Some(Route::Health) => Ok(text(StatusCode::OK, "ok")),
+            Some(Route::Ready) => Ok(self.ready().await),
+            Some(Route::Metrics) => self.metrics(),
+            None => Err(DailpError::NotFound(format!(
+                "No route for {} {}",
+                req.method(),
+                path
+            ))),
+        };
+        let mut res = result.unwrap_or_else(error_response);
+        add_cors_headers(res.headers_mut());
+        res
+    }
+
+    fn playground(&self, path: &str) -> Response<String> {
+        // The playground needs to know the path to the GraphQL endpoint.
+        let mut config = GraphQLPlaygroundConfig::new(path);
+        if let Some(endpoint) = &self.subscription_endpoint {
+            config = config.subscription_endpoint(endpoint);
+        }
+        Response::builder()
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(playground_source(config))
+            .unwrap()
+    }
+
+    async fn graphql(
+        &self,
+        req: Request<Vec<u8>>,
+        gateway_claims: Option<serde_json::Value>,
+    ) -> Result<Response<String>, DailpError> {
+        let user = self.user(&req, gateway_claims).await?;
+        if let Some(user) = &user {
+            self.database.upsert_dailp_user(user).await?;
+        }
+        let api_key = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok());
+
+        let gql_req: async_graphql::Request = serde_json::from_slice(req.body())
+            .map_err(|err| DailpError::Validation(format!("Invalid GraphQL request: {}", err)))?;
+        // Requests with an API key act as its owner, unless they're signed in.
+        let gql_req = if let Some(user) = user {
+            gql_req.data(user)
+        } else if let Some(api_key) = api_key {
+            match self
+                .database
+                .use_api_key(api_key, gql_req.operation_name.as_deref())
+                .await?
+            {
+                Some((user, access)) => gql_req.data(user).data(access),
+                None => {
+                    return json(&async_graphql::Response::from_errors(vec![
+                        DailpError::Forbidden("Invalid API key".into()).server_error(),
+                    ]))
+                }
+            }
+        } else {
+            gql_req
+        };
+        json(&self.schema.execute(gql_req).await)
+    }
+
+    /// The user making the request: either the one in the claims that API
+    /// Gateway already checked, or the one whose identity token is in the `Authorization`
+    /// header.
+    async fn user(
+        &self,
+        req: &Request<Vec<u8>>,
+        gateway_claims: Option<serde_json::Value>,
+    ) -> Result<Option<UserInfo>, DailpError> {
+        let invalid_token = |err: &dyn std::fmt::Debug| {
+            info!("Invalid identity token: {:?}", err);
+            DailpError::Forbidden("Invalid identity token".into())
+        };
+        if let Some(claims) = gateway_claims {
+            return serde_json::from_value::<ApiGatewayUserInfo>(claims)
+                .map(|ApiGatewayUserInfo(user)| Some(user))
+                .map_err(|err| invalid_token(&err));
+        }
+        let authorization = match req.headers().get(header::AUTHORIZATION) {
+            Some(value) => value.to_str().map_err(|err| invalid_token(&err))?,
+            None => return Ok(None),
+        };
+        // Missing keys or settings are our problem, not a bad token.
+        let keys = self
+            .keys
+            .get_or_try_init(cognito::load_jwk_set)
+            .await
+            .map_err(DailpError::Upstream)?;
+        let settings = cognito::TokenSettings::from_env().map_err(DailpError::Upstream)?;
+        cognito::user_info_from_authorization(authorization, keys, &settings)
+            .map(Some)
+            .map_err(|err| invalid_token(&err))
+    }
+
+    /// Ready once the database answers, so a load balancer can hold off
+    /// sending requests until then.
+    async fn ready(&self) -> Response<String> {
+        let ping = tokio::time::timeout(Duration::from_secs(5), self.database.ping()).await;
+        match ping {
+            Ok(Ok(())) => text(StatusCode::OK, "ok"),
+            Ok(Err(err)) => {
+                error!("Database isn't ready: {:?}", err);
+                text(StatusCode::SERVICE_UNAVAILABLE, "database unavailable")
+            }
+            Err(_) => {
+                error!("Database didn't answer in time");
+                text(StatusCode::SERVICE_UNAVAILABLE, "database unavailable")
+            }
+        }
+    }
+
+    fn metrics(&self) -> Result<Response<String>, DailpError> {
+        let body = metrics::gather(&self.database)?;
+        Ok(Response::builder()
+            .header(header::CONTENT_TYPE, metrics::CONTENT_TYPE)
+            .body(body)
+            .unwrap())
+    }
+
+    async fn manifest(&self, name: &str, url: String) -> Result<Response<String>, DailpError> {
+        json(&self.database.document_manifest(name, url).await?)
+    }
+}
+
+/// Let web pages anywhere call the API with an identity token or API key.
+fn add_cors_headers(headers: &mut HeaderMap) {
+    let cors = [
+        (header::ACCESS_CONTROL_ALLOW_ORIGIN, "*"),
+        (header::ACCESS_CONTROL_ALLOW_METHODS, "GET, POST, OPTIONS"),
+        (
+            header::ACCESS_CONTROL_ALLOW_HEADERS,
+            "Authorization, X-API-Key, Content-Type",
+        ),
+        (header::ACCESS_CONTROL_ALLOW_CREDENTIALS, "true"),
+    ];
+    for (name, value) in cors {
+        headers.insert(name, HeaderValue::from_static(value));
+    }
+}
+
+fn text(status: StatusCode, body: &str) -> Response<String> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(body.to_owned())
+        .unwrap()
+}
+
+fn json(body: &impl Serialize) -> Result<Response<String>, DailpError> {
+    let body = serde_json::to_string(body).map_err(|err| DailpError::Upstream(err.into()))?;
+    Ok(Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap())
+}
+
+/// Respond with the status for this kind of error, and a body shaped like
+/// GraphQL errors.
+fn error_response(err: DailpError) -> Response<String> {
+    let body = serde_json::json!({ "errors": [err.server_error()] });
+    Response::builder()
+        .status(err.status())
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.to_string())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_routes_after_any_prefix() {
+        assert_eq!(Route::find(&Method::POST, "/graphql"), Some(Route::GraphQL));
+        assert_eq!(
+            Route::find(&Method::GET, "/dev/graphql-edit"),
+            Some(Route::Playground)
+        );
+        assert_eq!(
+            Route::find(&Method::GET, "/manifests/DF2018/"),
+            Some(Route::Manifest("DF2018"))
+        );
+        assert_eq!(
+            Route::find(&Method::OPTIONS, "/graphql-edit"),
+            Some(Route::Preflight)
+        );
+        assert_eq!(Route::find(&Method::GET, "/dev/readyz"), Some(Route::Ready));
+        assert_eq!(Route::find(&Method::GET, "/metrics"), Some(Route::Metrics));
+        assert_eq!(Route::find(&Method::GET, "/manifests"), None);
+        assert_eq!(Route::find(&Method::GET, "/graphql/extra"), None);
+        assert_eq!(Route::find(&Method::DELETE, "/graphql"), None);
+        assert_eq!(Route::find(&Method::GET, "/other"), None);
+    }
+}
@@ -1,21 +1,16 @@
-use log::error;
-
 mod cognito;
 mod query;
+mod router;
+mod subscription;
 
 use {
-    dailp::async_graphql::{
-        dataloader::DataLoader,
-        http::{playground_source, GraphQLPlaygroundConfig},
-        EmptySubscription, Schema,
-    },
-    dailp::auth::UserInfo,
-    tide::{
-        http::headers::HeaderValue,
-        http::mime,
-        security::{CorsMiddleware, Origin},
-        Body, Endpoint, Response, StatusCode,
-    },
+    dailp::api_key::ReadOnlyApiKeys,
+    dailp::async_graphql::{dataloader::DataLoader, Schema},
+    dailp::metrics::Metrics,
+    dailp::{EventBus, LocalEventBus},
+    router::Router,
+    std::sync::Arc,
+    tide::{Endpoint, Response},
 };
 
 use dailp::async_graphql::extensions::ApolloTracing;
@@ -26,111 +21,57 @@ async fn main() -> tide::Result<()> {
     pretty_env_logger::init();
     let mut app = tide::new();
 
-    // create schema
-    let schema = Schema::build(query::Query, query::Mutation, EmptySubscription)
-        .extension(ApolloTracing)
-        .data(DataLoader::new(
-            dailp::Database::connect(None)?,
-            tokio::spawn,
-        ))
-        .finish();
+    let events: Arc<dyn EventBus> = Arc::new(LocalEventBus::default());
 
-    let authed_schema = Schema::build(query::Query, query::Mutation, EmptySubscription)
+    // create schema
+    let schema = Schema::build(query::Query, query::Mutation, subscription::Subscription)
         .extension(ApolloTracing)
+        .extension(ReadOnlyApiKeys)
+        .extension(Metrics)
+        .data(events)
         .data(DataLoader::new(
             dailp::Database::connect(None)?,
             tokio::spawn,
         ))
         .finish();
 
-    let cors = CorsMiddleware::new()
-        .allow_methods("GET, POST, OPTIONS".parse::<HeaderValue>().unwrap())
-        .allow_origin(Origin::from("*"))
-        .allow_headers(
-            "Authorization, content-type"
-                .parse::<HeaderValue>()
-                .unwrap(),
-        )
-        .allow_credentials(true);
-
-    app.with(cors);
-
-    // add tide endpoint
-    app.at("/graphql").post(async_graphql_tide::graphql(schema));
-    app.at("/graphql-edit").post(AuthedEndpoint::new(
-        authed_schema,
+    // Subscriptions need a websocket, which only this server has.
+    app.at("/graphql-ws")
+        .get(async_graphql_tide::GraphQLSubscription::new(schema.clone()).build());
+    // Everything else goes through the same routes as the lambda.
+    let router = Arc::new(Router::new(
+        schema,
         dailp::Database::connect(None)?,
+        Some("/graphql-ws"),
     ));
-
-    // enable graphql playground
-    app.at("/graphql").get(|_| async move {
-        Ok(Response::builder(StatusCode::Ok)
-            .body(Body::from_string(playground_source(
-                // note that the playground needs to know
-                // the path to the graphql endpoint
-                GraphQLPlaygroundConfig::new("/graphql"),
-            )))
-            .content_type(mime::HTML)
-            .build())
-    });
-    app.at("/graphql-edit").get(|_| async move {
-        Ok(Response::builder(StatusCode::Ok)
-            .body(Body::from_string(playground_source(
-                // note that the playground needs to know
-                // the path to the graphql endpoint
-                GraphQLPlaygroundConfig::new("/graphql-edit"),
-            )))
-            .content_type(mime::HTML)
-            .build())
-    });
+    app.at("/").all(RouterEndpoint(router.clone()));
+    app.at("*").all(RouterEndpoint(router));
 
     Ok(app.listen("127.0.0.1:8080").await?)
 }
 
-struct AuthedEndpoint {
-    schema: Schema<query::Query, query::Mutation, EmptySubscription>,
-    database: dailp::Database,
-}
-
-impl AuthedEndpoint {
-    fn new(
-        schema: Schema<query::Query, query::Mutation, EmptySubscription>,
-        database: dailp::Database,
-    ) -> Self {
-        Self { schema, database }
-    }
-}
+/// Serves tide requests through our router.
+struct RouterEndpoint(Arc<Router>);
 
 #[async_trait::async_trait]
-impl Endpoint<()> for AuthedEndpoint {
-    async fn call(&self, req: tide::Request<()>) -> tide::Result {
-        let authorization = req.header("Authorization");
-
-        let user: Option<UserInfo> = authorization
-            .and_then(|values| values.iter().next())
-            .and_then(
-                |value| match cognito::user_info_from_authorization(value.as_str()) {
-                    Ok(value) => Some(value.into()),
-                    Err(err) => {
-                        error!("{:?}", err);
-                        None
-                    }
-                },
-            );
-
-        if let Some(user_id) = user.as_ref().map(|u| u.id) {
-            self.database.upsert_dailp_user(user_id).await?;
+impl Endpoint<()> for RouterEndpoint {
+    async fn call(&self, mut req: tide::Request<()>) -> tide::Result {
+        let mut builder = http::Request::builder()
+            .method(req.method().as_ref())
+            .uri(req.url().as_str());
+        for (name, values) in req.iter() {
+            for value in values {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
         }
+        let body = req.body_bytes().await?;
+        let res = self.0.handle(builder.body(body)?, None).await;
 
-        let req = async_graphql_tide::receive_request(req).await?;
-        let req = if let Some(user) = user {
-            req.data(user)
-        } else {
-            req
-        };
-
-        let gql_resp = self.schema.execute(req).await;
-
-        return async_graphql_tide::respond(gql_resp);
+        let mut response = Response::new(res.status().as_u16());
+        for (name, value) in res.headers() {
+            response.append_header(name.as_str(), value.to_str()?);
+        }
+        response.set_body(res.into_body());
+        Ok(response)
     }
 }
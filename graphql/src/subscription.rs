@@ -0,0 +1,118 @@
+//! Live updates to documents for clients connected over WebSocket.
+
+use {
+    dailp::async_graphql::{self, dataloader::DataLoader, Context, FieldResult},
+    dailp::{
+        comment::{can_moderate, Comment},
+        graphql_error, AnnotatedDoc, AnnotatedForm, DailpError, Database, DocumentEvent,
+        DocumentId, DocumentParagraph, EventBus, Uuid,
+    },
+    futures::{Stream, StreamExt},
+    std::sync::Arc,
+};
+
+pub struct Subscription;
+
+#[async_graphql::Subscription]
+impl Subscription {
+    /// Words in the given document as they are edited
+    async fn word_updated<'ctx>(
+        &self,
+        context: &Context<'ctx>,
+        document_id: Uuid,
+    ) -> FieldResult<impl Stream<Item = FieldResult<AnnotatedForm>> + 'ctx> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        Ok(
+            document_events(context, document_id)?.filter_map(move |event| async move {
+                match event {
+                    DocumentEvent::WordUpdated { word_id, .. } => {
+                        Some(db.word_by_id(&word_id).await.map_err(graphql_error))
+                    }
+                    _ => None,
+                }
+            }),
+        )
+    }
+
+    /// Paragraphs in the given document as they are edited
+    async fn paragraph_updated<'ctx>(
+        &self,
+        context: &Context<'ctx>,
+        document_id: Uuid,
+    ) -> FieldResult<impl Stream<Item = FieldResult<DocumentParagraph>> + 'ctx> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        Ok(
+            document_events(context, document_id)?.filter_map(move |event| async move {
+                match event {
+                    DocumentEvent::ParagraphUpdated { paragraph_id, .. } => Some(
+                        db.paragraph_by_id(&paragraph_id)
+                            .await
+                            .map_err(graphql_error),
+                    ),
+                    _ => None,
+                }
+            }),
+        )
+    }
+
+    /// New comments on words and paragraphs in the given document
+    async fn comment_posted<'ctx>(
+        &self,
+        context: &Context<'ctx>,
+        document_id: Uuid,
+    ) -> FieldResult<impl Stream<Item = FieldResult<Comment>> + 'ctx> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        let include_hidden = can_moderate(context);
+        Ok(
+            document_events(context, document_id)?.filter_map(move |event| async move {
+                match event {
+                    // Hidden comments are skipped unless the subscriber is an
+                    // editor.
+                    DocumentEvent::CommentPosted { comment_id, .. } => {
+                        match db.comment_by_id(&comment_id, include_hidden).await {
+                            Ok(comment) => Some(Ok(comment)),
+                            Err(err) => match err.downcast_ref::<DailpError>() {
+                                Some(DailpError::NotFound(_)) => None,
+                                _ => Some(Err(graphql_error(err))),
+                            },
+                        }
+                    }
+                    _ => None,
+                }
+            }),
+        )
+    }
+
+    /// The given document each time its pages, paragraphs or words are added,
+    /// removed, split or merged
+    async fn document_restructured<'ctx>(
+        &self,
+        context: &Context<'ctx>,
+        document_id: Uuid,
+    ) -> FieldResult<impl Stream<Item = FieldResult<AnnotatedDoc>> + 'ctx> {
+        let loader = context.data::<DataLoader<Database>>()?;
+        Ok(
+            document_events(context, document_id)?.filter_map(move |event| async move {
+                match event {
+                    DocumentEvent::DocumentRestructured { document_id } => loader
+                        .load_one(DocumentId(document_id))
+                        .await
+                        .map_err(graphql_error)
+                        .transpose(),
+                    _ => None,
+                }
+            }),
+        )
+    }
+}
+
+/// All events that happen in the given document from now on.
+fn document_events(
+    context: &Context<'_>,
+    document_id: Uuid,
+) -> FieldResult<impl Stream<Item = DocumentEvent>> {
+    Ok(context
+        .data::<Arc<dyn EventBus>>()?
+        .subscribe()
+        .filter(move |event| futures::future::ready(event.document_id() == document_id)))
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use dailp::{ContributorDetails, Database, SheetResult};
+use dailp::{Database, SheetResult};
 
 pub async fn migrate_all(db: &Database) -> Result<()> {
     let sheet =
@@ -9,15 +9,13 @@ pub async fn migrate_all(db: &Database) -> Result<()> {
         let full_name = row.next()?;
         let alternate_name = row.next();
         let birth_date = row.next();
-        Some(ContributorDetails {
-            full_name,
-            alternate_name,
-            birth_date: birth_date.and_then(|d| dailp::Date::parse(&d).ok()),
-        })
+        let birth_date = birth_date.and_then(|d| dailp::Date::parse(&d).ok());
+        Some((full_name, alternate_name, birth_date))
     });
 
-    for person in contributors {
-        db.upsert_contributor(person).await?;
+    for (full_name, alternate_name, birth_date) in contributors {
+        db.upsert_contributor(&full_name, alternate_name.as_deref(), birth_date)
+            .await?;
     }
     Ok(())
 }
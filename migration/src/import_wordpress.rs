@@ -0,0 +1,270 @@
+//! This program moves the text of edited collection chapters out of WordPress.
+//! It reads pages from WordPress export (WXR) files on disk and saves each one
+//! as the body of the chapter that links to it by WordPress ID.
+//!
+//! Usage: `dailp-import-wordpress <collection-slug> <export.xml>...`
+
+use anyhow::{Context, Result};
+use dailp::async_graphql::dataloader::Loader;
+use dailp::page::{ContentBlock, Gallery, Markdown};
+use dailp::{ChaptersInCollection, Database};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+const CONTENT_NAMESPACE: &str = "http://purl.org/rss/1.0/modules/content/";
+const WORDPRESS_NAMESPACE: &str = "http://wordpress.org/export/";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+    pretty_env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let collection_slug = args
+        .next()
+        .context("Usage: dailp-import-wordpress <collection-slug> <export.xml>...")?;
+    let db = Database::connect(Some(1))?;
+
+    let key = ChaptersInCollection(dailp::slugify_ltree(&collection_slug));
+    let chapters = db
+        .load(&[key.clone()])
+        .await?
+        .remove(&key)
+        .unwrap_or_default();
+    let mut chapters_by_post: HashMap<_, _> = chapters
+        .into_iter()
+        .filter_map(|chapter| Some((chapter.wordpress_id?, chapter)))
+        .collect();
+
+    for path in args {
+        println!("Reading {}...", path);
+        let xml = std::fs::read_to_string(&path)?;
+        for post in wordpress_posts(&xml).with_context(|| format!("Couldn't parse {}", path))? {
+            if let Some(chapter) = chapters_by_post.remove(&post.id) {
+                println!(
+                    "Importing \"{}\" into {}",
+                    post.title,
+                    chapter.path.join(".")
+                );
+                db.save_chapter_body(chapter.id, content_blocks(&post.content))
+                    .await?;
+            }
+        }
+    }
+
+    for (post_id, chapter) in chapters_by_post {
+        println!(
+            "No WordPress page {} found for chapter \"{}\"",
+            post_id, chapter.title
+        );
+    }
+
+    Ok(())
+}
+
+/// One page or post from a WordPress export.
+struct WordpressPost {
+    id: i64,
+    title: String,
+    /// HTML content of the post
+    content: String,
+}
+
+/// All pages and posts in a WordPress export file.
+fn wordpress_posts(xml: &str) -> Result<Vec<WordpressPost>> {
+    let doc = roxmltree::Document::parse(xml)?;
+    Ok(doc
+        .descendants()
+        .filter(|node| node.has_tag_name("item"))
+        .filter_map(|item| {
+            let child = |name: &str, namespace: &str| {
+                item.children()
+                    .find(|node| {
+                        node.tag_name().name() == name
+                            && node
+                                .tag_name()
+                                .namespace()
+                                .unwrap_or_default()
+                                .starts_with(namespace)
+                    })
+                    .map(|node| node.text().unwrap_or_default().to_owned())
+            };
+            Some(WordpressPost {
+                id: child("post_id", WORDPRESS_NAMESPACE)?.trim().parse().ok()?,
+                title: child("title", "").unwrap_or_default(),
+                content: child("encoded", CONTENT_NAMESPACE)?,
+            })
+        })
+        .collect())
+}
+
+/// Split the HTML content of a WordPress page into blocks. Paragraphs that
+/// only have images in them become galleries, and everything else is kept as
+/// Markdown.
+fn content_blocks(html: &str) -> Vec<ContentBlock> {
+    lazy_static! {
+        static ref IMAGE: Regex = Regex::new(r"!\[[^\]]*\]\(([^)\s]*)\)").unwrap();
+    }
+    let markdown = html_to_markdown(html);
+    let mut blocks = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut images: Vec<String> = Vec::new();
+    for paragraph in markdown.split("\n\n") {
+        let only_images = IMAGE.replace_all(paragraph, "").trim().is_empty();
+        if only_images {
+            if !prose.is_empty() {
+                blocks.push(ContentBlock::Markdown(Markdown {
+                    content: prose.join("\n\n"),
+                }));
+                prose.clear();
+            }
+            images.extend(IMAGE.captures_iter(paragraph).map(|c| c[1].to_owned()));
+        } else {
+            if !images.is_empty() {
+                blocks.push(ContentBlock::Gallery(Gallery {
+                    media_urls: std::mem::take(&mut images),
+                }));
+            }
+            prose.push(paragraph);
+        }
+    }
+    if !prose.is_empty() {
+        blocks.push(ContentBlock::Markdown(Markdown {
+            content: prose.join("\n\n"),
+        }));
+    }
+    if !images.is_empty() {
+        blocks.push(ContentBlock::Gallery(Gallery { media_urls: images }));
+    }
+    blocks
+}
+
+/// Convert the HTML that WordPress stores into Markdown. Tags without a
+/// Markdown equivalent, like tables, are left as HTML.
+fn html_to_markdown(html: &str) -> String {
+    lazy_static! {
+        static ref COMMENT: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+        static ref IMAGE: Regex = Regex::new(r"(?i)<img\s[^>]*>").unwrap();
+        static ref SRC: Regex = Regex::new(r#"(?i)\ssrc="([^"]*)""#).unwrap();
+        static ref ALT: Regex = Regex::new(r#"(?i)\salt="([^"]*)""#).unwrap();
+        static ref LINK: Regex =
+            Regex::new(r#"(?is)<a\s[^>]*href="([^"]*)"[^>]*>(.*?)</a>"#).unwrap();
+        static ref HEADING: Regex = Regex::new(r"(?is)<h([1-6])[^>]*>(.*?)</h[1-6]>").unwrap();
+        static ref STRONG: Regex = Regex::new(r"(?i)</?(strong|b)(\s[^>]*)?>").unwrap();
+        static ref EMPHASIS: Regex = Regex::new(r"(?i)</?(em|i)(\s[^>]*)?>").unwrap();
+        static ref LIST_ITEM: Regex = Regex::new(r"(?i)<li(\s[^>]*)?>").unwrap();
+        static ref LINE_BREAK: Regex = Regex::new(r"(?i)<br\s*/?>").unwrap();
+        static ref BLOCK: Regex =
+            Regex::new(r"(?i)</?(p|div|figure|figcaption|ul|ol)(\s[^>]*)?>").unwrap();
+        static ref INLINE: Regex = Regex::new(r"(?i)</?(span|li)(\s[^>]*)?>").unwrap();
+        static ref ENTITY: Regex = Regex::new(r"&(#x[0-9a-fA-F]+|#[0-9]+|[a-z]+);").unwrap();
+        static ref BLANK_LINES: Regex = Regex::new(r"\n\s*\n").unwrap();
+    }
+    let text = html.replace("\r\n", "\n");
+    let text = COMMENT.replace_all(&text, "");
+    let text = IMAGE.replace_all(&text, |c: &Captures| {
+        let attribute = |re: &Regex| {
+            re.captures(&c[0])
+                .map_or("", |a| a.get(1).unwrap().as_str())
+        };
+        format!("![{}]({})", attribute(&ALT), attribute(&SRC))
+    });
+    let text = LINK.replace_all(&text, "[$2]($1)");
+    let text = HEADING.replace_all(&text, |c: &Captures| {
+        format!(
+            "\n\n{} {}\n\n",
+            "#".repeat(c[1].parse().unwrap()),
+            c[2].trim()
+        )
+    });
+    let text = STRONG.replace_all(&text, "**");
+    let text = EMPHASIS.replace_all(&text, "_");
+    let text = LIST_ITEM.replace_all(&text, "\n- ");
+    let text = LINE_BREAK.replace_all(&text, "  \n");
+    let text = BLOCK.replace_all(&text, "\n\n");
+    let text = INLINE.replace_all(&text, "");
+    let text = ENTITY.replace_all(&text, |c: &Captures| {
+        let entity = &c[1];
+        let code = if let Some(hex) = entity.strip_prefix("#x") {
+            u32::from_str_radix(hex, 16).ok()
+        } else if let Some(decimal) = entity.strip_prefix('#') {
+            decimal.parse().ok()
+        } else {
+            match entity {
+                "amp" => Some('&' as u32),
+                "lt" => Some('<' as u32),
+                "gt" => Some('>' as u32),
+                "quot" => Some('"' as u32),
+                "apos" => Some('\'' as u32),
+                "nbsp" => Some(' ' as u32),
+                _ => None,
+            }
+        };
+        code.and_then(char::from_u32)
+            .map_or_else(|| c[0].to_owned(), String::from)
+    });
+    BLANK_LINES
+        .split(&text)
+        .map(|paragraph| {
+            paragraph
+                .lines()
+                .map(str::trim_start)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_wordpress_pages() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+  xmlns:content="http://purl.org/rss/1.0/modules/content/"
+  xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+  <item>
+    <title>Letters Home</title>
+    <content:encoded><![CDATA[<!-- wp:heading --><h2>Letters&nbsp;Home</h2><!-- /wp:heading -->
+<p>Written by <a href="https://example.com">a <em>Keetoowah</em> elder</a> &#8211; in 1890.</p>
+<figure><img src="https://example.com/a.jpg" alt="A letter" /></figure>
+<p><img src="https://example.com/b.jpg"></p>
+<ul><li>One</li><li><strong>Two</strong></li></ul>]]></content:encoded>
+    <wp:post_id>42</wp:post_id>
+  </item>
+</channel>
+</rss>"#;
+        let posts = wordpress_posts(xml).unwrap();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].id, 42);
+        assert_eq!(posts[0].title, "Letters Home");
+
+        let blocks = content_blocks(&posts[0].content);
+        assert_eq!(blocks.len(), 3);
+        match &blocks[0] {
+            ContentBlock::Markdown(block) => assert_eq!(
+                block.content,
+                "## Letters Home\n\nWritten by [a _Keetoowah_ elder](https://example.com) \u{2013} in 1890."
+            ),
+            _ => panic!("Expected prose first"),
+        }
+        match &blocks[1] {
+            ContentBlock::Gallery(block) => assert_eq!(
+                block.media_urls,
+                ["https://example.com/a.jpg", "https://example.com/b.jpg"]
+            ),
+            _ => panic!("Expected images to become a gallery"),
+        }
+        match &blocks[2] {
+            ContentBlock::Markdown(block) => assert_eq!(block.content, "- One\n- **Two**"),
+            _ => panic!("Expected a list last"),
+        }
+    }
+}
@@ -384,7 +384,11 @@ impl SheetInterpretation {
             .into_iter()
             .skip(1)
             .zip(roles.into_iter().skip(1))
-            .map(|(name, role)| Contributor { name, role })
+            .map(|(name, role)| Contributor {
+                id: None,
+                name,
+                role,
+            })
             .collect();
         let sources = if let (Some(names), Some(links)) = (values.next(), values.next()) {
             names
@@ -9,12 +9,13 @@ in {
       architectures = [ "x86_64" ];
       description = ''
         To be invoked by Cognito on PostConfirmation. 
-        Adds a user to a group if their email is in a predefined list.
+        Adds a user to a group if an editor invited their email.
       '';
       environment.variables = {
         DAILP_AWS_REGION = builtins.getEnv "DAILP_AWS_REGION";
-        GOOGLE_API_KEY = builtins.getEnv "GOOGLE_API_KEY";
         DAILP_USER_POOL = builtins.getEnv "DAILP_USER_POOL";
+        DATABASE_URL =
+          "postgres://\${aws_db_instance.sql_database.username}:${config.servers.database.password}@\${aws_db_instance.sql_database.endpoint}/dailp";
       };
       filename = "${config.functions.package_path}/auth-post-confirmation.zip";
       handler="function_handler";
@@ -75,6 +75,10 @@ in {
       name = prefixName "graphql";
       env = {
         VITE_DEPLOYMENT_ENV = config.setup.stage;
+        # The public endpoint checks identity tokens itself.
+        DAILP_AWS_REGION = config.provider.aws.region;
+        DAILP_USER_POOL = "\${aws_cognito_user_pool.main.id}";
+        DAILP_USER_POOL_CLIENT = "\${aws_cognito_user_pool_client.main.id}";
         DATABASE_URL =
           "postgres://\${aws_db_instance.sql_database.username}:${config.servers.database.password}@\${aws_db_instance.sql_database.endpoint}/dailp";
       };
@@ -0,0 +1,4 @@
+-- Keep the genre from each document's metadata sheet so that search results
+-- can be grouped and filtered by it.
+alter table document
+add column genre text;
@@ -0,0 +1,23 @@
+-- Keep the English translations of each dictionary headword, which were
+-- previously dropped during ingestion.
+alter table morpheme_gloss
+add column english_definition text;
+
+-- Stemmed English words from the translation of each word and headword, which
+-- lets learners look up Cherokee words by their English meaning.
+alter table word
+add column english_search tsvector generated always as (
+  to_tsvector('english', coalesce(english_gloss, ''))
+) stored;
+
+create index word_english_search on word using gin (english_search);
+
+alter table morpheme_gloss
+add column english_search tsvector generated always as (
+  to_tsvector(
+    'english',
+    coalesce(english_definition, '') || ' ' || replace(gloss, '.', ' ')
+  )
+) stored;
+
+create index morpheme_gloss_english_search on morpheme_gloss using gin (english_search);
@@ -0,0 +1,18 @@
+-- Add migration script here
+
+create type edit_target_type as enum (
+  'Word',
+  'Paragraph'
+);
+
+create table edit_revision (
+  id autouuid primary key,
+  target_type edit_target_type not null,
+  target_id uuid not null,
+  edited_by uuid references dailp_user (id) on delete set null,
+  edited_at timestamp not null default now(),
+  before jsonb not null,
+  after jsonb not null
+);
+
+create index on edit_revision (target_type, target_id, edited_at);
@@ -0,0 +1,12 @@
+-- Add migration script here
+
+-- Each edit increments the version, so an edit based on an older version can
+-- be rejected instead of silently overwriting someone else's changes.
+alter table word
+add column version bigint not null default 0;
+
+alter table paragraph
+add column version bigint not null default 0;
+
+alter table document
+add column version bigint not null default 0;
@@ -0,0 +1,16 @@
+-- Add migration script here
+
+-- Replies are comments on other comments.
+alter type comment_parent_type add value 'Comment';
+
+alter table comment
+add column edited_at timestamp,
+-- Editors can hide comments from readers, for example if they're abusive.
+add column hidden_at timestamp,
+add column hidden_by uuid references dailp_user (id) on delete set null,
+add column hidden_reason text,
+-- Suggestions and questions stay open until someone resolves them.
+add column resolved_at timestamp,
+add column resolved_by uuid references dailp_user (id) on delete set null;
+
+create index on comment (parent_type, parent_id);
@@ -0,0 +1,23 @@
+-- Add migration script here
+
+create type proposed_edit_status as enum (
+  'Open',
+  'Accepted',
+  'Rejected'
+);
+
+-- A structured change to a word or paragraph, suggested in a comment for an
+-- editor to review.
+create table proposed_edit (
+  id autouuid primary key,
+  comment_id uuid not null unique references comment (id) on delete cascade,
+  target_type edit_target_type not null,
+  target_id uuid not null,
+  proposed_update jsonb not null,
+  status proposed_edit_status not null default 'Open',
+  reviewed_by uuid references dailp_user (id) on delete set null,
+  reviewed_at timestamp,
+  review_note text
+);
+
+create index on proposed_edit (target_type, target_id);
@@ -0,0 +1,32 @@
+-- Add migration script here
+
+-- A note on a word, a page image or a region of one, or a reply to another
+-- annotation.
+create table annotation (
+  id autouuid primary key,
+  content text not null,
+  created_by uuid references dailp_user (id) on delete set null,
+  created_at timestamp not null default now(),
+  updated_at timestamp,
+  -- Every annotation belongs to a document, even if it's attached to
+  -- something more specific.
+  document_id uuid not null references document (id) on delete cascade,
+  word_id uuid references word (id) on delete cascade,
+  reply_to uuid references annotation (id) on delete cascade,
+  page_id uuid references document_page (id) on delete cascade,
+  -- Region of the page image, in percentages of its size.
+  region_x_min double precision,
+  region_y_min double precision,
+  region_x_max double precision,
+  region_y_max double precision,
+  constraint annotation_one_attachment check (num_nonnulls(word_id, reply_to, page_id) <= 1),
+  constraint annotation_whole_region check (
+    num_nulls(region_x_min, region_y_min, region_x_max, region_y_max) in (0, 4)
+  ),
+  constraint annotation_region_on_page check (region_x_min is null or page_id is not null)
+);
+
+create index on annotation (document_id);
+create index on annotation (word_id);
+create index on annotation (reply_to);
+create index on annotation (page_id);
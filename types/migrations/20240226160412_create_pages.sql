@@ -0,0 +1,28 @@
+-- Content pages for the website, like "/our-team", which editors write and
+-- publish from the CMS instead of committing them to the website code.
+
+create type page_status as enum (
+  'Draft',
+  'Published'
+);
+
+-- A content page on the website, like "/our-team".
+create table page (
+  path text primary key,
+  title text not null,
+  status page_status not null default 'Draft',
+  -- Order of the page in lists of all pages.
+  index_in_site bigint not null default 0,
+  created_at timestamp not null default now(),
+  updated_at timestamp not null default now(),
+  published_at timestamp,
+  constraint page_path_is_absolute check (path like '/%')
+);
+
+-- One block of content on a page, serialized as JSON.
+create table page_block (
+  page_path text not null references page (path) on delete cascade on update cascade,
+  index_in_page bigint not null,
+  content jsonb not null,
+  primary key (page_path, index_in_page)
+);
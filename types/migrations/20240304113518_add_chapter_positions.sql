@@ -0,0 +1,12 @@
+-- Chapters with the same parent are shown in order of this position. New
+-- chapters take the next value of a sequence, which puts them after all of
+-- their existing siblings, and existing chapters are numbered in the order
+-- they were imported.
+create sequence collection_chapter_position;
+
+alter table collection_chapter
+add column position_in_parent bigint not null
+default nextval('collection_chapter_position');
+
+alter sequence collection_chapter_position
+owned by collection_chapter.position_in_parent;
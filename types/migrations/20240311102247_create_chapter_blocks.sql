@@ -0,0 +1,8 @@
+-- Content of an edited collection chapter, one block at a time. Blocks are
+-- serialized the same way as page blocks.
+create table chapter_block (
+  chapter_id uuid not null references collection_chapter (id) on delete cascade,
+  index_in_chapter bigint not null,
+  content jsonb not null,
+  primary key (chapter_id, index_in_chapter)
+);
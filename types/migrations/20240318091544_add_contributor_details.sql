@@ -0,0 +1,14 @@
+-- Details about each contributor, so that editors can maintain their records
+-- instead of relying on the exact spelling of their names.
+alter table contributor
+  add column alternate_name text,
+  add column birth_date date,
+  add column death_date date,
+  -- Tribal nation or community the person belongs to
+  add column affiliation text,
+  add column biography text,
+  -- Kinds of work they do, like "Translator" or "Annotator"
+  add column roles text[] not null default '{}',
+  add column viaf_id text,
+  add column orcid text,
+  add column wikidata_id text;
@@ -0,0 +1,14 @@
+create type interface_language as enum (
+  'English',
+  'Cherokee'
+);
+
+-- Profile details and reading preferences that each user can change.
+alter table dailp_user
+  add column avatar_url text,
+  -- Name of the `CherokeeOrthography` to show words in when a query doesn't
+  -- ask for one, like 'LEARNER'.
+  add column preferred_orthography text,
+  add column interface_language interface_language not null default 'English',
+  add column notify_on_comment_replies boolean not null default true,
+  add column notify_on_edit_reviews boolean not null default true;
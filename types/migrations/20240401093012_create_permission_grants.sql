@@ -0,0 +1,26 @@
+-- Same names as the user groups in Cognito.
+create type user_group as enum (
+  'Contributors',
+  'Editors'
+);
+
+create type permission_scope as enum (
+  'EditedCollection',
+  'DocumentCollection',
+  'Document'
+);
+
+-- Gives one user a role on a single edited collection, document collection or
+-- document, on top of any groups they belong to in Cognito. The target isn't a
+-- foreign key because it can point into any of those tables, so grants are
+-- checked against the target when they are made.
+create table permission_grant (
+  id autouuid primary key,
+  user_id uuid not null references dailp_user (id) on delete cascade,
+  role user_group not null,
+  scope permission_scope not null,
+  target_id uuid not null,
+  granted_by uuid references dailp_user (id) on delete set null,
+  granted_at timestamp not null default now(),
+  unique (user_id, scope, target_id)
+);
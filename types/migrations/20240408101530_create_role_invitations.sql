@@ -0,0 +1,11 @@
+-- People who should be put in a Cognito user group when they confirm their
+-- account. Emails are stored in lowercase so that they match however the user
+-- typed theirs when signing up.
+create table role_invitation (
+  email text primary key check (email = lower(email)),
+  role user_group not null,
+  invited_by uuid references dailp_user (id) on delete set null,
+  invited_at timestamp not null default now(),
+  -- When the user confirmed their account and was added to the group.
+  accepted_at timestamp
+);
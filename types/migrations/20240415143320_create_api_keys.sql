@@ -0,0 +1,32 @@
+-- Keys that scripts and partner institutions use to call the API as one of
+-- our users.
+create table api_key (
+  id autouuid primary key,
+  user_id uuid not null references dailp_user (id) on delete cascade,
+  -- Email of the owner when the key was made, which requests made with the key
+  -- are attributed to.
+  owner_email text not null,
+  name text not null,
+  -- The start of the key, so that people can tell their keys apart.
+  prefix text not null,
+  -- SHA-256 of the key. Keys are long and random, so they don't need a salt.
+  key_hash bytea not null unique,
+  read_only boolean not null,
+  -- Groups that requests made with the key act in, which the owner must have
+  -- been in when they made it.
+  groups user_group[] not null default '{}',
+  created_at timestamp not null default now(),
+  expires_at timestamp,
+  last_used_at timestamp,
+  revoked_at timestamp,
+  check (not read_only or groups = '{}')
+);
+
+create table api_key_use (
+  api_key_id uuid not null references api_key (id) on delete cascade,
+  used_at timestamp not null default now(),
+  -- Name of the GraphQL operation, if the request gave one.
+  operation_name text
+);
+
+create index on api_key_use (api_key_id, used_at);
@@ -0,0 +1,17 @@
+-- Store each user's preferred orthography as an enum instead of the GraphQL
+-- name of one, so that the database only accepts orthographies we support.
+create type cherokee_orthography as enum (
+  'Taoc',
+  'Crg',
+  'Learner'
+);
+
+alter table dailp_user
+  alter column preferred_orthography type cherokee_orthography
+  using (
+    case preferred_orthography
+      when 'TAOC' then 'Taoc'
+      when 'CRG' then 'Crg'
+      when 'LEARNER' then 'Learner'
+    end
+  )::cherokee_orthography;
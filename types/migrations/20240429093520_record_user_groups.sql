@@ -0,0 +1,4 @@
+-- The Cognito groups each user was in the last time they signed in. Requests
+-- made with an API key only act in groups that its owner is still in.
+alter table dailp_user
+  add column groups user_group[] not null default '{}';
@@ -0,0 +1,2 @@
+update role_invitation set accepted_at = now()
+where email = lower($1)
@@ -0,0 +1,5 @@
+insert into collection_chapter (
+  title, document_id, wordpress_id, index_in_parent, chapter_path, section
+)
+values ($1, $2, $3, nlevel($4::ltree) - 1, $4, $5)
+returning id
@@ -0,0 +1,7 @@
+insert into document_page (document_id, index_in_document, iiif_source_id, iiif_oid)
+select document.id, coalesce(max(document_page.index_in_document) + 1, 0), $2, $3
+from document
+  left join document_page on document_page.document_id = document.id
+where document.id = $1
+group by document.id
+returning id
@@ -0,0 +1,5 @@
+-- Add a paragraph to a page. It gets a real position once the document is
+-- laid out.
+insert into paragraph (page_id, character_range, english_translation)
+values ($1, 'empty', $2)
+returning id
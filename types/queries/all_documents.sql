@@ -3,6 +3,7 @@ select
   document.short_name,
   document.title,
   document.written_at,
+  document.genre,
   document.is_reference,
   coalesce(
     jsonb_agg(
@@ -20,5 +21,11 @@ from document
   left join
     contributor_attribution on contributor_attribution.document_id = document.id
   left join contributor on contributor.id = contributor_attribution.contributor_id
+where (document.short_name > $1 or $1 is null)
+  and (document.short_name < $2 or $2 is null)
 group by
   document.id
+order by
+  case when $3 then document.short_name end desc,
+  document.short_name
+limit $4
@@ -0,0 +1,10 @@
+-- Find the document an annotation belongs to given what it's attached to,
+-- along with the page for a page number.
+select
+  coalesce(word.document_id, parent.document_id, $3) as "document_id?",
+  document_page.id as "page_id?"
+from (select 1) as anchor
+  left join word on word.id = $1
+  left join annotation as parent on parent.id = $2
+  left join document_page
+    on document_page.document_id = $3 and document_page.index_in_document = $4::bigint - 1
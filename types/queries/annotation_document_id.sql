@@ -0,0 +1,3 @@
+select document_id
+from annotation
+where id = $1
@@ -0,0 +1,29 @@
+-- Annotations attached to a document, page, word, or another annotation, or
+-- one annotation by ID.
+select
+  annotation.id,
+  annotation.content,
+  annotation.created_at,
+  annotation.updated_at,
+  annotation.created_by,
+  dailp_user.display_name as "created_by_name?",
+  annotation.document_id,
+  annotation.word_id,
+  annotation.reply_to,
+  document_page.index_in_document + 1 as page_number,
+  annotation.region_x_min,
+  annotation.region_y_min,
+  annotation.region_x_max,
+  annotation.region_y_max
+from annotation
+  left join dailp_user on dailp_user.id = annotation.created_by
+  left join document_page on document_page.id = annotation.page_id
+where
+  case $1::text
+    when 'Id' then annotation.id = $2
+    when 'Document' then annotation.document_id = $2
+    when 'Page' then annotation.page_id = $2
+    when 'Word' then annotation.word_id = $2
+    when 'Reply' then annotation.reply_to = $2
+  end
+order by annotation.created_at
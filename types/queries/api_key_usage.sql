@@ -0,0 +1,5 @@
+select used_at, operation_name
+from api_key_use
+where api_key_id = $1
+order by used_at desc
+limit $2
@@ -0,0 +1,14 @@
+select
+  id,
+  user_id,
+  name,
+  prefix,
+  read_only,
+  groups as "groups: Vec<UserGroup>",
+  created_at,
+  expires_at,
+  last_used_at,
+  revoked_at
+from api_key
+where user_id = $1
+order by created_at desc
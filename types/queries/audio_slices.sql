@@ -0,0 +1,25 @@
+-- Audio slices with these IDs, leaving out any that editors have kept out of
+-- the edited collection.
+select distinct on (media_slice.id)
+  media_slice.id as "id",
+  media_slice.time_range as "range?",
+  media_resource.url as "resource_url",
+  coalesce(word_user_media.include_in_edited_collection, true) as "include_in_edited_collection!",
+  media_resource.recorded_at as "recorded_at?",
+  contributor.id as "recorded_by?",
+  contributor.display_name as "recorded_by_name?",
+  editor.id as "edited_by?",
+  editor.display_name as "edited_by_name?"
+from media_slice
+  inner join media_resource on media_resource.id = media_slice.resource_id
+  left join word_user_media on word_user_media.media_slice_id = media_slice.id
+  left join dailp_user contributor on contributor.id = media_resource.recorded_by
+  left join dailp_user editor on editor.id = word_user_media.edited_by
+where
+  media_slice.id = any($1)
+  and not exists (
+    select from word_user_media hidden
+    where hidden.media_slice_id = media_slice.id
+      and not hidden.include_in_edited_collection
+  )
+order by media_slice.id
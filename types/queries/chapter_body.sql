@@ -0,0 +1,4 @@
+select content
+from chapter_block
+where chapter_id = $1
+order by index_in_chapter
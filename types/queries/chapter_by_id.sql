@@ -0,0 +1,11 @@
+select
+  id,
+  title,
+  document_id,
+  wordpress_id,
+  index_in_parent,
+  chapter_path,
+  section as "section: CollectionSection",
+  collection_slug
+from collection_chapter
+where id = $1
@@ -8,4 +8,10 @@ select
     chapter_path,
     ltree2text(subpath(chapter_path, 0, 1)) AS "collection_slug!"
 from collection_chapter
-where ltree2text(subpath(chapter_path, 0, 1)) = any($1);
\ No newline at end of file
+where ltree2text(subpath(chapter_path, 0, 1)) = any($1)
+-- Parents come before their children, and siblings are in order.
+order by (
+  select array_agg(ancestor.position_in_parent order by nlevel(ancestor.chapter_path))
+  from collection_chapter as ancestor
+  where ancestor.chapter_path @> collection_chapter.chapter_path
+);
@@ -0,0 +1,15 @@
+-- Find the document a comment belongs to by following replies up to the word
+-- or paragraph at the top of the thread.
+with recursive thread as (
+  select parent_id, parent_type from comment where id = $1
+  union all
+  select comment.parent_id, comment.parent_type
+  from comment
+    inner join thread on thread.parent_type = 'Comment' and comment.id = thread.parent_id
+)
+select coalesce(word.document_id, document_page.document_id) as "document_id!"
+from thread
+  left join word on thread.parent_type = 'Word' and word.id = thread.parent_id
+  left join paragraph on thread.parent_type = 'Paragraph' and paragraph.id = thread.parent_id
+  left join document_page on document_page.id = paragraph.page_id
+where thread.parent_type != 'Comment'
@@ -1,13 +1,22 @@
 select
     comment.id,
-    posted_at,
-    posted_by,
+    comment.posted_at,
+    comment.posted_by,
     u_posted_by.display_name as "posted_by_name",
-    text_content,
-    comment_type as "comment_type: _",
-    parent_id,
-    parent_type as "parent_type: _"
+    comment.text_content,
+    comment.comment_type as "comment_type: _",
+    comment.parent_id,
+    comment.parent_type as "parent_type: _",
+    comment.edited_at,
+    comment.hidden_at,
+    comment.hidden_reason,
+    comment.resolved_at,
+    comment.resolved_by,
+    u_resolved_by.display_name as "resolved_by_name?"
 from comment
-join dailp_user u_posted_by on u_posted_by.id = posted_by
-where parent_id = $1 and parent_type = $2
-order by posted_at asc
\ No newline at end of file
+join dailp_user u_posted_by on u_posted_by.id = comment.posted_by
+left join dailp_user u_resolved_by on u_resolved_by.id = comment.resolved_by
+where comment.parent_id = $1 and comment.parent_type = $2
+  -- Only moderators see hidden comments.
+  and ($3 or comment.hidden_at is null)
+order by comment.posted_at asc
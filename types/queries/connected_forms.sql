@@ -40,14 +40,33 @@ select
   word.include_audio_in_edited_collection,
   editor.id as "audio_edited_by?",
   editor.display_name as "audio_edited_by_name?"
-from relations
-  inner join
-    morpheme_gloss on
-      morpheme_gloss.id = relations.left_gloss_id or morpheme_gloss.id = relations.right_gloss_id
-  inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
-  inner join word on word.id = word_segment.word_id
+from word
   left join media_slice on media_slice.id = word.audio_slice_id
   left join media_resource on media_resource.id = media_slice.resource_id
   left join dailp_user contributor on contributor.id = media_resource.recorded_by
   left join dailp_user editor on editor.id = word.audio_edited_by
-order by word.document_id
+where word.id in (
+    select word_segment.word_id
+    from relations
+      inner join
+        morpheme_gloss on
+          morpheme_gloss.id = relations.left_gloss_id or morpheme_gloss.id = relations.right_gloss_id
+      inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
+  )
+  and (word.id != $3 or $3 is null)
+  and (
+    $6::uuid is null
+    or (word.document_id, word.index_in_document, word.id) > ($4::uuid, $5::bigint, $6)
+  )
+  and (
+    $9::uuid is null
+    or (word.document_id, word.index_in_document, word.id) < ($7::uuid, $8::bigint, $9)
+  )
+order by
+  case when $10 then word.document_id end desc,
+  case when $10 then word.index_in_document end desc,
+  case when $10 then word.id end desc,
+  word.document_id,
+  word.index_in_document,
+  word.id
+limit $11
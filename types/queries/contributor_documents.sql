@@ -0,0 +1,8 @@
+-- Documents that a contributor worked on, in the order of the archive.
+select
+  document.id as document_id,
+  contributor_attribution.contribution_role
+from contributor_attribution
+  inner join document on document.id = contributor_attribution.document_id
+where contributor_attribution.contributor_id = $1
+order by document.short_name
@@ -0,0 +1,17 @@
+-- Contributors with the given IDs or full names, or all contributors if $3 is
+-- true.
+select
+  id,
+  full_name,
+  alternate_name,
+  birth_date as "birth_date: Date",
+  death_date as "death_date: Date",
+  affiliation,
+  biography,
+  roles,
+  viaf_id,
+  orcid,
+  wikidata_id
+from contributor
+where id = any($1) or full_name = any($2) or $3
+order by full_name
@@ -1,3 +0,0 @@
-select full_name
-from contributor
-where full_name = any($1)
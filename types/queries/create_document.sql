@@ -0,0 +1,10 @@
+-- Add a new document at the end of its group.
+insert into document (
+  short_name, title, group_id, index_in_group, is_reference, written_at, genre
+)
+select $1, $2, $3::uuid, coalesce(max(document.index_in_group) + 1, 0), $4, $5, $6
+from document_group
+  left join document on document.group_id = document_group.id
+where document_group.id = $3
+group by document_group.id
+returning id
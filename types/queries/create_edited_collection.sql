@@ -0,0 +1,3 @@
+insert into edited_collection (title, slug, wordpress_menu_id)
+values ($1, $2, $3)
+returning id, title, slug, wordpress_menu_id
@@ -0,0 +1,3 @@
+-- Replies are deleted along with the annotation
+delete from annotation
+where id = $1
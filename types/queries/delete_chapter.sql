@@ -0,0 +1,3 @@
+-- Delete a chapter along with all of its subchapters.
+delete from collection_chapter
+where chapter_path <@ (select chapter_path from collection_chapter where id = $1)
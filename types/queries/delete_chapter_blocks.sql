@@ -0,0 +1,2 @@
+delete from chapter_block
+where chapter_id = $1
@@ -1,2 +1,18 @@
--- delete a comment given its ID
-delete from comment where id = $1 returning id;
\ No newline at end of file
+-- delete a comment given its ID, along with all replies to it. Unless $2 is
+-- true, nothing is deleted when someone other than the poster has replied.
+with recursive thread as (
+  select id, posted_by from comment where id = $1
+  union all
+  select reply.id, reply.posted_by
+  from comment as reply
+    inner join thread on reply.parent_type = 'Comment' and reply.parent_id = thread.id
+)
+delete from comment
+where id in (select id from thread)
+  and (
+    $2
+    or not exists (
+      select from thread
+      where thread.posted_by <> (select posted_by from comment where id = $1)
+    )
+  );
@@ -0,0 +1,2 @@
+delete from contributor
+where id = $1
@@ -0,0 +1,2 @@
+delete from page
+where path = $1
@@ -0,0 +1,2 @@
+delete from page_block
+where page_path = $1
@@ -0,0 +1 @@
+delete from paragraph where id = $1
@@ -0,0 +1,3 @@
+delete from permission_grant
+where id = $1
+returning id
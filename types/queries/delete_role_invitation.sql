@@ -0,0 +1,3 @@
+delete from role_invitation
+where email = lower($1)
+returning email
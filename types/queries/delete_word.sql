@@ -0,0 +1,6 @@
+with deleted_comments as (
+  delete from comment
+  where parent_type = 'Word' and parent_id = $1
+)
+
+delete from word where id = $1
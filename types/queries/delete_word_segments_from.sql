@@ -0,0 +1,2 @@
+delete from word_segment
+where word_id = $1 and index_in_word >= $2
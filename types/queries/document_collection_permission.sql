@@ -0,0 +1,7 @@
+select exists (
+  select from permission_grant
+  where user_id = $1
+    and scope = 'DocumentCollection'
+    and target_id = $2
+    and role = any($3)
+) as "allowed!"
@@ -0,0 +1,18 @@
+-- Every page of a document with its paragraphs and their words, in order.
+-- Words belong to the paragraph they start in, like in `words_in_paragraph`.
+select
+  document_page.id as page_id,
+  paragraph.id as "paragraph_id?",
+  word.id as "word_id?",
+  word.page_number as "page_number?",
+  word.source_text as "word_source?"
+from document_page
+  left join paragraph on paragraph.page_id = document_page.id
+  left join word on word.page_id = document_page.id
+    and word.character_range && paragraph.character_range
+    and word.character_range &> paragraph.character_range
+where document_page.document_id = $1
+order by
+  document_page.index_in_document,
+  paragraph.character_range,
+  word.character_range
@@ -0,0 +1,8 @@
+select
+  document.version,
+  jsonb_build_object(
+    'title', document.title,
+    'writtenAt', document.written_at
+  ) as "snapshot!"
+from document
+where document.id = $1
@@ -0,0 +1,25 @@
+-- Whether the user has one of the roles on a document, either directly or
+-- through the document collection it belongs to or an edited collection with a
+-- chapter about it.
+select exists (
+  select from permission_grant
+  where permission_grant.user_id = $1
+    and permission_grant.role = any($3)
+    and (
+      (permission_grant.scope = 'Document' and permission_grant.target_id = $2)
+      or (
+        permission_grant.scope = 'DocumentCollection'
+        and permission_grant.target_id in (select group_id from document where id = $2)
+      )
+      or (
+        permission_grant.scope = 'EditedCollection'
+        and permission_grant.target_id in (
+          select edited_collection.id
+          from edited_collection
+            inner join collection_chapter
+              on collection_chapter.collection_slug = edited_collection.slug
+          where collection_chapter.document_id = $2
+        )
+      )
+    )
+) as "allowed!"
@@ -0,0 +1,7 @@
+select
+  word.page_id as "page_id!",
+  lower(word.character_range) as "start!",
+  word.source_text
+from word
+where word.document_id = $1
+  and word.page_id is not null
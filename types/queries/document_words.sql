@@ -26,4 +26,19 @@ where
   document_id = $1 and (
     word.index_in_document >= $2 or $2 is null
   ) and (word.index_in_document < $3 or $3 is null)
-order by index_in_document
+  and (
+    $6::uuid is null
+    or (word.document_id, word.index_in_document, word.id) > ($4::uuid, $5::bigint, $6)
+  )
+  and (
+    $9::uuid is null
+    or (word.document_id, word.index_in_document, word.id) < ($7::uuid, $8::bigint, $9)
+  )
+order by
+  case when $10 then word.document_id end desc,
+  case when $10 then word.index_in_document end desc,
+  case when $10 then word.id end desc,
+  word.document_id,
+  word.index_in_document,
+  word.id
+limit $11
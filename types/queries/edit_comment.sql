@@ -0,0 +1,5 @@
+update comment set
+  text_content = $2,
+  comment_type = $3,
+  edited_at = now()
+where id = $1
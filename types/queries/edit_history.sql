@@ -0,0 +1,11 @@
+select
+  edit_revision.id,
+  edit_revision.edited_at,
+  edit_revision.edited_by,
+  dailp_user.display_name as "edited_by_name?",
+  edit_revision.before,
+  edit_revision.after
+from edit_revision
+  left join dailp_user on dailp_user.id = edit_revision.edited_by
+where edit_revision.target_type = $1 and edit_revision.target_id = $2
+order by edit_revision.edited_at desc
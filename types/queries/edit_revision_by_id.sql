@@ -0,0 +1,5 @@
+select
+  target_id,
+  before
+from edit_revision
+where id = $1 and target_type = $2
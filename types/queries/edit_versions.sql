@@ -0,0 +1,5 @@
+select 'Word' as "kind!", id, version from word where id = any($1)
+union all
+select 'Paragraph', id, version from paragraph where id = any($2)
+union all
+select 'Document', id, version from document where id = any($3)
@@ -0,0 +1,8 @@
+select exists (
+  select from permission_grant
+    inner join edited_collection on edited_collection.id = permission_grant.target_id
+  where permission_grant.user_id = $1
+    and permission_grant.scope = 'EditedCollection'
+    and edited_collection.slug = $2
+    and permission_grant.role = any($3)
+) as "allowed!"
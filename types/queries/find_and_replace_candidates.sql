@@ -0,0 +1,25 @@
+-- Words in a document or collection containing the text to find in the given
+-- field, locked so they can be changed safely.
+select
+  word.id,
+  word.document_id,
+  word.source_text,
+  word.commentary
+from word
+  inner join document on document.id = word.document_id
+  inner join document_group on document_group.id = document.group_id
+where ($1::uuid is null or word.document_id = $1)
+  and ($2::text is null or document_group.slug = $2)
+  and case $3::text
+    when 'Source' then strpos(word.source_text, $4) > 0
+    when 'Commentary' then strpos(word.commentary, $4) > 0
+    when 'Gloss' then exists (
+      select from word_segment
+        inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+      where word_segment.word_id = word.id and morpheme_gloss.gloss = $4
+    )
+    else false
+  end
+order by word.document_id, word.index_in_document
+limit $5
+for update of word
@@ -0,0 +1,14 @@
+insert into annotation (
+  content,
+  created_by,
+  document_id,
+  word_id,
+  reply_to,
+  page_id,
+  region_x_min,
+  region_y_min,
+  region_x_max,
+  region_y_max
+)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+returning id
@@ -0,0 +1,18 @@
+with new_key as (
+  select
+    'dailp_' || replace(uuid_generate_v4()::text || uuid_generate_v4()::text, '-', '') as secret
+)
+insert into api_key (
+  user_id, owner_email, name, prefix, key_hash, read_only, groups, expires_at
+)
+select
+  $1,
+  $2,
+  $3,
+  left(new_key.secret, 12),
+  sha256(convert_to(new_key.secret, 'UTF8')),
+  $4,
+  $5,
+  now() + make_interval(days => $6)
+from new_key
+returning id, (select secret from new_key) as "secret!"
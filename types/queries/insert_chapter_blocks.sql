@@ -0,0 +1,3 @@
+insert into chapter_block (chapter_id, index_in_chapter, content)
+select $1, block.index - 1, block.content
+from unnest($2::jsonb[]) with ordinality as block (content, index)
@@ -0,0 +1,14 @@
+insert into contributor (
+  full_name,
+  alternate_name,
+  birth_date,
+  death_date,
+  affiliation,
+  biography,
+  roles,
+  viaf_id,
+  orcid,
+  wikidata_id
+)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+returning id
@@ -0,0 +1,2 @@
+insert into edit_revision (target_type, target_id, edited_by, before, after)
+values ($1, $2, $3, $4, $5)
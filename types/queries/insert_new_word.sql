@@ -0,0 +1,8 @@
+-- Add a word to a document. It gets a real position once the document is laid
+-- out again.
+insert into word (
+  source_text, simple_phonetics, phonemic, english_gloss, commentary,
+  document_id, index_in_document
+)
+values ($1, $2, $3, $4, $5, $6, 0)
+returning id
@@ -0,0 +1,9 @@
+-- New pages go at the end of the list of all pages.
+insert into page (path, title, status, index_in_site, published_at)
+select
+  $1,
+  $2,
+  $3,
+  coalesce(max(page.index_in_site) + 1, 0),
+  case when $3 = 'Published'::page_status then now() end
+from page
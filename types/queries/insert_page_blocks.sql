@@ -0,0 +1,3 @@
+insert into page_block (page_path, index_in_page, content)
+select $1, block.index - 1, block.content
+from unnest($2::jsonb[]) with ordinality as block (content, index)
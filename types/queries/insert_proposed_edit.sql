@@ -0,0 +1,3 @@
+insert into proposed_edit (comment_id, target_type, target_id, proposed_update)
+values ($1, $2, $3, $4)
+returning id
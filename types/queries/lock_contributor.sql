@@ -0,0 +1 @@
+select id from contributor where id = $1 for update
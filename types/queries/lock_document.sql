@@ -0,0 +1,2 @@
+-- Serializes changes to the structure of one document.
+select id from document where id = $1 for update
@@ -0,0 +1,2 @@
+-- Serializes changes to the chapters of one collection.
+select id from edited_collection where slug = $1 for update
@@ -0,0 +1,15 @@
+select
+  proposed_edit.id,
+  proposed_edit.comment_id,
+  proposed_edit.target_type as "target_type: EditTargetType",
+  proposed_edit.target_id,
+  proposed_edit.proposed_update,
+  proposed_edit.status as "status: ProposedEditStatus",
+  proposed_edit.reviewed_at,
+  proposed_edit.reviewed_by,
+  reviewer.display_name as "reviewed_by_name?",
+  proposed_edit.review_note
+from proposed_edit
+  left join dailp_user as reviewer on reviewer.id = proposed_edit.reviewed_by
+where proposed_edit.id = $1
+for update of proposed_edit
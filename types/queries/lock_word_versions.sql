@@ -0,0 +1,4 @@
+select id, version
+from word
+where id = any($1)
+for update
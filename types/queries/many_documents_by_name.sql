@@ -4,6 +4,7 @@ select
   d.title,
   d.is_reference,
   d.written_at,
+  d.genre,
   d.audio_slice_id,
   media_resource.url as "audio_url?",
   media_resource.recorded_at as "recorded_at?",
@@ -0,0 +1,19 @@
+-- Move every attribution of the duplicate contributors ($2) onto the one we
+-- keep ($1), then delete the duplicates. Where the kept contributor already
+-- worked on the same document or chapter, their own role stays.
+with moved_document_attributions as (
+  insert into contributor_attribution (document_id, contributor_id, contribution_role)
+  select distinct on (document_id) document_id, $1, contribution_role
+  from contributor_attribution
+  where contributor_id = any($2)
+  on conflict do nothing
+),
+moved_chapter_attributions as (
+  insert into collection_chapter_attribution (chapter_id, contributor_id, contribution_role)
+  select distinct on (chapter_id) chapter_id, $1, contribution_role
+  from collection_chapter_attribution
+  where contributor_id = any($2)
+  on conflict do nothing
+)
+delete from contributor
+where id = any($2) and id != $1
@@ -0,0 +1,26 @@
+-- Segments of every word which contains all of the required glosses ($1),
+-- optionally limited to a single document ($2).
+select
+  word_segment.word_id,
+  word_segment.index_in_word,
+  word_segment.morpheme,
+  word_segment.gloss_id,
+  morpheme_gloss.gloss,
+  word_segment.role as "role: WordSegmentRole"
+from word_segment
+  inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+  inner join word on word.id = word_segment.word_id
+where (word.document_id = $2 or $2 is null)
+  and (
+    cardinality($1::text[]) = 0
+    or word_segment.word_id in (
+      select candidate.word_id
+      from word_segment as candidate
+        inner join morpheme_gloss as candidate_gloss
+          on candidate_gloss.id = candidate.gloss_id
+      where candidate_gloss.gloss = any($1)
+      group by candidate.word_id
+      having count(distinct candidate_gloss.gloss) = cardinality($1)
+    )
+  )
+order by word_segment.word_id, word_segment.index_in_word
@@ -1,3 +1,21 @@
+-- Words containing the given gloss ($1), optionally within one document ($2),
+-- in pages of whole documents.
+with document_page as (
+  select word.document_id
+  from word
+    inner join word_segment on word_segment.word_id = word.id
+    inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+  where morpheme_gloss.gloss = $1
+    and (word.document_id = $2 or $2 is null)
+    and (word.document_id > $3 or $3 is null)
+    and (word.document_id < $4 or $4 is null)
+  group by word.document_id
+  order by
+    case when $5 then word.document_id end desc,
+    word.document_id
+  limit $6
+)
+
 select
   document.id as document_id,
   document.is_reference,
@@ -11,10 +29,14 @@ select
   word.index_in_document,
   word.page_number
 from word
+  inner join document_page on document_page.document_id = word.document_id
   inner join document on document.id = word.document_id
   left join word_segment on word_segment.word_id = word.id
   inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
 where morpheme_gloss.gloss = $1
   and (word.document_id = $2 or $2 is null)
 group by document.id, word.id
-order by document.id
+order by
+  case when $5 then document.id end desc,
+  document.id,
+  word.index_in_document
@@ -0,0 +1,17 @@
+-- Move a chapter and all of its subchapters from one path to another.
+with moved as (
+  select
+    id,
+    case
+      when chapter_path = $1 then $2::ltree
+      else $2::ltree || subpath(chapter_path, nlevel($1))
+    end as new_path
+  from collection_chapter
+  where chapter_path <@ $1
+)
+
+update collection_chapter set
+  chapter_path = moved.new_path,
+  index_in_parent = nlevel(moved.new_path) - 1
+from moved
+where collection_chapter.id = moved.id
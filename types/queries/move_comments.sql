@@ -0,0 +1,9 @@
+-- Move comments and annotations from a word or paragraph that is about to be
+-- merged into another.
+with moved_annotations as (
+  update annotation set word_id = $2
+  where word_id = $1
+)
+
+update comment set parent_id = $2
+where parent_id = $1 and parent_type in ('Word', 'Paragraph')
@@ -0,0 +1,38 @@
+-- Unresolved suggestions on words and paragraphs in a document
+select
+    comment.id,
+    comment.posted_at,
+    comment.posted_by,
+    u_posted_by.display_name as "posted_by_name",
+    comment.text_content,
+    comment.comment_type as "comment_type: _",
+    comment.parent_id,
+    comment.parent_type as "parent_type: _",
+    comment.edited_at,
+    comment.hidden_at,
+    comment.hidden_reason,
+    comment.resolved_at,
+    comment.resolved_by,
+    u_resolved_by.display_name as "resolved_by_name?"
+from comment
+join dailp_user u_posted_by on u_posted_by.id = comment.posted_by
+left join dailp_user u_resolved_by on u_resolved_by.id = comment.resolved_by
+where comment.comment_type = 'Suggestion'
+  and comment.resolved_at is null
+  and ($2 or comment.hidden_at is null)
+  and (
+    (
+      comment.parent_type = 'Word'
+      and comment.parent_id in (select word.id from word where word.document_id = $1)
+    )
+    or (
+      comment.parent_type = 'Paragraph'
+      and comment.parent_id in (
+        select paragraph.id
+        from paragraph
+          inner join document_page on document_page.id = paragraph.page_id
+        where document_page.document_id = $1
+      )
+    )
+  )
+order by comment.posted_at asc
@@ -0,0 +1 @@
+select document_id from document_page where id = $1
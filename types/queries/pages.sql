@@ -0,0 +1,19 @@
+-- Pages with the given paths, or all pages if $1 is null. Drafts are only
+-- included if $2 is true.
+select
+  page.path,
+  page.title,
+  page.status as "status: PageStatus",
+  page.published_at,
+  coalesce(
+    (
+      select jsonb_agg(page_block.content order by page_block.index_in_page)
+      from page_block
+      where page_block.page_path = page.path
+    ),
+    '[]'::jsonb
+  ) as "body!"
+from page
+where ($1::text[] is null or page.path = any($1))
+  and (page.status = 'Published' or $2)
+order by page.index_in_site, page.path
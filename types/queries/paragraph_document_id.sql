@@ -0,0 +1,4 @@
+select document_page.document_id
+from paragraph
+  inner join document_page on document_page.id = paragraph.page_id
+where paragraph.id = $1
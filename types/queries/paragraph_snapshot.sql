@@ -0,0 +1,4 @@
+select
+  jsonb_build_object('translation', paragraph.english_translation) as "snapshot!"
+from paragraph
+where paragraph.id = $1
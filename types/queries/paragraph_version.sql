@@ -0,0 +1 @@
+select version from paragraph where id = $1
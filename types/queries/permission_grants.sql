@@ -0,0 +1,21 @@
+select
+  permission_grant.id,
+  permission_grant.user_id,
+  dailp_user.display_name as user_name,
+  permission_grant.role as "role: UserGroup",
+  permission_grant.scope as "scope: PermissionScope",
+  permission_grant.target_id,
+  case permission_grant.scope
+    when 'EditedCollection' then edited_collection.title
+    when 'DocumentCollection' then document_group.title
+    when 'Document' then document.title
+  end as target_title,
+  permission_grant.granted_by,
+  permission_grant.granted_at
+from permission_grant
+  inner join dailp_user on dailp_user.id = permission_grant.user_id
+  left join edited_collection on edited_collection.id = permission_grant.target_id
+  left join document_group on document_group.id = permission_grant.target_id
+  left join document on document.id = permission_grant.target_id
+where $1::uuid is null or permission_grant.user_id = $1
+order by permission_grant.granted_at
@@ -0,0 +1,5 @@
+select case $1::permission_scope
+  when 'EditedCollection' then exists (select from edited_collection where id = $2)
+  when 'DocumentCollection' then exists (select from document_group where id = $2)
+  when 'Document' then exists (select from document where id = $2)
+end as "exists!"
@@ -0,0 +1,9 @@
+-- Number the words of a document that have no place in its layout after all
+-- of those that do, keeping their order, so that no two words share an index.
+update word set index_in_document = $2 + unplaced.position
+from (
+  select id, row_number() over (order by index_in_document, id) as position
+  from word
+  where document_id = $1 and not (id = any($3))
+) as unplaced
+where word.id = unplaced.id
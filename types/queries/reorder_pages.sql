@@ -0,0 +1,20 @@
+-- Put the given pages first, in the given order, followed by any other pages
+-- in their current order.
+with ordering as (
+  select path, index
+  from unnest($1::text[]) with ordinality as ordering (path, index)
+),
+
+ranked as (
+  select
+    page.path,
+    row_number() over (
+      order by ordering.index nulls last, page.index_in_site, page.path
+    ) - 1 as index_in_site
+  from page
+    left join ordering on ordering.path = page.path
+)
+
+update page set index_in_site = ranked.index_in_site
+from ranked
+where page.path = ranked.path
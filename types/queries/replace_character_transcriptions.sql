@@ -0,0 +1,11 @@
+-- Replace the characters of a document, after its words have moved.
+with cleared as (
+  delete from character_transcription
+  using document_page
+  where document_page.id = character_transcription.page_id
+    and document_page.document_id = $1
+)
+
+insert into character_transcription (page_id, index_in_page, possible_transcriptions)
+select page_id, index, array[transcription]
+from unnest($2::uuid[], $3::bigint[], $4::text[]) as t(page_id, index, transcription)
@@ -0,0 +1,18 @@
+-- Put back segments exactly as they were, glosses included. Glosses that have
+-- since been deleted are left out, as they would be on the live segments.
+insert into word_segment (word_id, index_in_word, morpheme, gloss_id, role)
+select
+  $1,
+  input_data.index - 1,
+  input_data.morpheme,
+  morpheme_gloss.id,
+  input_data.role
+from
+  unnest($2::text[], $3::uuid[], $4::word_segment_role[])
+    with ordinality as input_data(morpheme, gloss_id, role, index)
+  left join morpheme_gloss on morpheme_gloss.id = input_data.gloss_id
+on conflict (word_id, index_in_word)
+do update set
+  morpheme = excluded.morpheme,
+  gloss_id = excluded.gloss_id,
+  role = excluded.role
@@ -0,0 +1,47 @@
+-- Dictionary words whose English translation matches the given full text
+-- query ($1), along with the headword for the root of each word, if known.
+select
+  word.id,
+  word.source_text,
+  word.simple_phonetics,
+  word.phonemic,
+  word.english_gloss,
+  word.commentary,
+  word.document_id,
+  word.index_in_document,
+  word.page_number,
+  media_resource.recorded_at as "audio_recorded_at?",
+  media_resource.url as "audio_url?",
+  media_slice.time_range as "audio_slice?",
+  media_slice.id as "audio_slice_id?",
+  contributor.id as "audio_recorded_by?",
+  contributor.display_name as "audio_recorded_by_name?",
+  word.include_audio_in_edited_collection,
+  editor.id as "audio_edited_by?",
+  editor.display_name as "audio_edited_by_name?",
+  root.id as "root_id?",
+  root.gloss as "root_gloss?",
+  root.example_shape as "root_shape?",
+  root.english_definition as "root_definition?",
+  document.short_name as document_short_name,
+  ts_rank(word.english_search, query) as "rank!"
+from word
+  cross join to_tsquery('english', $1) as query
+  inner join document on document.id = word.document_id
+  left join media_slice on media_slice.id = word.audio_slice_id
+  left join media_resource on media_resource.id = media_slice.resource_id
+  left join dailp_user contributor on contributor.id = media_resource.recorded_by
+  left join dailp_user editor on editor.id = word.audio_edited_by
+  left join lateral (
+    select morpheme_gloss.*
+    from word_segment
+      inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+    where word_segment.word_id = word.id
+      and morpheme_gloss.example_shape is not null
+    order by word_segment.index_in_word
+    limit 1
+  ) as root on true
+where document.is_reference
+  and word.english_search @@ query
+order by "rank!" desc
+limit $2
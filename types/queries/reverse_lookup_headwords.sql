@@ -0,0 +1,17 @@
+-- Dictionary headwords whose English definition or gloss matches the given
+-- full text query ($1).
+select
+  morpheme_gloss.id,
+  morpheme_gloss.gloss,
+  morpheme_gloss.example_shape,
+  morpheme_gloss.english_definition,
+  document.short_name as document_short_name,
+  ts_rank(morpheme_gloss.english_search, query) as "rank!"
+from morpheme_gloss
+  cross join to_tsquery('english', $1) as query
+  inner join document on document.id = morpheme_gloss.document_id
+where document.is_reference
+  and morpheme_gloss.example_shape is not null
+  and morpheme_gloss.english_search @@ query
+order by "rank!" desc
+limit $2
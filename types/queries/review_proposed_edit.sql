@@ -0,0 +1,6 @@
+update proposed_edit set
+  status = $2,
+  reviewed_by = $3,
+  reviewed_at = now(),
+  review_note = $4
+where id = $1
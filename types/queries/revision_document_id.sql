@@ -0,0 +1,10 @@
+-- The document that the word or paragraph changed by a revision is in, if it
+-- still exists.
+select coalesce(word.document_id, document_page.document_id) as "document_id?"
+from edit_revision
+  left join word on edit_revision.target_type = 'Word'
+    and word.id = edit_revision.target_id
+  left join paragraph on edit_revision.target_type = 'Paragraph'
+    and paragraph.id = edit_revision.target_id
+  left join document_page on document_page.id = paragraph.page_id
+where edit_revision.id = $1
@@ -0,0 +1,9 @@
+select
+  email,
+  role as "role: UserGroup",
+  invited_by,
+  invited_at,
+  accepted_at
+from role_invitation
+where $1::text is null or email = lower($1)
+order by email
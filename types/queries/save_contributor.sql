@@ -0,0 +1,12 @@
+update contributor set
+  full_name = $2,
+  alternate_name = $3,
+  birth_date = $4,
+  death_date = $5,
+  affiliation = $6,
+  biography = $7,
+  roles = $8,
+  viaf_id = $9,
+  orcid = $10,
+  wikidata_id = $11
+where id = $1
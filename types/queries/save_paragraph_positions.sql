@@ -0,0 +1,6 @@
+update paragraph set
+  page_id = layout.page_id,
+  character_range = int8range(layout.range_start, layout.range_end)
+from unnest($1::uuid[], $2::uuid[], $3::bigint[], $4::bigint[])
+  as layout(paragraph_id, page_id, range_start, range_end)
+where paragraph.id = layout.paragraph_id
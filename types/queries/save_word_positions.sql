@@ -0,0 +1,8 @@
+update word set
+  page_id = layout.page_id,
+  page_number = layout.page_number,
+  index_in_document = layout.index_in_document,
+  character_range = int8range(layout.range_start, layout.range_end)
+from unnest($1::uuid[], $2::uuid[], $3::text[], $4::bigint[], $5::bigint[], $6::bigint[])
+  as layout(word_id, page_id, page_number, index_in_document, range_start, range_end)
+where word.id = layout.word_id
@@ -0,0 +1,41 @@
+select
+  document.id,
+  document.short_name,
+  document.title,
+  document.written_at as "date: Date",
+  document.index_in_group as order_index,
+  document_group.slug as collection_slug,
+  document_group.title as collection_title,
+  document.genre,
+  array(
+    select attributed.full_name
+    from contributor_attribution as attr
+      inner join contributor as attributed on attributed.id = attr.contributor_id
+    where attr.document_id = document.id
+  ) as "contributors!",
+  (
+    document.audio_slice_id is not null
+  ) as "has_audio!"
+from document
+  inner join document_group on document_group.id = document.group_id
+where (
+    document.title ilike $1
+    or document.short_name ilike $1
+  )
+  and (cardinality($2::text[]) = 0 or document_group.slug = any($2))
+  and (cardinality($3::text[]) = 0 or document.genre = any($3))
+  and (
+    cardinality($4::bigint[]) = 0
+    or (extract(year from document.written_at)::bigint / 10 * 10) = any($4)
+  )
+  and (
+    cardinality($5::text[]) = 0
+    or exists (
+      select from contributor_attribution as attr
+        inner join contributor as attributed on attributed.id = attr.contributor_id
+      where attr.document_id = document.id
+        and attributed.full_name = any($5)
+    )
+  )
+  and ($6::boolean is null or $6 = (document.audio_slice_id is not null))
+order by document_group.slug, document.index_in_group
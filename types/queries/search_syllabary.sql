@@ -23,3 +23,19 @@ from word
   left join dailp_user contributor on contributor.id = media_resource.recorded_by
   left join dailp_user editor on editor.id = word.audio_edited_by
 where source_text like any($1)
+  and (
+    $4::uuid is null
+    or (word.document_id, word.index_in_document, word.id) > ($2::uuid, $3::bigint, $4)
+  )
+  and (
+    $7::uuid is null
+    or (word.document_id, word.index_in_document, word.id) < ($5::uuid, $6::bigint, $7)
+  )
+order by
+  case when $8 then word.document_id end desc,
+  case when $8 then word.index_in_document end desc,
+  case when $8 then word.id end desc,
+  word.document_id,
+  word.index_in_document,
+  word.id
+limit $9
@@ -22,6 +22,24 @@ from word
   left join media_resource on media_resource.id = media_slice.resource_id
   left join dailp_user contributor on contributor.id = media_resource.recorded_by
   left join dailp_user editor on editor.id = media_resource.recorded_by
-where source_text ilike $1
-  or simple_phonetics ilike $1
-  or english_gloss ilike $1
+where (
+    source_text ilike $1
+    or simple_phonetics ilike $1
+    or english_gloss ilike $1
+  )
+  and (
+    $4::uuid is null
+    or (word.document_id, word.index_in_document, word.id) > ($2::uuid, $3::bigint, $4)
+  )
+  and (
+    $7::uuid is null
+    or (word.document_id, word.index_in_document, word.id) < ($5::uuid, $6::bigint, $7)
+  )
+order by
+  case when $8 then word.document_id end desc,
+  case when $8 then word.index_in_document end desc,
+  case when $8 then word.id end desc,
+  word.document_id,
+  word.index_in_document,
+  word.id
+limit $9
@@ -0,0 +1,76 @@
+select
+  word.id,
+  word.source_text,
+  word.simple_phonetics,
+  word.phonemic,
+  word.english_gloss,
+  word.commentary,
+  word.document_id,
+  word.index_in_document,
+  word.page_number,
+  media_resource.recorded_at as "audio_recorded_at?",
+  media_resource.url as "audio_url?",
+  media_slice.time_range as "audio_slice?",
+  media_slice.id as "audio_slice_id?",
+  contributor.id as "audio_recorded_by?",
+  contributor.display_name as "audio_recorded_by_name?",
+  word.include_audio_in_edited_collection,
+  editor.id as "audio_edited_by?",
+  editor.display_name as "audio_edited_by_name?",
+  document_group.slug as collection_slug,
+  document_group.title as collection_title,
+  document.genre,
+  document.written_at,
+  array(
+    select attributed.full_name
+    from contributor_attribution as attr
+      inner join contributor as attributed on attributed.id = attr.contributor_id
+    where attr.document_id = document.id
+  ) as "contributors!",
+  (
+    word.audio_slice_id is not null
+    or exists (
+      select from word_user_media
+      where word_user_media.word_id = word.id
+        and word_user_media.include_in_edited_collection
+    )
+  ) as "has_audio!"
+from word
+  inner join document on document.id = word.document_id
+  inner join document_group on document_group.id = document.group_id
+  left join media_slice on media_slice.id = word.audio_slice_id
+  left join media_resource on media_resource.id = media_slice.resource_id
+  left join dailp_user contributor on contributor.id = media_resource.recorded_by
+  left join dailp_user editor on editor.id = word.audio_edited_by
+where (
+    word.source_text ilike any($1)
+    or word.simple_phonetics ilike any($1)
+    or word.english_gloss ilike any($1)
+  )
+  and (cardinality($2::text[]) = 0 or document_group.slug = any($2))
+  and (cardinality($3::text[]) = 0 or document.genre = any($3))
+  and (
+    cardinality($4::bigint[]) = 0
+    or (extract(year from document.written_at)::bigint / 10 * 10) = any($4)
+  )
+  and (
+    cardinality($5::text[]) = 0
+    or exists (
+      select from contributor_attribution as attr
+        inner join contributor as attributed on attributed.id = attr.contributor_id
+      where attr.document_id = document.id
+        and attributed.full_name = any($5)
+    )
+  )
+  and (
+    $6::boolean is null
+    or $6 = (
+      word.audio_slice_id is not null
+      or exists (
+        select from word_user_media
+        where word_user_media.word_id = word.id
+          and word_user_media.include_in_edited_collection
+      )
+    )
+  )
+order by document.id, word.index_in_document
@@ -0,0 +1,4 @@
+update collection_chapter set
+  position_in_parent = ordered.position_in_parent
+from unnest($1::uuid[]) with ordinality as ordered (id, position_in_parent)
+where collection_chapter.id = ordered.id
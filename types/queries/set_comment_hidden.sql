@@ -0,0 +1,5 @@
+update comment set
+  hidden_at = case when $2 then now() end,
+  hidden_by = case when $2 then $3::uuid end,
+  hidden_reason = case when $2 then $4::text end
+where id = $1
@@ -0,0 +1,5 @@
+update comment set
+  resolved_at = case when $2 then now() end,
+  resolved_by = case when $2 then $3::uuid end
+where id = $1 and comment_type in ('Suggestion', 'Question')
+returning id
@@ -0,0 +1,5 @@
+-- Chapters directly under the given collection or chapter path, in order.
+select id
+from collection_chapter
+where subpath(chapter_path, 0, -1) = $1
+order by position_in_parent, id
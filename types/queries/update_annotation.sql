@@ -0,0 +1,10 @@
+-- Change the content of an annotation, and its region if $3 is true.
+update annotation set
+  content = coalesce($2, content),
+  region_x_min = case when $3 then $4 else region_x_min end,
+  region_y_min = case when $3 then $5 else region_y_min end,
+  region_x_max = case when $3 then $6 else region_x_max end,
+  region_y_max = case when $3 then $7 else region_y_max end,
+  updated_at = now()
+where id = $1
+returning id
@@ -0,0 +1,6 @@
+update collection_chapter set
+  title = coalesce($2, title),
+  section = coalesce($3, section),
+  document_id = case when $4 then $5 else document_id end,
+  wordpress_id = case when $6 then $7 else wordpress_id end
+where id = $1
@@ -8,5 +8,7 @@ update document set
         case
             when $3::date is not null then $3::date
             else written_at
-        end
-where id = $1
+        end,
+    version = version + 1
+where id = $1 and version = $4
+returning document.id
@@ -0,0 +1,10 @@
+update page set
+  title = coalesce($2, title),
+  status = coalesce($3, status),
+  published_at = case
+    when $3 = 'Published'::page_status and status <> 'Published' then now()
+    else published_at
+  end,
+  updated_at = now()
+where path = $1
+returning path
@@ -3,5 +3,7 @@ update paragraph set
         case
             when $2::text[] != '{}' and $2[1] is not null then $2[1]
             else english_translation
-        end
-where id = $1;
\ No newline at end of file
+        end,
+    version = version + 1
+where id = $1 and ($3::bigint is null or version = $3)
+returning paragraph.id;
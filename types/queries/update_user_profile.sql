@@ -0,0 +1,8 @@
+update dailp_user set
+  display_name = coalesce($2, display_name),
+  avatar_url = case when $3 then $4 else avatar_url end,
+  preferred_orthography = case when $5 then $6 else preferred_orthography end,
+  interface_language = coalesce($7, interface_language),
+  notify_on_comment_replies = coalesce($8, notify_on_comment_replies),
+  notify_on_edit_reviews = coalesce($9, notify_on_edit_reviews)
+where id = $1
@@ -8,6 +8,7 @@ update word set
         case
             when $3::text[] != '{}' then $3[1]
             else commentary
-        end
-where id = $1
-returning word.document_id;
\ No newline at end of file
+        end,
+    version = version + 1
+where id = $1 and ($4::bigint is null or version = $4)
+returning word.document_id;
@@ -1,4 +1,6 @@
-insert into contributor (full_name)
-values ($1)
+insert into contributor (full_name, alternate_name, birth_date)
+values ($1, $2, $3)
 on conflict (full_name) do update set
-full_name = excluded.full_name
+  full_name = excluded.full_name,
+  alternate_name = coalesce(excluded.alternate_name, contributor.alternate_name),
+  birth_date = coalesce(excluded.birth_date, contributor.birth_date)
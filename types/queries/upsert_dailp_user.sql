@@ -1,9 +1,10 @@
-insert into dailp_user (id, display_name, created_at)
+insert into dailp_user (id, display_name, created_at, groups)
 values (
     -- hint for uuid type instead of autouuid (column type), which can't be used
     -- as a parameter
     $1::uuid,
     '',
-    now()
+    now(),
+    $2
 )
-on conflict do nothing;
+on conflict (id) do update set groups = excluded.groups;
@@ -0,0 +1,7 @@
+insert into permission_grant (user_id, role, scope, target_id, granted_by)
+values ($1, $2, $3, $4, $5)
+on conflict (user_id, scope, target_id) do update set
+  role = excluded.role,
+  granted_by = excluded.granted_by,
+  granted_at = now()
+returning id
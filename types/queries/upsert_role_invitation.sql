@@ -0,0 +1,8 @@
+insert into role_invitation (email, role, invited_by)
+values (lower($1), $2, $3)
+on conflict (email) do update set
+  role = excluded.role,
+  invited_by = excluded.invited_by,
+  invited_at = now(),
+  accepted_at = null
+returning email
@@ -0,0 +1,27 @@
+-- Find a key that is still active by its secret, and record that it was used.
+-- The key only acts in the groups that its owner is still in.
+with used_key as (
+  update api_key set last_used_at = now()
+  where key_hash = sha256(convert_to($1, 'UTF8'))
+    and revoked_at is null
+    and (expires_at is null or expires_at > now())
+  returning id, user_id, owner_email, read_only, groups
+),
+
+logged as (
+  insert into api_key_use (api_key_id, operation_name)
+  select used_key.id, $2 from used_key
+)
+
+select
+  used_key.id,
+  used_key.user_id,
+  used_key.owner_email,
+  used_key.read_only,
+  array(
+    select key_group
+    from unnest(used_key.groups) as key_group
+    where key_group = any(owner.groups)
+  ) as "groups!: Vec<UserGroup>"
+from used_key
+  inner join dailp_user owner on owner.id = used_key.user_id
@@ -0,0 +1,10 @@
+select
+  id,
+  display_name,
+  avatar_url,
+  preferred_orthography as "preferred_orthography: CherokeeOrthography",
+  interface_language as "interface_language: InterfaceLanguage",
+  notify_on_comment_replies,
+  notify_on_edit_reviews
+from dailp_user
+where id = any($1)
@@ -0,0 +1 @@
+select document_id from word where id = $1
@@ -0,0 +1,25 @@
+select
+  jsonb_build_object(
+    'source', word.source_text,
+    'commentary', word.commentary,
+    'segments', coalesce(
+      (
+        select
+          jsonb_agg(
+            jsonb_build_object(
+              'morpheme', word_segment.morpheme,
+              'gloss', coalesce(morpheme_gloss.gloss, ''),
+              'glossId', word_segment.gloss_id,
+              'role', word_segment.role
+            )
+            order by word_segment.index_in_word
+          )
+        from word_segment
+          left join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+        where word_segment.word_id = word.id
+      ),
+      '[]'::jsonb
+    )
+  ) as "snapshot!"
+from word
+where word.id = $1
@@ -0,0 +1 @@
+select version from word where id = $1
@@ -1,28 +1,48 @@
-#![allow(missing_docs)]
+//! Annotations are notes left by editors on a word, a page image or a region
+//! of one, or in reply to another annotation. They are shown alongside
+//! documents and included in their IIIF manifests.
 
-use crate::{DocumentId, FormId, Geometry};
-use serde::{Deserialize, Serialize};
+use crate::permission::PermissionTarget;
+use crate::{graphql_error, user::User, AnnotatedForm, DailpError, Database, DateTime, Geometry};
+use async_graphql::{dataloader::DataLoader, FieldResult, MaybeUndefined};
+use sqlx::types::Uuid;
 
 /// An annotation is a piece of information that provides details about a word,
 /// document, image, slice of an image, or audio recording.
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
 pub struct Annotation {
     /// Database ID
-    pub id: AnnotationId,
+    pub id: Uuid,
     /// The text content of this annotation
     pub content: String,
     /// What is this annotation attached to? Perhaps a word or this could be a reply to another
     /// annotation.
     pub attached_to: AnnotationAttachment,
+    /// Who wrote this annotation, unless their account has since been deleted
+    pub created_by: Option<User>,
+    /// When this annotation was written
+    pub created_at: DateTime,
+    /// When this annotation was last changed, if it has been
+    pub updated_at: Option<DateTime>,
+    /// Document this annotation belongs to
+    #[graphql(skip)]
+    pub document_id: Uuid,
 }
 
-/// Database ID for a single annotation
-#[derive(Serialize, Deserialize, PartialEq, Clone, Hash)]
-pub struct AnnotationId(pub String);
+#[async_graphql::ComplexObject]
+impl Annotation {
+    /// Replies to this annotation, oldest first
+    async fn replies(&self, context: &async_graphql::Context<'_>) -> FieldResult<Vec<Annotation>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.annotations(AnnotationsOn::Replies(self.id))
+            .await
+            .map_err(graphql_error)
+    }
+}
 
-#[derive(Serialize, Deserialize)]
-#[serde(tag = "__typename")]
+/// What an annotation is attached to
+#[derive(Clone, Debug, async_graphql::Union)]
 pub enum AnnotationAttachment {
     /// Reply to another existing annotation, referred to by ID.
     Reply(Reply),
@@ -32,22 +52,173 @@ pub enum AnnotationAttachment {
     DocumentRegion(DocumentRegion),
 }
 
-#[derive(Serialize, Deserialize)]
+/// Attachment of an annotation that replies to another one
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
 pub struct Reply {
-    to: AnnotationId,
+    /// ID of the annotation being replied to
+    pub to: Uuid,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Attachment of an annotation on a word
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
 pub struct WordAttachment {
-    to: FormId,
+    /// ID of the annotated word
+    pub to: Uuid,
+}
+
+#[async_graphql::ComplexObject]
+impl WordAttachment {
+    /// The annotated word
+    async fn word(&self, context: &async_graphql::Context<'_>) -> FieldResult<AnnotatedForm> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.word_by_id(&self.to).await.map_err(graphql_error)
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+/// Attachment of an annotation on a document, one of its page images, or a
+/// region of a page image
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
 pub struct DocumentRegion {
-    pub document: DocumentId,
+    /// ID of the annotated document
+    pub document_id: Uuid,
     /// Number of the page this annotation applies to.
-    pub page: Option<u32>,
+    pub page: Option<i64>,
     /// An image annotation without a region applies to the whole image.
     /// A page number is required to specify a region.
     pub region: Option<Geometry>,
 }
+
+/// Which annotations to look up
+#[derive(Clone, Copy, Debug)]
+pub enum AnnotationsOn {
+    /// The one annotation with this ID
+    Id(Uuid),
+    /// Every annotation in a document, including replies
+    Document(Uuid),
+    /// Annotations on a page of a document, by page ID
+    Page(Uuid),
+    /// Annotations on a word
+    Word(Uuid),
+    /// Replies to an annotation
+    Replies(Uuid),
+}
+
+impl AnnotationsOn {
+    /// Kind of lookup as passed to our queries, and the ID to look up
+    pub fn as_query(&self) -> (&'static str, Uuid) {
+        match *self {
+            Self::Id(id) => ("Id", id),
+            Self::Document(id) => ("Document", id),
+            Self::Page(id) => ("Page", id),
+            Self::Word(id) => ("Word", id),
+            Self::Replies(id) => ("Reply", id),
+        }
+    }
+}
+
+/// Write a new annotation. Exactly one of `replyTo`, `wordId`, or
+/// `documentRegion` must be given.
+#[derive(async_graphql::InputObject)]
+pub struct CreateAnnotationInput {
+    /// The text content of the annotation
+    pub content: String,
+    /// ID of the annotation to reply to
+    pub reply_to: Option<Uuid>,
+    /// ID of the word to annotate
+    pub word_id: Option<Uuid>,
+    /// Document, page, or image region to annotate
+    pub document_region: Option<DocumentRegionInput>,
+}
+
+/// A document, one of its pages, or a region of a page image
+#[derive(async_graphql::InputObject)]
+pub struct DocumentRegionInput {
+    /// ID of the document
+    pub document_id: Uuid,
+    /// One-indexed page number, if the annotation is about one page
+    pub page: Option<i64>,
+    /// Region of the page image, if the annotation is about part of it
+    pub region: Option<Geometry>,
+}
+
+impl CreateAnnotationInput {
+    /// What an editor needs rights on to write this annotation: the thing it's
+    /// attached to.
+    pub fn permission_targets(&self) -> Vec<PermissionTarget> {
+        let target = if let Some(annotation_id) = self.reply_to {
+            Some(PermissionTarget::Annotation(annotation_id))
+        } else if let Some(word_id) = self.word_id {
+            Some(PermissionTarget::Word(word_id))
+        } else {
+            self.document_region
+                .as_ref()
+                .map(|region| PermissionTarget::Document(region.document_id))
+        };
+        target.into_iter().collect()
+    }
+
+    /// Check that the annotation is attached to exactly one thing, and that a
+    /// region is only given along with a page.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let attachments = [
+            self.reply_to.is_some(),
+            self.word_id.is_some(),
+            self.document_region.is_some(),
+        ];
+        if attachments.iter().filter(|given| **given).count() != 1 {
+            return Err(DailpError::Validation(
+                "Attach the annotation to exactly one reply, word, or document region".into(),
+            )
+            .into());
+        }
+        if let Some(DocumentRegionInput {
+            page: None,
+            region: Some(_),
+            ..
+        }) = self.document_region
+        {
+            return Err(DailpError::Validation(
+                "A page number is required to annotate a region".into(),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Change an existing annotation
+#[derive(async_graphql::InputObject)]
+pub struct UpdateAnnotationInput {
+    /// ID of the annotation to change
+    pub id: Uuid,
+    /// New text content, if it should change
+    pub content: Option<String>,
+    /// New region of the page image, or null to annotate the whole page.
+    /// Only page annotations may have a region.
+    pub region: MaybeUndefined<Geometry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_need_a_page() {
+        let mut input = CreateAnnotationInput {
+            content: "Faded ink".to_owned(),
+            reply_to: None,
+            word_id: None,
+            document_region: Some(DocumentRegionInput {
+                document_id: Uuid::nil(),
+                page: None,
+                region: Some(Geometry::new(0.0, 0.0, 50.0, 50.0)),
+            }),
+        };
+        assert!(input.validate().is_err());
+        input.document_region.as_mut().unwrap().page = Some(2);
+        assert!(input.validate().is_ok());
+        input.word_id = Some(Uuid::nil());
+        assert!(input.validate().is_err());
+    }
+}
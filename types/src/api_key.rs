@@ -0,0 +1,202 @@
+//! Keys that let scripts call the API as one of our users, without signing in
+//! through Cognito.
+
+use crate::auth::{UserGroup, UserInfo};
+use crate::{graphql_error, DailpError, Database, DateTime};
+use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory, NextParseQuery};
+use async_graphql::parser::types::{ExecutableDocument, OperationType};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult, ServerResult, Variables};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Name of the HTTP header that requests send their API key in.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// A key that a user made for calling the API from scripts. The key itself is
+/// only shown once, when it is made.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
+pub struct ApiKey {
+    /// Unique ID of the key
+    pub id: Uuid,
+    /// The user who owns the key
+    #[graphql(skip)]
+    pub user_id: Uuid,
+    /// What the owner called the key
+    pub name: String,
+    /// The first few characters of the key, to tell keys apart
+    pub prefix: String,
+    /// Whether the key can only be used for queries
+    pub read_only: bool,
+    /// Groups that requests made with the key act in
+    pub groups: Vec<UserGroup>,
+    /// When the key was made
+    pub created_at: DateTime,
+    /// When the key stops working, if ever
+    pub expires_at: Option<DateTime>,
+    /// When the key was last used
+    pub last_used_at: Option<DateTime>,
+    /// When the owner revoked the key
+    pub revoked_at: Option<DateTime>,
+}
+
+#[async_graphql::ComplexObject]
+impl ApiKey {
+    /// Most recent requests made with this key
+    async fn usage(
+        &self,
+        context: &Context<'_>,
+        #[graphql(default = 50)] limit: i64,
+    ) -> FieldResult<Vec<ApiKeyUse>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .api_key_usage(self.id, limit)
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+/// One request made with an API key.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+pub struct ApiKeyUse {
+    /// When the request was made
+    pub used_at: DateTime,
+    /// Name of the GraphQL operation, if the request gave one
+    pub operation_name: Option<String>,
+}
+
+/// A newly made API key, along with the key itself.
+#[derive(async_graphql::SimpleObject)]
+pub struct NewApiKey {
+    /// The key to send in the `X-API-Key` header. It can't be shown again.
+    pub key: String,
+    /// Details of the key
+    pub api_key: ApiKey,
+}
+
+/// Make an API key for the signed-in user.
+#[derive(async_graphql::InputObject)]
+pub struct CreateApiKeyInput {
+    /// What to call the key, like the script it's for
+    pub name: String,
+    /// Only allow queries with this key
+    #[graphql(default)]
+    pub read_only: bool,
+    /// Groups that requests made with the key act in, which must be ones the
+    /// user is in
+    #[graphql(default)]
+    pub groups: Vec<UserGroup>,
+    /// How many days until the key stops working. Keys without this work
+    /// until they're revoked.
+    pub expires_in_days: Option<i32>,
+}
+
+impl CreateApiKeyInput {
+    /// Make sure the key has a name, doesn't give more rights than the user
+    /// has, and doesn't give rights when it's read-only.
+    pub fn validate(&self, user: &UserInfo) -> anyhow::Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(DailpError::Validation("API key name can't be blank".into()).into());
+        }
+        if self.read_only && !self.groups.is_empty() {
+            return Err(
+                DailpError::Validation("Read-only API keys can't have groups".into()).into(),
+            );
+        }
+        if let Some(group) = self.groups.iter().find(|group| !user.is_in(**group)) {
+            return Err(DailpError::Forbidden(format!(
+                "Can't make an API key for group '{}'",
+                group
+            ))
+            .into());
+        }
+        if matches!(self.expires_in_days, Some(days) if days <= 0) {
+            return Err(
+                DailpError::Validation("API keys must expire in at least one day".into()).into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Request data for requests made with an API key.
+#[derive(Clone, Debug)]
+pub struct ApiKeyAccess {
+    /// ID of the key
+    pub key_id: Uuid,
+    /// Whether the key can only be used for queries
+    pub read_only: bool,
+    /// Groups that requests made with the key act in, which also limit the
+    /// roles on single collections or documents that they can use
+    pub groups: Vec<UserGroup>,
+}
+
+/// Stops requests made with read-only API keys from running mutations.
+pub struct ReadOnlyApiKeys;
+
+impl ExtensionFactory for ReadOnlyApiKeys {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ReadOnlyApiKeysExtension)
+    }
+}
+
+struct ReadOnlyApiKeysExtension;
+
+#[async_trait::async_trait]
+impl Extension for ReadOnlyApiKeysExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let read_only = ctx
+            .data_opt::<ApiKeyAccess>()
+            .map_or(false, |access| access.read_only);
+        let has_mutation = document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation);
+        if read_only && has_mutation {
+            Err(
+                DailpError::Forbidden("Forbidden, read-only API keys can't run mutations".into())
+                    .server_error(),
+            )
+        } else {
+            Ok(document)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_keys_only_give_rights_the_user_has() {
+        let contributor = UserInfo::new(
+            Uuid::default(),
+            "someone@example.com".to_owned(),
+            vec![UserGroup::Contributors],
+        );
+        let input = |read_only, groups: &[UserGroup]| CreateApiKeyInput {
+            name: "Analysis".to_owned(),
+            read_only,
+            groups: groups.to_vec(),
+            expires_in_days: Some(30),
+        };
+        assert!(input(true, &[]).validate(&contributor).is_ok());
+        assert!(input(false, &[UserGroup::Contributors])
+            .validate(&contributor)
+            .is_ok());
+        assert!(input(false, &[UserGroup::Editors])
+            .validate(&contributor)
+            .is_err());
+        assert!(input(true, &[UserGroup::Contributors])
+            .validate(&contributor)
+            .is_err());
+    }
+}
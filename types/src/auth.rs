@@ -1,15 +1,63 @@
-use async_graphql::Guard;
+use crate::api_key::ApiKey;
+use crate::permission::PermissionGrant;
+use crate::user::UserProfile;
+use crate::{graphql_error, DailpError, Database, UserProfileById};
+use async_graphql::{dataloader::DataLoader, Context, ErrorExtensions, FieldResult, Guard};
 use serde::{Deserialize, Serialize};
 use serde_with::{rust::StringWithSeparator, CommaSeparator};
 use uuid::Uuid;
 
 /// Auth metadata on the user making the current request.
 #[derive(PartialEq, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
 pub struct UserInfo {
     /// Unique ID for the User. Should be an AWS Cognito Sub.
     pub id: Uuid,
-    email: String,
-    groups: Vec<UserGroup>,
+    /// Email address the user signed in with
+    pub email: String,
+    pub(crate) groups: Vec<UserGroup>,
+}
+
+#[async_graphql::ComplexObject]
+impl UserInfo {
+    /// Profile details and reading preferences of this user
+    async fn profile(&self, ctx: &Context<'_>) -> FieldResult<Option<UserProfile>> {
+        ctx.data::<DataLoader<Database>>()?
+            .load_one(UserProfileById(self.id))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Roles this user has been given on single collections or documents
+    async fn permissions(&self, ctx: &Context<'_>) -> FieldResult<Vec<PermissionGrant>> {
+        ctx.data::<DataLoader<Database>>()?
+            .loader()
+            .permission_grants(Some(self.id))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// API keys this user has made, including revoked ones
+    async fn api_keys(&self, ctx: &Context<'_>) -> FieldResult<Vec<ApiKey>> {
+        ctx.data::<DataLoader<Database>>()?
+            .loader()
+            .api_keys(self.id)
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+impl UserInfo {
+    /// Auth metadata for a user who isn't signed in through Cognito, like one
+    /// using an API key.
+    pub fn new(id: Uuid, email: String, groups: Vec<UserGroup>) -> Self {
+        Self { id, email, groups }
+    }
+
+    /// Is this user a member of the given group?
+    pub fn is_in(&self, group: UserGroup) -> bool {
+        self.groups.contains(&group)
+    }
 }
 
 /// serde deserialization struct for UserInfo.
@@ -53,12 +101,21 @@ pub struct ApiGatewayUserInfoDef {
 pub struct ApiGatewayUserInfo(#[serde(with = "ApiGatewayUserInfoDef")] pub UserInfo);
 
 /// A user belongs to any number of user groups, which give them various permissions.
-#[derive(Eq, PartialEq, Copy, Clone, Serialize, Deserialize, Debug, async_graphql::Enum)]
+#[derive(
+    Eq, PartialEq, Copy, Clone, Serialize, Deserialize, Debug, async_graphql::Enum, sqlx::Type,
+)]
+#[sqlx(type_name = "user_group")]
 pub enum UserGroup {
     Contributors,
     Editors,
 }
 
+impl sqlx::postgres::PgHasArrayType for UserGroup {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_user_group")
+    }
+}
+
 // // Impl FromStr and Display automatically for UserGroup, using serde.
 // // This allows us to (de)serialize lists of groups via a comma-separated string
 // // like this: "Editor,Contributor,Translator"
@@ -80,7 +137,7 @@ impl GroupGuard {
 impl Guard for GroupGuard {
     async fn check(&self, ctx: &async_graphql::Context<'_>) -> async_graphql::Result<()> {
         let user = ctx.data_opt::<UserInfo>();
-        let has_group = user.map(|user| user.groups.iter().any(|group| group == &self.group));
+        let has_group = user.map(|user| user.is_in(self.group));
 
         match user {
             Some(user) => log::info!("Debug user info groups={:?}", user.clone()),
@@ -90,7 +147,10 @@ impl Guard for GroupGuard {
         if has_group == Some(true) {
             Ok(())
         } else {
-            Err(format!("Forbidden, user not in group '{:?}'", self.group).into())
+            Err(
+                DailpError::Forbidden(format!("Forbidden, user not in group '{:?}'", self.group))
+                    .extend(),
+            )
         }
     }
 }
@@ -105,7 +165,7 @@ impl Guard for AuthGuard {
         if user.is_some() {
             Ok(())
         } else {
-            Err("Forbidden, user not authenticated".into())
+            Err(DailpError::Forbidden("Forbidden, user not authenticated".into()).extend())
         }
     }
 }
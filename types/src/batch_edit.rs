@@ -0,0 +1,206 @@
+//! Editing many words at once, for fixing mistakes that repeat across a
+//! document or collection, like a misspelled gloss.
+
+use crate::permission::PermissionTarget;
+use crate::{graphql_error, AnnotatedForm, AnnotatedFormUpdate, CherokeeOrthography, Database};
+use async_graphql::{dataloader::DataLoader, FieldResult};
+use sqlx::types::Uuid;
+
+/// Largest number of words that may be changed at once, either by a batch of
+/// updates or by one find and replace.
+pub const MAX_BATCH_SIZE: usize = 1000;
+
+/// Outcome of one update in a batch of word updates
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
+pub struct WordUpdateResult {
+    /// ID of the word this update was for
+    pub word_id: Uuid,
+    /// Whether this update was saved. Batches are saved all at once or not at
+    /// all, so if any update has an error then none are saved.
+    pub saved: bool,
+    /// Why this update can't be saved, if it can't
+    pub error: Option<String>,
+    /// Current version of the word, if the update was based on an older one
+    pub current_version: Option<i64>,
+    /// Document containing the word, once it's saved
+    #[graphql(skip)]
+    pub document_id: Option<Uuid>,
+}
+
+#[async_graphql::ComplexObject]
+impl WordUpdateResult {
+    /// The word as it is now
+    async fn word(&self, context: &async_graphql::Context<'_>) -> FieldResult<AnnotatedForm> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.word_by_id(&self.word_id).await.map_err(graphql_error)
+    }
+}
+
+impl WordUpdateResult {
+    /// Check an update before anything in its batch is saved, given the
+    /// current version of the word and whether the word was already updated
+    /// earlier in the batch.
+    pub fn check(
+        update: &AnnotatedFormUpdate,
+        current_version: Option<i64>,
+        repeated: bool,
+    ) -> Self {
+        let mut result = Self {
+            word_id: update.id,
+            saved: false,
+            error: None,
+            current_version: None,
+            document_id: None,
+        };
+        let unsupported_system = update
+            .segments
+            .value()
+            .and_then(|segments| segments.first())
+            .map_or(false, |segment| {
+                segment.system != Some(CherokeeOrthography::Taoc)
+            });
+        match current_version {
+            _ if repeated => {
+                result.error = Some("This word is updated more than once in the batch".to_owned())
+            }
+            None => result.error = Some("There is no word with this ID".to_owned()),
+            Some(version) if version != update.version => {
+                result.error =
+                    Some("Someone else changed this word since you loaded it".to_owned());
+                result.current_version = Some(version);
+            }
+            Some(_) if unsupported_system => {
+                result.error = Some("Other Cherokee systems are currently not supported".to_owned())
+            }
+            Some(_) => {}
+        }
+        result
+    }
+}
+
+/// Which part of a word find and replace should change
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaceField {
+    /// Original source text
+    Source,
+    /// Annotator commentary
+    Commentary,
+    /// Glosses of word segments, which are only replaced when they match
+    /// exactly
+    Gloss,
+}
+
+impl ReplaceField {
+    /// Name of this field as passed to our queries.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Source => "Source",
+            Self::Commentary => "Commentary",
+            Self::Gloss => "Gloss",
+        }
+    }
+}
+
+/// Replace some text in every word of a document or collection
+#[derive(async_graphql::InputObject)]
+pub struct FindAndReplaceInput {
+    /// Text to look for
+    pub find: String,
+    /// Text to put in its place
+    pub replace: String,
+    /// Which part of each word to change
+    pub field: ReplaceField,
+    /// Only change words in this document
+    pub document_id: Option<Uuid>,
+    /// Only change words in documents of this collection, by slug
+    pub collection: Option<String>,
+    /// Only list the words that would change, without changing them
+    #[graphql(default)]
+    pub dry_run: bool,
+}
+
+impl FindAndReplaceInput {
+    /// What an editor needs rights on to make these replacements. Replacing
+    /// across a whole collection without naming a document is left to members
+    /// of the Editors group.
+    pub fn permission_targets(&self) -> Vec<PermissionTarget> {
+        self.document_id
+            .map(PermissionTarget::Document)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// One word changed by find and replace, or that would be in a dry run
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+pub struct Replacement {
+    /// ID of the changed word
+    pub word_id: Uuid,
+    /// Document containing the word
+    pub document_id: Uuid,
+    /// The field before replacing. Glosses are shown separated by dashes.
+    pub before: String,
+    /// The field after replacing
+    pub after: String,
+}
+
+/// Replace every segment gloss that exactly matches `find`, or return `None`
+/// if none match.
+pub fn replace_gloss(glosses: &[String], find: &str, replace: &str) -> Option<Vec<String>> {
+    if !glosses.iter().any(|gloss| gloss == find) {
+        return None;
+    }
+    Some(
+        glosses
+            .iter()
+            .map(|gloss| {
+                if gloss == find {
+                    replace.to_owned()
+                } else {
+                    gloss.clone()
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::MaybeUndefined;
+
+    #[test]
+    fn replaces_whole_glosses() {
+        let glosses = vec!["3SG".to_owned(), "wlak".to_owned(), "wlaker".to_owned()];
+        assert_eq!(
+            replace_gloss(&glosses, "wlak", "walk"),
+            Some(vec![
+                "3SG".to_owned(),
+                "walk".to_owned(),
+                "wlaker".to_owned()
+            ])
+        );
+        assert_eq!(replace_gloss(&glosses, "wla", "wa"), None);
+    }
+
+    #[test]
+    fn checks_updates_before_saving() {
+        let update = AnnotatedFormUpdate {
+            id: Uuid::nil(),
+            version: 2,
+            source: MaybeUndefined::Value("ᎠᏍᎦᏯ".to_owned()),
+            commentary: MaybeUndefined::Undefined,
+            segments: MaybeUndefined::Undefined,
+        };
+        assert_eq!(WordUpdateResult::check(&update, Some(2), false).error, None);
+        assert!(WordUpdateResult::check(&update, Some(2), true)
+            .error
+            .is_some());
+        assert!(WordUpdateResult::check(&update, None, false)
+            .error
+            .is_some());
+        let stale = WordUpdateResult::check(&update, Some(3), false);
+        assert_eq!(stale.current_version, Some(3));
+    }
+}
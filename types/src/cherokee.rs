@@ -7,7 +7,19 @@ use sqlx::postgres::*;
 /// want to convert between them.
 /// This type enumerates all of the systems that we support and provides
 /// conversion from our internal orthography into any of these.
-#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+#[derive(
+    sqlx::Type,
+    async_graphql::Enum,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Hash,
+    Debug,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[sqlx(type_name = "cherokee_orthography")]
 pub enum CherokeeOrthography {
     /// The t/th system for transcribing the Cherokee syllabary.
     /// This orthography is favored by linguists as it is segmentally more accurate.
@@ -1,12 +1,15 @@
 use uuid::Uuid;
 
+use crate::graphql_error;
+use crate::page::{ContentBlock, ContentBlockInput};
+use crate::permission::PermissionTarget;
 use crate::AnnotatedDoc;
 use {
-    crate::async_graphql::{self, dataloader::DataLoader, Context, FieldResult},
-    crate::slugify,
-    crate::Database,
+    crate::async_graphql::{self, dataloader::DataLoader, Context, FieldResult, MaybeUndefined},
     crate::DocumentCollection,
     crate::DocumentId,
+    crate::{slugify, slugify_ltree},
+    crate::{DailpError, Database},
 };
 
 /// Structure to represent an edited collection. Missing certain fields and chapters in it.
@@ -19,6 +22,7 @@ pub struct EditedCollection {
     /// Full title of the collection
     pub title: String,
     /// ID of WordPress menu for navigating the collection
+    #[graphql(deprecation = "Use `navigation` instead")]
     pub wordpress_menu_id: Option<i64>,
     #[graphql(skip)]
     /// URL slug for the collection, like "cwkw"
@@ -34,8 +38,10 @@ pub struct CollectionChapter {
     /// Full title of the chapter
     pub title: String,
     /// ID of WordPress page with text of the chapter
+    #[graphql(deprecation = "Use `body` instead")]
     pub wordpress_id: std::option::Option<i64>,
-    /// Order within the parent chapter or collection
+    /// How deeply nested this chapter is, starting from 1 for chapters directly
+    /// in the collection
     pub index_in_parent: i64,
     /// Whether the chapter is an "Intro" or "Body" chapter
     pub section: CollectionSection,
@@ -67,10 +73,23 @@ impl EditedCollection {
     }
 
     async fn chapters(&self, context: &Context<'_>) -> FieldResult<Option<Vec<CollectionChapter>>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(crate::ChaptersInCollection(self.slug.clone()))
-            .await?)
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Links to every chapter of the collection, nested the same way as the
+    /// chapters themselves
+    async fn navigation(&self, context: &Context<'_>) -> FieldResult<Vec<NavigationItem>> {
+        let chapters = context
+            .data::<DataLoader<Database>>()?
+            .load_one(crate::ChaptersInCollection(self.slug.clone()))
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default();
+        Ok(navigation_tree(&chapters))
     }
 }
 
@@ -85,12 +104,23 @@ impl CollectionChapter {
         slugify((&self.path.last()).unwrap())
     }
 
+    /// Content of the chapter, in order
+    async fn body(&self, context: &Context<'_>) -> FieldResult<Vec<ContentBlock>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .chapter_body(self.id)
+            .await
+            .map_err(graphql_error)
+    }
+
     async fn document(&self, context: &Context<'_>) -> FieldResult<Option<AnnotatedDoc>> {
         if let Some(doc_id) = &self.document_id {
             Ok(context
                 .data::<DataLoader<Database>>()?
                 .load_one(doc_id.clone())
-                .await?)
+                .await
+                .map_err(graphql_error)?)
         } else {
             Ok(None)
         }
@@ -100,10 +130,263 @@ impl CollectionChapter {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> FieldResult<Vec<DocumentCollection>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .chapter_breadcrumbs(self.path.clone())
-            .await?)
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+/// A link to one chapter in the navigation menu of an edited collection.
+#[derive(Debug, Clone, async_graphql::SimpleObject)]
+pub struct NavigationItem {
+    /// UUID of the linked chapter
+    pub chapter_id: Uuid,
+    /// Full title of the chapter
+    pub title: String,
+    /// URL slug of the chapter
+    pub slug: String,
+    /// Full path of the chapter
+    pub path: Vec<String>,
+    /// Whether the chapter is an "Intro" or "Body" chapter
+    pub section: CollectionSection,
+    /// Links to the subchapters of this chapter, in order
+    pub children: Vec<NavigationItem>,
+}
+
+/// Nest a list of chapters into a navigation menu. Chapters must be in the
+/// order they are stored, with each one followed by all of its subchapters.
+pub fn navigation_tree(chapters: &[CollectionChapter]) -> Vec<NavigationItem> {
+    let mut items = Vec::new();
+    let mut rest = chapters;
+    while let Some((chapter, after)) = rest.split_first() {
+        let subchapters = after
+            .iter()
+            .take_while(|c| c.path.starts_with(&chapter.path))
+            .count();
+        let path: Vec<_> = chapter.path.iter().map(slugify).collect();
+        items.push(NavigationItem {
+            chapter_id: chapter.id,
+            title: chapter.title.clone(),
+            slug: path.last().cloned().unwrap_or_default(),
+            path,
+            section: chapter.section,
+            children: navigation_tree(&after[..subchapters]),
+        });
+        rest = &after[subchapters..];
+    }
+    items
+}
+
+/// Make a new edited collection with no chapters
+#[derive(async_graphql::InputObject)]
+pub struct CreateEditedCollectionInput {
+    /// Full title of the collection
+    pub title: String,
+    /// URL slug for the collection, like "cwkw". Made from the title if not
+    /// given.
+    pub slug: Option<String>,
+    /// ID of WordPress menu for navigating the collection
+    pub wordpress_menu_id: Option<i64>,
+}
+
+/// Add a chapter to an edited collection
+#[derive(async_graphql::InputObject)]
+pub struct AddChapterInput {
+    /// Slug of the collection to add the chapter to
+    pub collection_slug: String,
+    /// ID of the chapter to nest this one under, or nothing to add it directly
+    /// to the collection
+    pub parent_id: Option<Uuid>,
+    /// Full title of the chapter
+    pub title: String,
+    /// URL slug for the chapter, unique within the collection. Made from the
+    /// title if not given.
+    pub slug: Option<String>,
+    /// Which section of the collection the chapter is in
+    #[graphql(default_with = "CollectionSection::Body")]
+    pub section: CollectionSection,
+    /// ID of a document to show in the chapter
+    pub document_id: Option<Uuid>,
+    /// ID of WordPress page with text of the chapter
+    pub wordpress_id: Option<i64>,
+    /// Content of the chapter, in order
+    #[graphql(default)]
+    pub body: Vec<ContentBlockInput>,
+    /// Zero-indexed position among the other chapters with the same parent,
+    /// or nothing to add it after all of them
+    pub index: Option<i64>,
+}
+
+impl AddChapterInput {
+    /// What an editor needs rights on to add this chapter: its collection, and
+    /// the document it shows, since chapters pass collection roles on to
+    /// their documents.
+    pub fn permission_targets(&self) -> Vec<PermissionTarget> {
+        let collection = PermissionTarget::EditedCollection(self.collection_slug.clone());
+        std::iter::once(collection)
+            .chain(self.document_id.map(PermissionTarget::Document))
+            .collect()
+    }
+}
+
+/// Change the details of a chapter. Fields that aren't given stay the same.
+#[derive(async_graphql::InputObject)]
+pub struct UpdateChapterInput {
+    /// ID of the chapter to change
+    pub id: Uuid,
+    /// New title of the chapter
+    pub title: Option<String>,
+    /// New URL slug for the chapter, which also changes the paths of its
+    /// subchapters
+    pub slug: Option<String>,
+    /// New section of the collection for the chapter
+    pub section: Option<CollectionSection>,
+    /// ID of the document to show in the chapter, or null to show none
+    pub document_id: MaybeUndefined<Uuid>,
+    /// ID of WordPress page with text of the chapter, or null for none
+    pub wordpress_id: MaybeUndefined<i64>,
+    /// New content of the chapter, replacing all of its blocks
+    pub body: Option<Vec<ContentBlockInput>>,
+}
+
+impl UpdateChapterInput {
+    /// What an editor needs rights on to make this change: the chapter, and
+    /// the document it will show if that changes.
+    pub fn permission_targets(&self) -> Vec<PermissionTarget> {
+        std::iter::once(PermissionTarget::Chapter(self.id))
+            .chain(
+                self.document_id
+                    .value()
+                    .copied()
+                    .map(PermissionTarget::Document),
+            )
+            .collect()
+    }
+}
+
+/// Move a chapter, along with its subchapters, to a new place in its
+/// collection. Moving a chapter within the same parent reorders it.
+#[derive(async_graphql::InputObject)]
+pub struct MoveChapterInput {
+    /// ID of the chapter to move
+    pub id: Uuid,
+    /// ID of the chapter to nest this one under, or nothing to move it
+    /// directly into the collection
+    pub parent_id: Option<Uuid>,
+    /// Zero-indexed position among the chapters with the new parent, or
+    /// nothing to put it after all of them
+    pub index: Option<i64>,
+}
+
+/// Turn a title or slug into one label of a chapter path.
+pub fn chapter_slug(s: &str) -> anyhow::Result<String> {
+    let slug = slugify_ltree(s);
+    if slug.is_empty() {
+        return Err(DailpError::Validation(format!("\"{}\" doesn't make a usable slug", s)).into());
+    }
+    Ok(slug)
+}
+
+/// Order a chapter among its siblings, putting it at the given index or after
+/// all of them.
+pub fn place_chapter(siblings: &[Uuid], chapter_id: Uuid, index: Option<i64>) -> Vec<Uuid> {
+    let mut ordered: Vec<_> = siblings
+        .iter()
+        .copied()
+        .filter(|id| *id != chapter_id)
+        .collect();
+    let index = index.map_or(ordered.len(), |i| (i.max(0) as usize).min(ordered.len()));
+    ordered.insert(index, chapter_id);
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_chapters_among_siblings() {
+        let [a, b, c] = [1, 2, 3].map(Uuid::from_u128);
+        assert_eq!(place_chapter(&[a, b, c], c, Some(0)), [c, a, b]);
+        assert_eq!(place_chapter(&[a, b, c], a, None), [b, c, a]);
+        assert_eq!(place_chapter(&[a, b], c, Some(9)), [a, b, c]);
+        assert!(chapter_slug("!!").is_err());
+        assert_eq!(chapter_slug("Letters Home").unwrap(), "letters_home");
+    }
+
+    #[test]
+    fn nests_navigation_by_path() {
+        let chapter = |id, path: &[&str]| CollectionChapter {
+            id: Uuid::from_u128(id),
+            title: path.last().unwrap().to_string(),
+            wordpress_id: None,
+            index_in_parent: path.len() as i64 - 1,
+            section: CollectionSection::Body,
+            document_id: None,
+            path: path.iter().map(|s| s.to_string()).collect(),
+        };
+        let menu = navigation_tree(&[
+            chapter(1, &["cwkw", "intro"]),
+            chapter(2, &["cwkw", "letters"]),
+            chapter(3, &["cwkw", "letters", "first"]),
+            chapter(4, &["cwkw", "letters", "first", "notes"]),
+            chapter(5, &["cwkw", "letters", "second"]),
+            chapter(6, &["cwkw", "credits"]),
+        ]);
+        let titles = |items: &[NavigationItem]| -> Vec<String> {
+            items.iter().map(|item| item.title.clone()).collect()
+        };
+        assert_eq!(titles(&menu), ["intro", "letters", "credits"]);
+        assert_eq!(titles(&menu[1].children), ["first", "second"]);
+        assert_eq!(titles(&menu[1].children[0].children), ["notes"]);
+        assert_eq!(menu[1].children[1].path, ["cwkw", "letters", "second"]);
+    }
+
+    #[test]
+    fn collection_editors_need_rights_on_documents_they_attach() {
+        let foreign = Uuid::from_u128(9);
+        let add = AddChapterInput {
+            collection_slug: "cwkw".into(),
+            parent_id: None,
+            title: "Letters".into(),
+            slug: None,
+            section: CollectionSection::Body,
+            document_id: Some(foreign),
+            wordpress_id: None,
+            body: Vec::new(),
+            index: None,
+        };
+        assert!(matches!(
+            &add.permission_targets()[..],
+            [
+                PermissionTarget::EditedCollection(slug),
+                PermissionTarget::Document(id),
+            ] if slug == "cwkw" && *id == foreign
+        ));
+
+        let chapter = Uuid::from_u128(1);
+        let update = |document_id| UpdateChapterInput {
+            id: chapter,
+            title: None,
+            slug: None,
+            section: None,
+            document_id,
+            wordpress_id: MaybeUndefined::Undefined,
+            body: None,
+        };
+        assert!(matches!(
+            &update(MaybeUndefined::Value(foreign)).permission_targets()[..],
+            [PermissionTarget::Chapter(a), PermissionTarget::Document(b)]
+                if *a == chapter && *b == foreign
+        ));
+        for document_id in [MaybeUndefined::Undefined, MaybeUndefined::Null] {
+            assert!(matches!(
+                &update(document_id).permission_targets()[..],
+                [PermissionTarget::Chapter(_)]
+            ));
+        }
     }
 }
@@ -1,7 +1,8 @@
 //! Types that power our features for reading / leaving comments on words and
 //! paragraphs
-use crate::{user::User, AnnotatedForm};
-use crate::{Database, DateTime, DocumentParagraph};
+use crate::auth::{GroupGuard, UserGroup, UserInfo};
+use crate::{graphql_error, user::User, AnnotatedForm};
+use crate::{Database, DateTime, DocumentParagraph, ProposedEdit};
 use async_graphql::Context;
 use async_graphql::{dataloader::DataLoader, FieldResult};
 use serde::{Deserialize, Serialize};
@@ -10,7 +11,7 @@ use sqlx::types::Uuid;
 /// A comment a user has made on some piece of a document.
 #[derive(Clone, Serialize, Deserialize, Debug, async_graphql::SimpleObject)]
 #[serde(rename_all = "camelCase")]
-// #[graphql(complex)]
+#[graphql(complex)]
 pub struct Comment {
     /// Unique identifier of this comment
     pub id: Uuid,
@@ -24,8 +25,21 @@ pub struct Comment {
     pub text_content: String,
     /// An optional classification of the comment's content
     pub comment_type: Option<CommentType>,
+    /// When the comment was last edited, if it has been
+    pub edited_at: Option<DateTime>,
 
-    /// The id of the word or paragraph this comment is attached to
+    /// When an editor hid this comment from readers, if they did
+    pub hidden_at: Option<DateTime>,
+    /// Why the comment was hidden, which only editors can see
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    pub hidden_reason: Option<String>,
+
+    /// When this suggestion or question was resolved, if it has been
+    pub resolved_at: Option<DateTime>,
+    /// Who resolved this suggestion or question
+    pub resolved_by: Option<User>,
+
+    /// The id of the word, paragraph, or comment this comment is attached to
     #[graphql(skip = true)]
     pub parent_id: Uuid,
     /// The kind of entity parent ID points to
@@ -43,17 +57,30 @@ pub enum CommentParentType {
     Word,
     /// A comment attached to a paragraph
     Paragraph,
+    /// A reply to another comment
+    Comment,
 }
 
 impl CommentParentType {
-    /// Get the actual object referenced by this type, given an id
-    pub async fn resolve(&self, db: &Database, parent_id: &Uuid) -> FieldResult<CommentParent> {
+    /// Get the actual object referenced by this type, given an id. Hidden
+    /// parent comments are only found if asked for.
+    pub async fn resolve(
+        &self,
+        db: &Database,
+        parent_id: &Uuid,
+        include_hidden: bool,
+    ) -> FieldResult<CommentParent> {
         match &self {
-            CommentParentType::Word => {
-                Ok(CommentParent::WordParent(db.word_by_id(parent_id).await?))
-            }
+            CommentParentType::Word => Ok(CommentParent::WordParent(
+                db.word_by_id(parent_id).await.map_err(graphql_error)?,
+            )),
             CommentParentType::Paragraph => Ok(CommentParent::ParagraphParent(
-                db.paragraph_by_id(parent_id).await?,
+                db.paragraph_by_id(parent_id).await.map_err(graphql_error)?,
+            )),
+            CommentParentType::Comment => Ok(CommentParent::CommentParent(
+                db.comment_by_id(parent_id, include_hidden)
+                    .await
+                    .map_err(graphql_error)?,
             )),
         }
     }
@@ -64,10 +91,38 @@ impl Comment {
     /// The parent entity of this comment
     pub async fn parent(&self, context: &Context<'_>) -> FieldResult<CommentParent> {
         let db = context.data::<DataLoader<Database>>()?.loader();
-        self.parent_type.resolve(db, &self.parent_id).await
+        self.parent_type
+            .resolve(db, &self.parent_id, can_moderate(context))
+            .await
+    }
+
+    /// Replies to this comment, oldest first
+    pub async fn replies(&self, context: &Context<'_>) -> FieldResult<Vec<Comment>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.comments_by_parent(&self.id, &CommentParentType::Comment, can_moderate(context))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// The change this suggestion proposes, if it came with one
+    pub async fn proposed_edit(&self, context: &Context<'_>) -> FieldResult<Option<ProposedEdit>> {
+        if self.comment_type != Some(CommentType::Suggestion) {
+            return Ok(None);
+        }
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.proposed_edit_by_comment(&self.id)
+            .await
+            .map_err(graphql_error)
     }
 }
 
+/// Can the current user see and moderate hidden comments?
+pub fn can_moderate(context: &Context<'_>) -> bool {
+    context
+        .data_opt::<UserInfo>()
+        .map_or(false, |user| user.is_in(UserGroup::Editors))
+}
+
 /// A type describing the kind of comment being made
 #[derive(
     sqlx::Type, async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize,
@@ -89,6 +144,8 @@ pub enum CommentParent {
     WordParent(AnnotatedForm),
     /// The paragraph that the given comment is attached to
     ParagraphParent(DocumentParagraph),
+    /// The comment that the given comment replies to
+    CommentParent(Comment),
 }
 
 /// Input object for posting a new comment on some object
@@ -110,3 +167,23 @@ pub struct DeleteCommentInput {
     /// ID of the comment to delete
     pub comment_id: Uuid,
 }
+
+/// Input object for changing the content of an existing comment
+#[derive(async_graphql::InputObject)]
+pub struct EditCommentInput {
+    /// ID of the comment to edit
+    pub comment_id: Uuid,
+    /// New content of the comment
+    pub text_content: String,
+    /// New classification for the comment (optional)
+    pub comment_type: Option<CommentType>,
+}
+
+/// Input object for hiding a comment from readers
+#[derive(async_graphql::InputObject)]
+pub struct HideCommentInput {
+    /// ID of the comment to hide
+    pub comment_id: Uuid,
+    /// Why the comment is being hidden, shown to other editors
+    pub reason: String,
+}
@@ -1,25 +1,33 @@
 #![allow(missing_docs)]
 
 use chrono::{NaiveDate, NaiveDateTime};
-use sqlx::postgres::types::PgLTree;
+use sqlx::postgres::types::{PgLTree, PgLTreeLabel};
 use std::ops::Bound;
 use std::str::FromStr;
 
+use crate::api_key::{ApiKey, ApiKeyAccess, ApiKeyUse, CreateApiKeyInput, NewApiKey};
+use crate::auth::{UserGroup, UserInfo};
 use crate::collection::CollectionChapter;
 use crate::collection::EditedCollection;
 use crate::comment::{Comment, CommentParentType, CommentType};
+use crate::error::DailpError;
+use crate::page::PageStatus;
+use crate::permission::{GrantPermissionInput, PermissionGrant, PermissionScope, PermissionTarget};
 use crate::user::User;
 use crate::user::UserId;
+use crate::user::{
+    InterfaceLanguage, InviteUserInput, RoleInvitation, UpdateProfileInput, UserProfile,
+};
 use {
     crate::*,
     anyhow::Result,
     async_graphql::dataloader::*,
-    async_graphql::InputType,
+    async_graphql::{InputType, MaybeUndefined},
     async_trait::async_trait,
     itertools::Itertools,
     sqlx::{
         postgres::{types::PgRange, PgPoolOptions},
-        query_file, query_file_as, query_file_scalar, Acquire,
+        query_file, query_file_as, query_file_scalar, Acquire, Connection,
     },
     std::collections::HashMap,
     std::sync::Arc,
@@ -31,43 +39,66 @@ use {
 /// for accessing the data therein.
 pub struct Database {
     client: sqlx::Pool<sqlx::Postgres>,
+    max_connections: u32,
 }
 impl Database {
     pub fn connect(num_connections: Option<u32>) -> Result<Self> {
         let db_url = std::env::var("DATABASE_URL")?;
+        let max_connections = num_connections
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(2, |x| x.get() as u32));
         let conn = PgPoolOptions::new()
-            .max_connections(num_connections.unwrap_or_else(|| {
-                std::thread::available_parallelism().map_or(2, |x| x.get() as u32)
-            }))
+            .max_connections(max_connections)
             .acquire_timeout(Duration::from_secs(60 * 8))
             .max_lifetime(Duration::from_secs(60 * 20))
             // Disable excessive pings to the database.
             .test_before_acquire(false)
             .connect_lazy(&db_url)?;
-        Ok(Database { client: conn })
+        Ok(Database {
+            client: conn,
+            max_connections,
+        })
     }
 
-    /// Get a specific comment by id
-    pub async fn comment_by_id(&self, comment_id: &Uuid) -> Result<Comment> {
-        Ok(
-            query_file_as!(BasicComment, "queries/comment_by_id.sql", comment_id,)
-                .fetch_one(&self.client)
-                .await?
-                .into(),
+    /// The connection pool and the most connections it may open, for
+    /// reporting how busy it is.
+    pub(crate) fn pool(&self) -> (&sqlx::PgPool, u32) {
+        (&self.client, self.max_connections)
+    }
+
+    /// Check that the database accepts queries.
+    pub async fn ping(&self) -> Result<()> {
+        self.client.acquire().await?.ping().await?;
+        Ok(())
+    }
+
+    /// Get a specific comment by id. Hidden comments are only found if asked
+    /// for.
+    pub async fn comment_by_id(&self, comment_id: &Uuid, include_hidden: bool) -> Result<Comment> {
+        query_file_as!(
+            BasicComment,
+            "queries/comment_by_id.sql",
+            comment_id,
+            include_hidden
         )
+        .fetch_optional(&self.client)
+        .await?
+        .map(|comment| comment.into())
+        .ok_or_else(|| DailpError::NotFound("That comment doesn't exist".into()).into())
     }
 
-    /// Get all comments on a given object
+    /// Get all comments on a given object, including hidden ones if asked.
     pub async fn comments_by_parent(
         &self,
         parent_id: &Uuid,
         parent_type: &CommentParentType,
+        include_hidden: bool,
     ) -> Result<Vec<Comment>> {
         Ok(query_file_as!(
             BasicComment,
             "queries/comments_by_parent.sql",
             parent_id,
-            parent_type.clone() as CommentParentType
+            parent_type.clone() as CommentParentType,
+            include_hidden
         )
         .fetch_all(&self.client)
         .await?
@@ -97,13 +128,267 @@ impl Database {
         .await?)
     }
 
-    /// Delete a comment from the database
-    pub async fn delete_comment(&self, comment_id: &Uuid) -> Result<Uuid> {
-        Ok(
-            query_file_scalar!("queries/delete_comment.sql", comment_id,)
+    /// Delete a comment from the database, along with all replies to it.
+    /// Comments that others have replied to are only deleted when
+    /// `delete_others_replies` is set.
+    pub async fn delete_comment(
+        &self,
+        comment_id: &Uuid,
+        delete_others_replies: bool,
+    ) -> Result<Uuid> {
+        let deleted = query_file!(
+            "queries/delete_comment.sql",
+            comment_id,
+            delete_others_replies
+        )
+        .execute(&self.client)
+        .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(DailpError::Conflict(
+                "Others have replied to this comment, so only an editor can delete it".into(),
+            )
+            .into());
+        }
+        Ok(*comment_id)
+    }
+
+    /// Change the content of a comment, marking it as edited
+    pub async fn edit_comment(
+        &self,
+        comment_id: &Uuid,
+        text_content: String,
+        comment_type: &Option<CommentType>,
+    ) -> Result<()> {
+        query_file!(
+            "queries/edit_comment.sql",
+            comment_id,
+            text_content,
+            comment_type.clone() as Option<CommentType>
+        )
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    /// Hide a comment from readers, or show it again if `reason` is `None`
+    pub async fn set_comment_hidden(
+        &self,
+        comment_id: &Uuid,
+        hidden_by: &Uuid,
+        reason: Option<String>,
+    ) -> Result<()> {
+        query_file!(
+            "queries/set_comment_hidden.sql",
+            comment_id,
+            reason.is_some(),
+            hidden_by,
+            reason
+        )
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    /// Mark a suggestion or question as resolved or open again
+    pub async fn set_comment_resolved(
+        &self,
+        comment_id: &Uuid,
+        resolved_by: &Uuid,
+        resolved: bool,
+    ) -> Result<()> {
+        query_file!(
+            "queries/set_comment_resolved.sql",
+            comment_id,
+            resolved,
+            resolved_by
+        )
+        .fetch_optional(&self.client)
+        .await?
+        .ok_or_else(|| {
+            DailpError::Validation("Only suggestions and questions can be resolved".into())
+        })?;
+        Ok(())
+    }
+
+    /// Unresolved suggestions on the words and paragraphs of a document
+    pub async fn open_suggestions(
+        &self,
+        document_id: &Uuid,
+        include_hidden: bool,
+    ) -> Result<Vec<Comment>> {
+        Ok(query_file_as!(
+            BasicComment,
+            "queries/open_suggestions.sql",
+            document_id,
+            include_hidden
+        )
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|c| c.into())
+        .collect())
+    }
+
+    /// The document containing the word or paragraph at the top of a comment
+    /// thread.
+    pub async fn comment_document_id(&self, comment_id: &Uuid) -> Result<DocumentId> {
+        Ok(DocumentId(
+            query_file_scalar!("queries/comment_document_id.sql", comment_id)
                 .fetch_one(&self.client)
                 .await?,
+        ))
+    }
+
+    /// Suggest a change to a word or paragraph, posting the explanation as a
+    /// suggestion comment on it. Returns the ID of the new comment.
+    pub async fn propose_edit(
+        &self,
+        posted_by: &Uuid,
+        text_content: String,
+        update: ProposedUpdate,
+    ) -> Result<Uuid> {
+        let target_type = update.target_type();
+        let target_id = update.target_id();
+        let mut tx = self.client.begin().await?;
+
+        // Check the update now, so editors only see edits they could accept.
+        let parent_type = match &update {
+            ProposedUpdate::Word(word) => {
+                let current_version = query_file_scalar!("queries/word_version.sql", target_id)
+                    .fetch_optional(&mut tx)
+                    .await?;
+                let checked = WordUpdateResult::check(word, current_version, false);
+                if let Some(error) = checked.error {
+                    return Err(match (current_version, checked.current_version) {
+                        (None, _) => DailpError::NotFound(error),
+                        (_, Some(_)) => DailpError::Conflict(error),
+                        _ => DailpError::Validation(error),
+                    }
+                    .into());
+                }
+                CommentParentType::Word
+            }
+            ProposedUpdate::Paragraph(paragraph) => {
+                match query_file_scalar!("queries/paragraph_version.sql", target_id)
+                    .fetch_optional(&mut tx)
+                    .await?
+                {
+                    None => {
+                        return Err(DailpError::NotFound(
+                            "There is no paragraph with this ID".into(),
+                        )
+                        .into())
+                    }
+                    Some(version) if version != paragraph.version => {
+                        return Err(DailpError::Conflict(
+                            "Someone else changed this paragraph since you loaded it".into(),
+                        )
+                        .into())
+                    }
+                    Some(_) => CommentParentType::Paragraph,
+                }
+            }
+        };
+
+        let comment_id = query_file_scalar!(
+            "queries/insert_comment.sql",
+            posted_by,
+            text_content,
+            target_id,
+            parent_type as CommentParentType,
+            Some(CommentType::Suggestion) as Option<CommentType>
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        query_file_scalar!(
+            "queries/insert_proposed_edit.sql",
+            comment_id,
+            target_type as _,
+            target_id,
+            update.to_json()?
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(comment_id)
+    }
+
+    /// The edit proposed alongside a suggestion comment, if there is one
+    pub async fn proposed_edit_by_comment(
+        &self,
+        comment_id: &Uuid,
+    ) -> Result<Option<ProposedEdit>> {
+        Ok(query_file_as!(
+            BasicProposedEdit,
+            "queries/proposed_edit_by_comment.sql",
+            comment_id
+        )
+        .fetch_optional(&self.client)
+        .await?
+        .map(|edit| edit.into()))
+    }
+
+    pub async fn proposed_edit_by_id(&self, proposed_edit_id: &Uuid) -> Result<ProposedEdit> {
+        Ok(query_file_as!(
+            BasicProposedEdit,
+            "queries/proposed_edit_by_id.sql",
+            proposed_edit_id
+        )
+        .fetch_one(&self.client)
+        .await?
+        .into())
+    }
+
+    /// Apply a proposed edit as the given editor, recording it in the edit
+    /// history and resolving the suggestion it came with. Fails if the word or
+    /// paragraph changed since the edit was proposed.
+    pub async fn accept_proposed_edit(
+        &self,
+        proposed_edit_id: &Uuid,
+        reviewed_by: &Uuid,
+    ) -> Result<ProposedEdit> {
+        let mut tx = self.client.begin().await?;
+        let proposal = lock_open_proposed_edit(&mut tx, proposed_edit_id).await?;
+        match proposal.update()? {
+            ProposedUpdate::Word(word) => {
+                update_word_in(&mut tx, word, reviewed_by).await?;
+            }
+            ProposedUpdate::Paragraph(paragraph) => {
+                update_paragraph_in(&mut tx, paragraph, reviewed_by).await?;
+            }
+        }
+        review_proposed_edit(
+            &mut tx,
+            &proposal,
+            ProposedEditStatus::Accepted,
+            reviewed_by,
+            None,
+        )
+        .await?;
+        tx.commit().await?;
+        self.proposed_edit_by_id(proposed_edit_id).await
+    }
+
+    /// Turn down a proposed edit, resolving the suggestion it came with.
+    pub async fn reject_proposed_edit(
+        &self,
+        proposed_edit_id: &Uuid,
+        reviewed_by: &Uuid,
+        note: String,
+    ) -> Result<ProposedEdit> {
+        let mut tx = self.client.begin().await?;
+        let proposal = lock_open_proposed_edit(&mut tx, proposed_edit_id).await?;
+        review_proposed_edit(
+            &mut tx,
+            &proposal,
+            ProposedEditStatus::Rejected,
+            reviewed_by,
+            Some(note),
         )
+        .await?;
+        tx.commit().await?;
+        self.proposed_edit_by_id(proposed_edit_id).await
     }
 
     pub async fn paragraph_by_id(&self, paragraph_id: &Uuid) -> Result<DocumentParagraph> {
@@ -116,6 +401,15 @@ impl Database {
         .await?)
     }
 
+    /// The document containing the given paragraph.
+    pub async fn paragraph_document_id(&self, paragraph_id: &Uuid) -> Result<DocumentId> {
+        Ok(DocumentId(
+            query_file_scalar!("queries/paragraph_document_id.sql", paragraph_id)
+                .fetch_one(&self.client)
+                .await?,
+        ))
+    }
+
     pub async fn word_by_id(&self, word_id: &Uuid) -> Result<AnnotatedForm> {
         Ok(query_file_as!(BasicWord, "queries/word_by_id.sql", word_id)
             .fetch_one(&self.client)
@@ -123,45 +417,332 @@ impl Database {
             .into())
     }
 
-    pub async fn upsert_contributor(&self, person: ContributorDetails) -> Result<()> {
-        query_file!("queries/upsert_contributor.sql", person.full_name)
+    /// Save a contributor by their full name, filling in any details they
+    /// didn't have yet.
+    pub async fn upsert_contributor(
+        &self,
+        full_name: &str,
+        alternate_name: Option<&str>,
+        birth_date: Option<Date>,
+    ) -> Result<()> {
+        query_file!(
+            "queries/upsert_contributor.sql",
+            full_name,
+            alternate_name,
+            birth_date as _
+        )
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    /// All contributors, ordered by full name.
+    pub async fn all_contributors(&self) -> Result<Vec<ContributorDetails>> {
+        Ok(query_file_as!(
+            ContributorDetails,
+            "queries/contributors.sql",
+            &[] as &[Uuid],
+            &[] as &[String],
+            true
+        )
+        .fetch_all(&self.client)
+        .await?)
+    }
+
+    pub async fn contributor_by_id(&self, id: Uuid) -> Result<Option<ContributorDetails>> {
+        Ok(query_file_as!(
+            ContributorDetails,
+            "queries/contributors.sql",
+            &[id],
+            &[] as &[String],
+            false
+        )
+        .fetch_optional(&self.client)
+        .await?)
+    }
+
+    /// Make a record for a new contributor, returning their ID.
+    pub async fn create_contributor(&self, input: CreateContributorInput) -> Result<Uuid> {
+        let birth_date: Option<Date> = input.birth_date.as_ref().map(Into::into);
+        let death_date: Option<Date> = input.death_date.as_ref().map(Into::into);
+        Ok(query_file_scalar!(
+            "queries/insert_contributor.sql",
+            input.full_name,
+            input.alternate_name,
+            birth_date as _,
+            death_date as _,
+            input.affiliation,
+            input.biography,
+            &input.roles,
+            input.viaf_id,
+            input.orcid,
+            input.wikidata_id
+        )
+        .fetch_one(&self.client)
+        .await?)
+    }
+
+    /// Change the details of a contributor.
+    pub async fn update_contributor(&self, input: UpdateContributorInput) -> Result<Uuid> {
+        let id = input.id;
+        let mut tx = self.client.begin().await?;
+        let mut person = locked_contributor(&mut tx, id).await?;
+        person.update(input);
+        save_contributor(&mut tx, &person).await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Delete a contributor along with their attributions.
+    pub async fn delete_contributor(&self, id: Uuid) -> Result<()> {
+        query_file!("queries/delete_contributor.sql", id)
             .execute(&self.client)
             .await?;
         Ok(())
     }
 
-    pub async fn potential_syllabary_matches(&self, syllabary: &str) -> Result<Vec<AnnotatedForm>> {
+    /// Combine duplicate records of one person into the record we keep. The
+    /// kept record gets any details it was missing from the duplicates, and
+    /// all of their attributions.
+    pub async fn merge_contributors(&self, input: MergeContributorsInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let mut person = locked_contributor(&mut tx, input.keep_id).await?;
+        for duplicate_id in &input.duplicate_ids {
+            if *duplicate_id != input.keep_id {
+                person.absorb(locked_contributor(&mut tx, *duplicate_id).await?);
+            }
+        }
+        query_file!(
+            "queries/merge_contributors.sql",
+            input.keep_id,
+            &input.duplicate_ids
+        )
+        .execute(&mut tx)
+        .await?;
+        save_contributor(&mut tx, &person).await?;
+        tx.commit().await?;
+        Ok(input.keep_id)
+    }
+
+    /// Documents that a contributor worked on, and their role in each one.
+    pub async fn contributor_documents(
+        &self,
+        contributor_id: Uuid,
+    ) -> Result<Vec<DocumentContribution>> {
+        Ok(
+            query_file!("queries/contributor_documents.sql", contributor_id)
+                .fetch_all(&self.client)
+                .await?
+                .into_iter()
+                .map(|row| DocumentContribution {
+                    role: row.contribution_role,
+                    document_id: row.document_id,
+                })
+                .collect(),
+        )
+    }
+
+    pub async fn potential_syllabary_matches(
+        &self,
+        syllabary: &str,
+        page: &PageRequest<WordCursor>,
+    ) -> Result<Vec<AnnotatedForm>> {
         let alternate_spellings: Vec<_> = CherokeeOrthography::similar_syllabary_strings(syllabary)
             .into_iter()
             // Convert into "LIKE"-compatible format
             .map(|x| format!("%{}%", x))
             .collect();
+        let (after_document, after_index, after_id) = WordCursor::parts(&page.after);
+        let (before_document, before_index, before_id) = WordCursor::parts(&page.before);
         let items = query_file_as!(
             BasicWord,
             "queries/search_syllabary.sql",
-            &alternate_spellings
+            &alternate_spellings,
+            after_document,
+            after_index,
+            after_id,
+            before_document,
+            before_index,
+            before_id,
+            page.from_end(),
+            page.limit()
         )
         .fetch_all(&self.client)
         .await?;
         Ok(items.into_iter().map(Into::into).collect())
     }
 
+    /// Find words containing the given gloss or any gloss related to it,
+    /// leaving out the word with the given ID if there is one.
     pub async fn connected_forms(
         &self,
         document_id: Option<DocumentId>,
         gloss: &str,
+        excluded_word: Option<Uuid>,
+        page: &PageRequest<WordCursor>,
     ) -> Result<Vec<AnnotatedForm>> {
+        let (after_document, after_index, after_id) = WordCursor::parts(&page.after);
+        let (before_document, before_index, before_id) = WordCursor::parts(&page.before);
         let items = query_file_as!(
             BasicWord,
             "queries/connected_forms.sql",
             gloss,
-            document_id.map(|id| id.0)
+            document_id.map(|id| id.0),
+            excluded_word,
+            after_document,
+            after_index,
+            after_id,
+            before_document,
+            before_index,
+            before_id,
+            page.from_end(),
+            page.limit()
         )
         .fetch_all(&self.client)
         .await?;
         Ok(items.into_iter().map(Into::into).collect())
     }
 
+    /// Find all words whose segmentation matches the given pattern, optionally
+    /// limited to a single document.
+    pub async fn morpheme_pattern_search(
+        &self,
+        pattern: &MorphemePattern,
+        document_id: Option<DocumentId>,
+    ) -> Result<Vec<MorphemePatternMatch>> {
+        let required_glosses = pattern.required_glosses();
+        if required_glosses.is_empty() && document_id.is_none() {
+            return Err(DailpError::Validation(
+                "A morpheme pattern must include at least one exact gloss \
+                 unless the search is limited to one document"
+                    .into(),
+            )
+            .into());
+        }
+        let segments = query_file!(
+            "queries/morpheme_pattern_candidates.sql",
+            &required_glosses,
+            document_id.map(|id| id.0)
+        )
+        .fetch_all(&self.client)
+        .await?;
+        let mut matches: HashMap<Uuid, (Vec<WordSegment>, usize, usize)> = segments
+            .into_iter()
+            .map(|part| {
+                (
+                    part.word_id,
+                    WordSegment {
+                        system: None,
+                        morpheme: part.morpheme,
+                        gloss: part.gloss,
+                        gloss_id: part.gloss_id,
+                        role: part.role,
+                        matching_tag: None,
+                    },
+                )
+            })
+            .into_group_map()
+            .into_iter()
+            .filter_map(|(word_id, segments)| {
+                let (start, end) = pattern.find(&segments)?;
+                Some((word_id, (segments, start, end)))
+            })
+            .collect();
+        let word_ids: Vec<_> = matches.keys().copied().collect();
+        let words = query_file_as!(BasicWord, "queries/words_by_ids.sql", &word_ids[..])
+            .fetch_all(&self.client)
+            .await?;
+        Ok(words
+            .into_iter()
+            .filter_map(|word| {
+                let (segments, start, end) = matches.remove(&word.id)?;
+                Some(MorphemePatternMatch {
+                    form: word.into(),
+                    segments,
+                    match_start: start as i64,
+                    match_end: end as i64,
+                })
+            })
+            .collect())
+    }
+
+    /// Look up dictionary entries by their English meaning, returning the best
+    /// matching roots first along with their matching attested forms.
+    pub async fn reverse_lookup(&self, english: &str) -> Result<Vec<ReverseLookupEntry>> {
+        // Limits on the number of matches to consider, to keep common words
+        // from overwhelming the results.
+        const MAX_MATCHES: i64 = 500;
+        const MAX_ENTRIES: usize = 50;
+
+        let Some(query) = english_lookup_query(english) else {
+            return Ok(Vec::new());
+        };
+        let headwords = query_file!("queries/reverse_lookup_headwords.sql", query, MAX_MATCHES)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|h| HeadwordMatch {
+                id: h.id,
+                headword: DictionaryHeadword {
+                    gloss: h.gloss,
+                    shape: h.example_shape,
+                    definition: h.english_definition,
+                    dictionary: h.document_short_name,
+                },
+                rank: h.rank,
+            })
+            .collect();
+        let forms = query_file!("queries/reverse_lookup_forms.sql", query, MAX_MATCHES)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|w| FormMatch {
+                root: w.root_id.zip(w.root_gloss).map(|(id, gloss)| {
+                    (
+                        id,
+                        DictionaryHeadword {
+                            gloss,
+                            shape: w.root_shape,
+                            definition: w.root_definition,
+                            dictionary: w.document_short_name,
+                        },
+                    )
+                }),
+                form: BasicWord {
+                    id: w.id,
+                    source_text: w.source_text,
+                    simple_phonetics: w.simple_phonetics,
+                    phonemic: w.phonemic,
+                    english_gloss: w.english_gloss,
+                    commentary: w.commentary,
+                    document_id: w.document_id,
+                    index_in_document: w.index_in_document,
+                    page_number: w.page_number,
+                    audio_url: w.audio_url,
+                    audio_slice_id: w.audio_slice_id,
+                    audio_slice: w.audio_slice,
+                    audio_recorded_at: w.audio_recorded_at,
+                    audio_recorded_by: w.audio_recorded_by,
+                    audio_recorded_by_name: w.audio_recorded_by_name,
+                    include_audio_in_edited_collection: w.include_audio_in_edited_collection,
+                    audio_edited_by: w.audio_edited_by,
+                    audio_edited_by_name: w.audio_edited_by_name,
+                }
+                .into(),
+                rank: w.rank,
+            })
+            .collect();
+        Ok(group_by_root(headwords, forms)
+            .into_iter()
+            .take(MAX_ENTRIES)
+            .map(|(headword, forms, score)| ReverseLookupEntry {
+                headword,
+                forms,
+                score,
+            })
+            .collect())
+    }
+
     pub async fn morphemes(
         &self,
         morpheme_id: MorphemeId,
@@ -223,15 +804,22 @@ impl Database {
             .collect())
     }
 
+    /// Find words containing the given gloss, grouped by document. Pages are
+    /// keyed by document ID.
     pub async fn words_by_doc(
         &self,
         document_id: Option<DocumentId>,
         gloss: &str,
+        page: &PageRequest<Uuid>,
     ) -> Result<Vec<WordsInDocument>> {
         let words = query_file!(
             "queries/morphemes_by_document.sql",
             gloss,
-            document_id.map(|id| id.0)
+            document_id.map(|id| id.0),
+            page.after,
+            page.before,
+            page.from_end(),
+            page.limit()
         )
         .fetch_all(&self.client)
         .await?;
@@ -272,10 +860,17 @@ impl Database {
             .collect())
     }
 
-    pub async fn all_documents(&self) -> Result<Vec<AnnotatedDoc>> {
-        let results = query_file!("queries/all_documents.sql")
-            .fetch_all(&self.client)
-            .await?;
+    /// List documents ordered by short name. Pages are keyed by short name.
+    pub async fn all_documents(&self, page: &PageRequest<String>) -> Result<Vec<AnnotatedDoc>> {
+        let results = query_file!(
+            "queries/all_documents.sql",
+            page.after,
+            page.before,
+            page.from_end(),
+            page.limit()
+        )
+        .fetch_all(&self.client)
+        .await?;
         Ok(results
             .into_iter()
             .map(|item| AnnotatedDoc {
@@ -291,7 +886,7 @@ impl Database {
                         .contributors
                         .and_then(|x| serde_json::from_value(x).ok())
                         .unwrap_or_default(),
-                    genre: None,
+                    genre: item.genre,
                     order_index: 0,
                     page_images: None,
                     sources: Vec::new(),
@@ -430,7 +1025,7 @@ impl Database {
                     .contributors
                     .and_then(|x| serde_json::from_value(x).ok())
                     .unwrap_or_default(),
-                genre: None,
+                genre: item.genre,
                 order_index: 0,
                 page_images: None,
                 sources: Vec::new(),
@@ -439,7 +1034,7 @@ impl Database {
             segments: None,
         };
         // Build a IIIF manifest for this document.
-        Ok(iiif::Manifest::from_document(self, doc, url).await)
+        iiif::Manifest::from_document(self, doc, url).await
     }
 
     pub async fn all_tags(&self, system: CherokeeOrthography) -> Result<Vec<MorphemeTag>> {
@@ -467,14 +1062,139 @@ impl Database {
             .collect())
     }
 
-    pub async fn search_words_any_field(&self, query: String) -> Result<Vec<AnnotatedForm>> {
+    pub async fn search_words_any_field(
+        &self,
+        query: String,
+        page: &PageRequest<WordCursor>,
+    ) -> Result<Vec<AnnotatedForm>> {
         let like_query = format!("%{}%", query);
-        let results = query_file_as!(BasicWord, "queries/search_words_any_field.sql", like_query)
-            .fetch_all(&self.client)
-            .await?;
-        Ok(results.into_iter().map(Into::into).collect())
-    }
-
+        let (after_document, after_index, after_id) = WordCursor::parts(&page.after);
+        let (before_document, before_index, before_id) = WordCursor::parts(&page.before);
+        let results = query_file_as!(
+            BasicWord,
+            "queries/search_words_any_field.sql",
+            like_query,
+            after_document,
+            after_index,
+            after_id,
+            before_document,
+            before_index,
+            before_id,
+            page.from_end(),
+            page.limit()
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(results.into_iter().map(Into::into).collect())
+    }
+
+    /// Search for words matching any of the given "LIKE" patterns in any
+    /// field, narrowed down by the given facet filter.
+    pub async fn search_words_faceted(
+        &self,
+        patterns: Vec<String>,
+        filter: SearchFilter,
+    ) -> Result<WordSearchResults> {
+        let results = query_file!(
+            "queries/search_words_faceted.sql",
+            &patterns,
+            &filter.collection_slugs(),
+            &filter.genre_names(),
+            &filter.decade_starts(),
+            &filter.contributor_names(),
+            filter.has_audio
+        )
+        .fetch_all(&self.client)
+        .await?;
+        let (forms, facets): (Vec<AnnotatedForm>, Vec<_>) = results
+            .into_iter()
+            .map(|w| {
+                (
+                    BasicWord {
+                        id: w.id,
+                        source_text: w.source_text,
+                        simple_phonetics: w.simple_phonetics,
+                        phonemic: w.phonemic,
+                        english_gloss: w.english_gloss,
+                        commentary: w.commentary,
+                        document_id: w.document_id,
+                        index_in_document: w.index_in_document,
+                        page_number: w.page_number,
+                        audio_url: w.audio_url,
+                        audio_slice_id: w.audio_slice_id,
+                        audio_slice: w.audio_slice,
+                        audio_recorded_at: w.audio_recorded_at,
+                        audio_recorded_by: w.audio_recorded_by,
+                        audio_recorded_by_name: w.audio_recorded_by_name,
+                        include_audio_in_edited_collection: w.include_audio_in_edited_collection,
+                        audio_edited_by: w.audio_edited_by,
+                        audio_edited_by_name: w.audio_edited_by_name,
+                    }
+                    .into(),
+                    FacetValues {
+                        collection_slug: w.collection_slug,
+                        collection_title: w.collection_title,
+                        genre: w.genre,
+                        date: w.written_at.map(Date::new),
+                        contributors: w.contributors,
+                        has_audio: w.has_audio,
+                    },
+                )
+            })
+            .unzip();
+        Ok(WordSearchResults {
+            facets: SearchFacets::tally(&facets),
+            forms,
+        })
+    }
+
+    /// Search for documents with a title or short name containing the given
+    /// query, narrowed down by the given facet filter.
+    pub async fn search_documents_faceted(
+        &self,
+        query: &str,
+        filter: SearchFilter,
+    ) -> Result<DocumentSearchResults> {
+        let like_query = format!("%{}%", query);
+        let results = query_file!(
+            "queries/search_documents_faceted.sql",
+            like_query,
+            &filter.collection_slugs(),
+            &filter.genre_names(),
+            &filter.decade_starts(),
+            &filter.contributor_names(),
+            filter.has_audio
+        )
+        .fetch_all(&self.client)
+        .await?;
+        let (documents, facets): (Vec<_>, Vec<_>) = results
+            .into_iter()
+            .map(|doc| {
+                (
+                    DocumentReference {
+                        id: doc.id,
+                        short_name: doc.short_name,
+                        title: doc.title,
+                        date: doc.date.clone(),
+                        order_index: doc.order_index,
+                    },
+                    FacetValues {
+                        collection_slug: doc.collection_slug,
+                        collection_title: doc.collection_title,
+                        genre: doc.genre,
+                        date: doc.date,
+                        contributors: doc.contributors,
+                        has_audio: doc.has_audio,
+                    },
+                )
+            })
+            .unzip();
+        Ok(DocumentSearchResults {
+            facets: SearchFacets::tally(&facets),
+            documents,
+        })
+    }
+
     pub async fn top_collections(&self) -> Result<Vec<DocumentCollection>> {
         Ok(
             query_file_as!(DocumentCollection, "queries/document_groups.sql")
@@ -491,112 +1211,990 @@ impl Database {
         )
     }
 
+    /// Make a new edited collection with no chapters.
+    pub async fn create_edited_collection(
+        &self,
+        input: CreateEditedCollectionInput,
+    ) -> Result<EditedCollection> {
+        let slug = chapter_slug(input.slug.as_deref().unwrap_or(&input.title))?;
+        Ok(query_file_as!(
+            EditedCollection,
+            "queries/create_edited_collection.sql",
+            input.title,
+            slug,
+            input.wordpress_menu_id
+        )
+        .fetch_one(&self.client)
+        .await?)
+    }
+
+    pub async fn chapter_by_id(&self, id: Uuid) -> Result<Option<CollectionChapter>> {
+        let chapter = query_file_as!(ChapterRow, "queries/chapter_by_id.sql", id)
+            .fetch_optional(&self.client)
+            .await?;
+        Ok(chapter.map(CollectionChapter::from))
+    }
+
+    /// Add a chapter to a collection, returning its ID.
+    pub async fn add_chapter(&self, input: AddChapterInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let collection_slug = slugify_ltree(&input.collection_slug);
+        lock_edited_collection(&mut tx, &collection_slug).await?;
+        let parent_path = chapter_parent_path(&mut tx, &collection_slug, input.parent_id).await?;
+
+        let mut path = parent_path.clone();
+        path.push(PgLTreeLabel::new(chapter_slug(
+            input.slug.as_deref().unwrap_or(&input.title),
+        )?)?);
+        let id = query_file_scalar!(
+            "queries/add_chapter.sql",
+            input.title,
+            input.document_id,
+            input.wordpress_id,
+            path,
+            input.section as _
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        place_chapter_in(&mut tx, parent_path, id, input.index).await?;
+        replace_chapter_blocks(&mut tx, id, input.body.into_iter().map(Into::into)).await?;
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Change the details of a chapter. A new slug also moves its subchapters.
+    pub async fn update_chapter(&self, input: UpdateChapterInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let chapter = locked_chapter(&mut tx, input.id).await?;
+        query_file!(
+            "queries/update_chapter.sql",
+            input.id,
+            input.title,
+            input.section as _,
+            !input.document_id.is_undefined(),
+            input.document_id.value(),
+            !input.wordpress_id.is_undefined(),
+            input.wordpress_id.value()
+        )
+        .execute(&mut tx)
+        .await?;
+
+        if let Some(slug) = &input.slug {
+            let mut path = chapter.chapter_path.clone();
+            path.pop();
+            path.push(PgLTreeLabel::new(chapter_slug(slug)?)?);
+            query_file!(
+                "queries/move_chapter_subtree.sql",
+                chapter.chapter_path,
+                path
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+        if let Some(body) = input.body {
+            replace_chapter_blocks(&mut tx, input.id, body.into_iter().map(Into::into)).await?;
+        }
+        tx.commit().await?;
+        Ok(input.id)
+    }
+
+    /// Move a chapter and its subchapters under a new parent in the same
+    /// collection, or to a new position under the same parent.
+    pub async fn move_chapter(&self, input: MoveChapterInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let chapter = locked_chapter(&mut tx, input.id).await?;
+        let parent_path =
+            chapter_parent_path(&mut tx, &chapter.collection_slug, input.parent_id).await?;
+        if parent_path.starts_with(&chapter.chapter_path) {
+            return Err(DailpError::Validation(
+                "A chapter can't be moved into itself or its own subchapters".into(),
+            )
+            .into());
+        }
+
+        let mut path = parent_path.clone();
+        path.push(chapter.chapter_path.last().unwrap().clone());
+        query_file!(
+            "queries/move_chapter_subtree.sql",
+            chapter.chapter_path,
+            path
+        )
+        .execute(&mut tx)
+        .await?;
+        place_chapter_in(&mut tx, parent_path, input.id, input.index).await?;
+        tx.commit().await?;
+        Ok(input.id)
+    }
+
+    /// Content blocks of a chapter, in order.
+    pub async fn chapter_body(&self, chapter_id: Uuid) -> Result<Vec<page::ContentBlock>> {
+        query_file_scalar!("queries/chapter_body.sql", chapter_id)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|block| Ok(serde_json::from_value(block)?))
+            .collect()
+    }
+
+    /// Replace the content of a chapter with the given blocks.
+    pub async fn save_chapter_body(
+        &self,
+        chapter_id: Uuid,
+        body: Vec<page::ContentBlock>,
+    ) -> Result<()> {
+        let mut tx = self.client.begin().await?;
+        locked_chapter(&mut tx, chapter_id).await?;
+        replace_chapter_blocks(&mut tx, chapter_id, body).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Delete a chapter along with all of its subchapters.
+    pub async fn delete_chapter(&self, id: Uuid) -> Result<()> {
+        let mut tx = self.client.begin().await?;
+        locked_chapter(&mut tx, id).await?;
+        query_file!("queries/delete_chapter.sql", id)
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
     /// Ensure that a user exists in the database
     /// user_id should be a congnito sub claim
-    pub async fn upsert_dailp_user(&self, user_id: Uuid) -> Result<Uuid> {
-        query_file!("queries/upsert_dailp_user.sql", user_id,)
+    /// Make sure a signed-in user has a row, and record the groups they're in.
+    pub async fn upsert_dailp_user(&self, user: &UserInfo) -> Result<Uuid> {
+        query_file!("queries/upsert_dailp_user.sql", user.id, &user.groups as _)
             .execute(&self.client)
             .await?;
 
-        Ok(user_id)
+        Ok(user.id)
     }
 
-    pub async fn update_annotation(&self, _annote: annotation::Annotation) -> Result<()> {
-        todo!("Implement image annotations")
+    /// Change the profile and preferences of a user.
+    pub async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        profile: UpdateProfileInput,
+    ) -> Result<()> {
+        profile.validate()?;
+        query_file!(
+            "queries/update_user_profile.sql",
+            user_id,
+            profile.display_name.as_deref().map(str::trim),
+            !profile.avatar_url.is_undefined(),
+            profile.avatar_url.value(),
+            !profile.preferred_orthography.is_undefined(),
+            profile.preferred_orthography.value() as _,
+            profile.interface_language as _,
+            profile.notify_on_comment_replies,
+            profile.notify_on_edit_reviews
+        )
+        .execute(&self.client)
+        .await?;
+        Ok(())
     }
 
-    pub async fn update_word(&self, word: AnnotatedFormUpdate) -> Result<Uuid> {
-        let mut tx = self.client.begin().await?;
+    /// Make an API key for a user, returning the key itself along with its
+    /// details.
+    pub async fn create_api_key(
+        &self,
+        user: &UserInfo,
+        input: CreateApiKeyInput,
+    ) -> Result<NewApiKey> {
+        input.validate(user)?;
+        let created = query_file!(
+            "queries/insert_api_key.sql",
+            user.id,
+            user.email,
+            input.name.trim(),
+            input.read_only,
+            &input.groups as _,
+            input.expires_in_days
+        )
+        .fetch_one(&self.client)
+        .await?;
+        let api_key = self
+            .api_keys(user.id)
+            .await?
+            .into_iter()
+            .find(|key| key.id == created.id)
+            .ok_or_else(|| DailpError::NotFound("Failed to load API key".into()))?;
+        Ok(NewApiKey {
+            key: created.secret,
+            api_key,
+        })
+    }
 
-        let source = word.source.into_vec();
-        let commentary = word.commentary.into_vec();
+    /// API keys that a user has made, newest first.
+    pub async fn api_keys(&self, user_id: Uuid) -> Result<Vec<ApiKey>> {
+        let keys = query_file!("queries/api_keys.sql", user_id)
+            .fetch_all(&self.client)
+            .await?;
+        Ok(keys
+            .into_iter()
+            .map(|key| ApiKey {
+                id: key.id,
+                user_id: key.user_id,
+                name: key.name,
+                prefix: key.prefix,
+                read_only: key.read_only,
+                groups: key.groups,
+                created_at: DateTime::new(key.created_at),
+                expires_at: key.expires_at.map(DateTime::new),
+                last_used_at: key.last_used_at.map(DateTime::new),
+                revoked_at: key.revoked_at.map(DateTime::new),
+            })
+            .collect())
+    }
 
-        let document_id = query_file!(
-            "queries/update_word.sql",
-            word.id,
-            &source as _,
-            &commentary as _,
+    /// Stop one of a user's API keys from working.
+    pub async fn revoke_api_key(&self, key_id: Uuid, user_id: Uuid) -> Result<Uuid> {
+        query_file_scalar!("queries/revoke_api_key.sql", key_id, user_id)
+            .fetch_optional(&self.client)
+            .await?
+            .ok_or_else(|| {
+                DailpError::NotFound(format!("No active API key {} found", key_id)).into()
+            })
+    }
+
+    /// Most recent requests made with an API key.
+    pub async fn api_key_usage(&self, key_id: Uuid, limit: i64) -> Result<Vec<ApiKeyUse>> {
+        let uses = query_file!("queries/api_key_usage.sql", key_id, limit)
+            .fetch_all(&self.client)
+            .await?;
+        Ok(uses
+            .into_iter()
+            .map(|usage| ApiKeyUse {
+                used_at: DateTime::new(usage.used_at),
+                operation_name: usage.operation_name,
+            })
+            .collect())
+    }
+
+    /// Find the user that an API key acts as, logging the request against the
+    /// key. Keys that are unknown, revoked or expired don't act as anyone.
+    pub async fn use_api_key(
+        &self,
+        key: &str,
+        operation_name: Option<&str>,
+    ) -> Result<Option<(UserInfo, ApiKeyAccess)>> {
+        let key = query_file!("queries/use_api_key.sql", key.trim(), operation_name)
+            .fetch_optional(&self.client)
+            .await?;
+        Ok(key.map(|key| {
+            (
+                UserInfo::new(key.user_id, key.owner_email, key.groups.clone()),
+                ApiKeyAccess {
+                    key_id: key.id,
+                    read_only: key.read_only,
+                    groups: key.groups,
+                },
+            )
+        }))
+    }
+
+    /// People invited to user groups, or just the one with the given email.
+    pub async fn role_invitations(&self, email: Option<&str>) -> Result<Vec<RoleInvitation>> {
+        let invitations = query_file!("queries/role_invitations.sql", email)
+            .fetch_all(&self.client)
+            .await?;
+        Ok(invitations
+            .into_iter()
+            .map(|invitation| RoleInvitation {
+                email: invitation.email,
+                role: invitation.role,
+                invited_by: invitation.invited_by,
+                invited_at: DateTime::new(invitation.invited_at),
+                accepted_at: invitation.accepted_at.map(DateTime::new),
+            })
+            .collect())
+    }
+
+    /// Invite someone to a user group, returning their email as stored.
+    pub async fn invite_user(&self, input: InviteUserInput, invited_by: &Uuid) -> Result<String> {
+        input.validate()?;
+        Ok(query_file_scalar!(
+            "queries/upsert_role_invitation.sql",
+            input.email.trim(),
+            input.role as _,
+            invited_by
         )
-        .fetch_one(&mut tx)
-        .await?
-        .document_id;
+        .fetch_one(&self.client)
+        .await?)
+    }
+
+    /// Take back an invitation. People who already accepted it stay in the
+    /// group they were put in.
+    pub async fn remove_invitation(&self, email: &str) -> Result<String> {
+        query_file_scalar!("queries/delete_role_invitation.sql", email.trim())
+            .fetch_optional(&self.client)
+            .await?
+            .ok_or_else(|| DailpError::NotFound(format!("{} hasn't been invited", email)).into())
+    }
+
+    /// Record that the invited person has been put in their group.
+    pub async fn accept_invitation(&self, email: &str) -> Result<()> {
+        query_file!("queries/accept_role_invitation.sql", email)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Roles given on single collections or documents, either to one user or
+    /// to everyone.
+    pub async fn permission_grants(&self, user_id: Option<Uuid>) -> Result<Vec<PermissionGrant>> {
+        let grants = query_file!("queries/permission_grants.sql", user_id)
+            .fetch_all(&self.client)
+            .await?;
+        Ok(grants
+            .into_iter()
+            .map(|grant| PermissionGrant {
+                id: grant.id,
+                user_id: grant.user_id,
+                user_name: grant.user_name,
+                role: grant.role,
+                scope: grant.scope,
+                target_id: grant.target_id,
+                target_title: grant.target_title,
+                granted_by: grant.granted_by,
+                granted_at: DateTime::new(grant.granted_at),
+            })
+            .collect())
+    }
 
-        // If word segmentation was not changed, then return early since SQL update queries need to be called.
-        if !word.segments.is_value() {
-            tx.commit().await?;
-            return Ok(word.id);
+    /// Give a user a role on one collection or document, returning the ID of
+    /// the grant.
+    pub async fn grant_permission(
+        &self,
+        input: GrantPermissionInput,
+        granted_by: &Uuid,
+    ) -> Result<Uuid> {
+        let target_exists = query_file_scalar!(
+            "queries/permission_target_exists.sql",
+            input.scope as _,
+            input.target_id
+        )
+        .fetch_one(&self.client)
+        .await?;
+        if !target_exists {
+            return Err(DailpError::NotFound(format!(
+                "{:?} {} doesn't exist",
+                input.scope, input.target_id
+            ))
+            .into());
         }
+        Ok(query_file_scalar!(
+            "queries/upsert_permission_grant.sql",
+            input.user_id,
+            input.role as _,
+            input.scope as _,
+            input.target_id,
+            granted_by
+        )
+        .fetch_one(&self.client)
+        .await?)
+    }
 
-        let segments = word.segments.take().unwrap();
-        // If word segmentation not present, return early.
-        if segments.is_empty() {
-            tx.commit().await?;
-            return Ok(word.id);
+    /// Take away a role given with [`Database::grant_permission`].
+    pub async fn revoke_permission(&self, grant_id: Uuid) -> Result<Uuid> {
+        query_file_scalar!("queries/delete_permission_grant.sql", grant_id)
+            .fetch_optional(&self.client)
+            .await?
+            .ok_or_else(|| {
+                DailpError::NotFound(format!("Permission grant {} doesn't exist", grant_id)).into()
+            })
+    }
+
+    /// Whether the user has been given the role, or an editor role, on the
+    /// target or on anything that contains it. Targets that don't exist have
+    /// no grants.
+    pub async fn has_permission(
+        &self,
+        user_id: Uuid,
+        roles: &[UserGroup],
+        target: &PermissionTarget,
+    ) -> Result<bool> {
+        let document_id = match target {
+            PermissionTarget::EditedCollection(slug) => {
+                return self
+                    .edited_collection_permission(user_id, roles, slug)
+                    .await
+            }
+            PermissionTarget::Chapter(chapter_id) => {
+                let chapter = query_file_as!(ChapterRow, "queries/chapter_by_id.sql", chapter_id)
+                    .fetch_optional(&self.client)
+                    .await?;
+                return match chapter {
+                    Some(chapter) => {
+                        self.edited_collection_permission(user_id, roles, &chapter.collection_slug)
+                            .await
+                    }
+                    None => Ok(false),
+                };
+            }
+            PermissionTarget::DocumentCollection(group_id) => {
+                return Ok(query_file_scalar!(
+                    "queries/document_collection_permission.sql",
+                    user_id,
+                    group_id,
+                    roles as _
+                )
+                .fetch_one(&self.client)
+                .await?)
+            }
+            PermissionTarget::Document(document_id) => Some(*document_id),
+            PermissionTarget::Page(page_id) => {
+                query_file_scalar!("queries/page_document_id.sql", page_id)
+                    .fetch_optional(&self.client)
+                    .await?
+            }
+            PermissionTarget::Paragraph(paragraph_id) => {
+                query_file_scalar!("queries/paragraph_document_id.sql", paragraph_id)
+                    .fetch_optional(&self.client)
+                    .await?
+            }
+            PermissionTarget::Word(word_id) => {
+                query_file_scalar!("queries/word_document_id.sql", word_id)
+                    .fetch_optional(&self.client)
+                    .await?
+            }
+            PermissionTarget::Annotation(annotation_id) => {
+                query_file_scalar!("queries/annotation_document_id.sql", annotation_id)
+                    .fetch_optional(&self.client)
+                    .await?
+            }
+            PermissionTarget::Revision(revision_id) => {
+                query_file_scalar!("queries/revision_document_id.sql", revision_id)
+                    .fetch_optional(&self.client)
+                    .await?
+                    .flatten()
+            }
+        };
+        match document_id {
+            Some(document_id) => Ok(query_file_scalar!(
+                "queries/document_permission.sql",
+                user_id,
+                document_id,
+                roles as _
+            )
+            .fetch_one(&self.client)
+            .await?),
+            None => Ok(false),
         }
+    }
 
-        let system_name: Option<CherokeeOrthography> = *(&segments[0].system.clone());
+    async fn edited_collection_permission(
+        &self,
+        user_id: Uuid,
+        roles: &[UserGroup],
+        slug: &str,
+    ) -> Result<bool> {
+        Ok(query_file_scalar!(
+            "queries/edited_collection_permission.sql",
+            user_id,
+            slug,
+            roles as _
+        )
+        .fetch_one(&self.client)
+        .await?)
+    }
 
-        let (doc_id, gloss, word_id, index, morpheme, role): (
-            Vec<_>,
-            Vec<_>,
-            Vec<_>,
-            Vec<_>,
-            Vec<_>,
-            Vec<_>,
-        ) = segments
+    /// Annotations on a document, page, or word, or replies to one, oldest
+    /// first.
+    pub async fn annotations(
+        &self,
+        on: annotation::AnnotationsOn,
+    ) -> Result<Vec<annotation::Annotation>> {
+        use annotation::{AnnotationAttachment, DocumentRegion, Reply, WordAttachment};
+        let (kind, id) = on.as_query();
+        Ok(query_file!("queries/annotations.sql", kind, id)
+            .fetch_all(&self.client)
+            .await?
             .into_iter()
-            .enumerate()
-            .map(move |(index, segment)| {
-                (
-                    document_id,
-                    segment.gloss,
-                    word.id,
-                    index as i64, // index of the segment in the word
-                    segment.morpheme,
-                    segment.role,
+            .map(|row| {
+                let attached_to = match (row.reply_to, row.word_id) {
+                    (Some(to), _) => AnnotationAttachment::Reply(Reply { to }),
+                    (None, Some(to)) => AnnotationAttachment::WordAttachment(WordAttachment { to }),
+                    (None, None) => AnnotationAttachment::DocumentRegion(DocumentRegion {
+                        document_id: row.document_id,
+                        page: row.page_number,
+                        region: match (
+                            row.region_x_min,
+                            row.region_y_min,
+                            row.region_x_max,
+                            row.region_y_max,
+                        ) {
+                            (Some(x_min), Some(y_min), Some(x_max), Some(y_max)) => {
+                                Some(Geometry::new(x_min, y_min, x_max, y_max))
+                            }
+                            _ => None,
+                        },
+                    }),
+                };
+                annotation::Annotation {
+                    id: row.id,
+                    content: row.content,
+                    attached_to,
+                    created_by: row.created_by.zip(row.created_by_name).map(
+                        |(id, display_name)| User {
+                            id: id.into(),
+                            display_name,
+                        },
+                    ),
+                    created_at: DateTime::new(row.created_at),
+                    updated_at: row.updated_at.map(DateTime::new),
+                    document_id: row.document_id,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn annotation_by_id(&self, annotation_id: &Uuid) -> Result<annotation::Annotation> {
+        self.annotations(annotation::AnnotationsOn::Id(*annotation_id))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| DailpError::NotFound("No annotation with that ID".into()).into())
+    }
+
+    /// Save a new annotation, returning its ID.
+    pub async fn create_annotation(
+        &self,
+        input: annotation::CreateAnnotationInput,
+        created_by: &Uuid,
+    ) -> Result<Uuid> {
+        input.validate()?;
+        let region = input.document_region.as_ref();
+        let document_id = region.map(|r| r.document_id);
+        let page = region.and_then(|r| r.page);
+        let bounds = region.and_then(|r| r.region.as_ref()).map(Geometry::bounds);
+
+        let anchor = query_file!(
+            "queries/annotation_anchor.sql",
+            input.word_id,
+            input.reply_to,
+            document_id,
+            page
+        )
+        .fetch_one(&self.client)
+        .await?;
+        let document_id = anchor
+            .document_id
+            .ok_or_else(|| DailpError::Validation("Nothing to attach the annotation to".into()))?;
+        if page.is_some() && anchor.page_id.is_none() {
+            return Err(DailpError::NotFound(format!(
+                "The document has no page {}",
+                page.unwrap_or_default()
+            ))
+            .into());
+        }
+
+        Ok(query_file_scalar!(
+            "queries/insert_annotation.sql",
+            input.content,
+            created_by,
+            document_id,
+            input.word_id,
+            input.reply_to,
+            anchor.page_id,
+            bounds.map(|b| b.0),
+            bounds.map(|b| b.1),
+            bounds.map(|b| b.2),
+            bounds.map(|b| b.3)
+        )
+        .fetch_one(&self.client)
+        .await?)
+    }
+
+    /// Change the content or region of an annotation.
+    pub async fn update_annotation(&self, input: annotation::UpdateAnnotationInput) -> Result<()> {
+        if !input.region.is_undefined() {
+            let current = self.annotation_by_id(&input.id).await?;
+            if !matches!(
+                current.attached_to,
+                annotation::AnnotationAttachment::DocumentRegion(annotation::DocumentRegion {
+                    page: Some(_),
+                    ..
+                })
+            ) {
+                return Err(DailpError::Validation(
+                    "Only annotations on a page can have a region".into(),
+                )
+                .into());
+            }
+        }
+        let bounds = input.region.value().map(Geometry::bounds);
+        query_file!(
+            "queries/update_annotation.sql",
+            input.id,
+            input.content,
+            !input.region.is_undefined(),
+            bounds.map(|b| b.0),
+            bounds.map(|b| b.1),
+            bounds.map(|b| b.2),
+            bounds.map(|b| b.3)
+        )
+        .fetch_optional(&self.client)
+        .await?
+        .ok_or_else(|| DailpError::NotFound("No annotation with that ID".into()))?;
+        Ok(())
+    }
+
+    /// Delete an annotation along with all replies to it.
+    pub async fn delete_annotation(&self, annotation_id: &Uuid) -> Result<()> {
+        query_file!("queries/delete_annotation.sql", annotation_id)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn update_word(&self, word: AnnotatedFormUpdate, edited_by: &Uuid) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let word_id = word.id;
+        update_word_in(&mut tx, word, edited_by).await?;
+        tx.commit().await?;
+        Ok(word_id)
+    }
+
+    /// Apply many word updates in one transaction. Every update is checked
+    /// before any are saved, and if any of them can't be saved then none are.
+    pub async fn update_words(
+        &self,
+        words: Vec<AnnotatedFormUpdate>,
+        edited_by: &Uuid,
+    ) -> Result<Vec<WordUpdateResult>> {
+        if words.len() > MAX_BATCH_SIZE {
+            return Err(DailpError::Validation(format!(
+                "At most {} words can be updated at once",
+                MAX_BATCH_SIZE
+            ))
+            .into());
+        }
+        let mut tx = self.client.begin().await?;
+
+        let ids: Vec<_> = words.iter().map(|word| word.id).collect();
+        let versions: HashMap<_, _> = query_file!("queries/lock_word_versions.sql", &ids[..])
+            .fetch_all(&mut tx)
+            .await?
+            .into_iter()
+            .map(|word| (word.id, word.version))
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        let mut results: Vec<_> = words
+            .iter()
+            .map(|word| {
+                WordUpdateResult::check(
+                    word,
+                    versions.get(&word.id).copied(),
+                    !seen.insert(word.id),
                 )
             })
-            .multiunzip();
+            .collect();
+        // Dropping the transaction rolls it back, releasing the locked words.
+        if results.iter().any(|result| result.error.is_some()) {
+            return Ok(results);
+        }
 
-        // Convert the given glosses if they have an internal for to add to the database.
-        let internal_glosses = query_file_scalar!(
-            "queries/find_internal_glosses.sql",
-            &*gloss,
-            match system_name {
-                Some(CherokeeOrthography::Taoc) => "TAOC",
-                _ =>
-                    return Err(anyhow::anyhow!(
-                        "Other Cherokee systems are currently not supported"
-                    )),
+        for (word, result) in words.into_iter().zip(&mut results) {
+            result.document_id = Some(update_word_in(&mut tx, word, edited_by).await?);
+            result.saved = true;
+        }
+        tx.commit().await?;
+
+        Ok(results)
+    }
+
+    /// Replace text in one field of every matching word in a document or
+    /// collection, all in one transaction. A dry run only lists the words
+    /// that would change.
+    pub async fn find_and_replace(
+        &self,
+        input: &FindAndReplaceInput,
+        edited_by: &Uuid,
+    ) -> Result<Vec<Replacement>> {
+        if input.document_id.is_none() && input.collection.is_none() {
+            return Err(DailpError::Validation(
+                "Find and replace needs a document or collection to search".into(),
+            )
+            .into());
+        }
+        if input.find.is_empty() {
+            return Err(DailpError::Validation("Nothing to find".into()).into());
+        }
+        let mut tx = self.client.begin().await?;
+
+        let candidates = query_file!(
+            "queries/find_and_replace_candidates.sql",
+            input.document_id,
+            input.collection,
+            input.field.as_str(),
+            input.find,
+            MAX_BATCH_SIZE as i64 + 1
+        )
+        .fetch_all(&mut tx)
+        .await?;
+        if candidates.len() > MAX_BATCH_SIZE {
+            return Err(DailpError::Validation(format!(
+                "More than {} words match, try a smaller document or collection",
+                MAX_BATCH_SIZE
+            ))
+            .into());
+        }
+
+        let ids: Vec<_> = candidates.iter().map(|word| word.id).collect();
+        let mut parts = if input.field == ReplaceField::Gloss {
+            query_file!("queries/word_parts.sql", &ids[..])
+                .fetch_all(&mut tx)
+                .await?
+                .into_iter()
+                .map(|part| (part.word_id, part))
+                .into_group_map()
+        } else {
+            HashMap::new()
+        };
+
+        let mut replacements = Vec::new();
+        for word in candidates {
+            let (before, after) = match input.field {
+                ReplaceField::Source => (
+                    word.source_text.clone(),
+                    word.source_text.replace(&input.find, &input.replace),
+                ),
+                ReplaceField::Commentary => {
+                    let commentary = word.commentary.unwrap_or_default();
+                    let replaced = commentary.replace(&input.find, &input.replace);
+                    (commentary, replaced)
+                }
+                ReplaceField::Gloss => {
+                    let glosses: Vec<_> = parts
+                        .get(&word.id)
+                        .into_iter()
+                        .flatten()
+                        .map(|part| part.gloss.clone())
+                        .collect();
+                    match replace_gloss(&glosses, &input.find, &input.replace) {
+                        Some(replaced) => (glosses.join("-"), replaced.join("-")),
+                        None => continue,
+                    }
+                }
+            };
+            replacements.push(Replacement {
+                word_id: word.id,
+                document_id: word.document_id,
+                before,
+                after,
+            });
+        }
+        if input.dry_run {
+            return Ok(replacements);
+        }
+
+        for replacement in &replacements {
+            let word_id = replacement.word_id;
+            let before = query_file_scalar!("queries/word_snapshot.sql", word_id)
+                .fetch_one(&mut tx)
+                .await?;
+
+            let (source, commentary) = match input.field {
+                ReplaceField::Source => (vec![Some(replacement.after.clone())], Vec::new()),
+                ReplaceField::Commentary => (Vec::new(), vec![Some(replacement.after.clone())]),
+                ReplaceField::Gloss => (Vec::new(), Vec::new()),
+            };
+            query_file!(
+                "queries/update_word.sql",
+                word_id,
+                &source as _,
+                &commentary as _,
+                None::<i64>
+            )
+            .fetch_one(&mut tx)
+            .await?;
+
+            if input.field == ReplaceField::Gloss {
+                // Only the matching segments change, so only those are written.
+                let (doc_id, gloss, word_ids, index, morpheme, role): (
+                    Vec<_>,
+                    Vec<_>,
+                    Vec<_>,
+                    Vec<_>,
+                    Vec<_>,
+                    Vec<_>,
+                ) = parts
+                    .remove(&word_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|part| part.gloss == input.find)
+                    .map(|part| {
+                        (
+                            replacement.document_id,
+                            input.replace.clone(),
+                            word_id,
+                            part.index_in_word,
+                            part.morpheme,
+                            part.role,
+                        )
+                    })
+                    .multiunzip();
+                query_file!(
+                    "queries/upsert_local_morpheme_glosses.sql",
+                    &*doc_id,
+                    &*gloss
+                )
+                .execute(&mut tx)
+                .await?;
+                query_file!(
+                    "queries/upsert_many_word_segments.sql",
+                    &*doc_id,
+                    &*gloss,
+                    &*word_ids,
+                    &*index,
+                    &*morpheme,
+                    &*role as _
+                )
+                .execute(&mut tx)
+                .await?;
             }
+
+            let after = query_file_scalar!("queries/word_snapshot.sql", word_id)
+                .fetch_one(&mut tx)
+                .await?;
+            record_revision(
+                &mut tx,
+                EditTargetType::Word,
+                word_id,
+                edited_by,
+                before,
+                after,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(replacements)
+    }
+
+    /// Put a word back the way it was before the given revision, recording the
+    /// revert as a new revision.
+    pub async fn revert_word(&self, revision_id: &Uuid, edited_by: &Uuid) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+
+        let revision = query_file!(
+            "queries/edit_revision_by_id.sql",
+            revision_id,
+            EditTargetType::Word as _
         )
-        .fetch_all(&mut tx)
-        .await?;
+        .fetch_optional(&mut tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound("No word revision with that ID".into()))?;
+        let word_id = revision.target_id;
+        let restored: WordSnapshot = serde_json::from_value(revision.before)?;
+
+        let before = query_file_scalar!("queries/word_snapshot.sql", word_id)
+            .fetch_one(&mut tx)
+            .await?;
 
-        // Add any newly created local glosses into morpheme gloss table.
         query_file!(
-            "queries/upsert_local_morpheme_glosses.sql",
-            &*doc_id,
-            &*internal_glosses as _,
+            "queries/update_word.sql",
+            word_id,
+            &vec![Some(restored.source)] as _,
+            &vec![restored.commentary] as _,
+            None::<i64>
         )
-        .execute(&mut tx)
+        .fetch_one(&mut tx)
         .await?;
 
+        let segment_count = restored.segments.len() as i64;
+        let (morpheme, gloss_id, role): (Vec<_>, Vec<_>, Vec<_>) = restored
+            .segments
+            .into_iter()
+            .map(|segment| (segment.morpheme, segment.gloss_id, segment.role))
+            .multiunzip();
         query_file!(
-            "queries/upsert_many_word_segments.sql",
-            &*doc_id,
-            &*internal_glosses as _,
-            &*word_id,
-            &*index,
+            "queries/restore_word_segments.sql",
+            word_id,
             &*morpheme,
+            &*gloss_id as _,
             &*role as _
         )
         .execute(&mut tx)
         .await?;
+        query_file!(
+            "queries/delete_word_segments_from.sql",
+            word_id,
+            segment_count
+        )
+        .execute(&mut tx)
+        .await?;
+
+        let after = query_file_scalar!("queries/word_snapshot.sql", word_id)
+            .fetch_one(&mut tx)
+            .await?;
+        record_revision(
+            &mut tx,
+            EditTargetType::Word,
+            word_id,
+            edited_by,
+            before,
+            after,
+        )
+        .await?;
 
         tx.commit().await?;
 
-        Ok(word.id)
+        Ok(word_id)
+    }
+
+    /// Past edits to a word or paragraph, newest first.
+    pub async fn edit_history<S>(
+        &self,
+        target_type: EditTargetType,
+        target_id: &Uuid,
+    ) -> Result<Vec<EditRevision<S>>>
+    where
+        S: async_graphql::OutputType + serde::de::DeserializeOwned,
+    {
+        query_file!("queries/edit_history.sql", target_type as _, target_id)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|revision| {
+                Ok(EditRevision {
+                    id: revision.id,
+                    edited_at: DateTime::new(revision.edited_at),
+                    edited_by: revision.edited_by.zip(revision.edited_by_name).map(
+                        |(id, display_name)| User {
+                            id: id.into(),
+                            display_name,
+                        },
+                    ),
+                    before: serde_json::from_value(revision.before)?,
+                    after: serde_json::from_value(revision.after)?,
+                })
+            })
+            .collect()
+    }
+
+    /// The editable fields of a word or paragraph as they are now, shaped like
+    /// the snapshots in its edit history.
+    pub async fn snapshot<S>(&self, target_type: EditTargetType, target_id: &Uuid) -> Result<S>
+    where
+        S: serde::de::DeserializeOwned,
+    {
+        let snapshot = match target_type {
+            EditTargetType::Word => {
+                query_file_scalar!("queries/word_snapshot.sql", target_id)
+                    .fetch_one(&self.client)
+                    .await?
+            }
+            EditTargetType::Paragraph => {
+                query_file_scalar!("queries/paragraph_snapshot.sql", target_id)
+                    .fetch_one(&self.client)
+                    .await?
+            }
+        };
+        Ok(serde_json::from_value(snapshot)?)
     }
 
     // pub async fn maybe_undefined_to_vec() -> Vec<Option<String>> {}
@@ -691,30 +2289,358 @@ impl Database {
         let title = document.title.into_vec();
         let written_at: Option<Date> = document.written_at.value().map(Into::into);
 
-        query_file!(
+        let updated = query_file!(
             "queries/update_document_metadata.sql",
             document.id,
             &title as _,
-            &written_at as _
+            &written_at as _,
+            document.version
         )
-        .execute(&self.client)
+        .fetch_optional(&self.client)
         .await?;
+        if updated.is_none() {
+            let current = query_file!("queries/document_metadata_snapshot.sql", document.id)
+                .fetch_one(&self.client)
+                .await?;
+            return Err(EditConflict {
+                current_version: current.version,
+                current: current.snapshot,
+            }
+            .into());
+        }
 
         Ok(document.id)
     }
 
-    pub async fn update_paragraph(&self, paragraph: ParagraphUpdate) -> Result<DocumentParagraph> {
-        let translation = paragraph.translation.into_vec();
+    /// Make a new document with no pages at the end of its group.
+    pub async fn create_document(&self, input: CreateDocumentInput) -> Result<Uuid> {
+        let written_at: Option<Date> = input.written_at.as_ref().map(Into::into);
+        query_file_scalar!(
+            "queries/create_document.sql",
+            input.short_name,
+            input.title,
+            input.group_id,
+            input.is_reference,
+            &written_at as _,
+            input.genre
+        )
+        .fetch_optional(&self.client)
+        .await?
+        .ok_or_else(|| {
+            DailpError::NotFound(format!("Document group {} doesn't exist", input.group_id)).into()
+        })
+    }
+
+    /// Add an empty page to the end of a document, returning the document ID.
+    pub async fn add_document_page(&self, input: AddDocumentPageInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        lock_document_layout(&mut tx, input.document_id).await?;
+        query_file_scalar!(
+            "queries/add_document_page.sql",
+            input.document_id,
+            input.iiif_source_id,
+            input.iiif_oid
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        tx.commit().await?;
+        Ok(input.document_id)
+    }
+
+    /// Add a word to a paragraph, returning the document ID.
+    pub async fn insert_word_in_paragraph(&self, input: InsertWordInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = paragraph_document_id(&mut tx, input.paragraph_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        let word = insert_new_word(&mut tx, document_id, &input.word).await?;
+        layout.insert_word(input.paragraph_id, input.after_word_id, word)?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Delete a word along with comments on it, returning the document ID.
+    pub async fn delete_word(&self, word_id: Uuid) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = word_document_id(&mut tx, word_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        layout.remove_word(word_id)?;
+        query_file!("queries/delete_word.sql", word_id)
+            .execute(&mut tx)
+            .await?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Break a word into two, returning the document ID.
+    pub async fn split_word(&self, input: SplitWordInput, edited_by: &Uuid) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = word_document_id(&mut tx, input.word_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        let paragraph_id = layout.paragraph_of_word(input.word_id)?;
+
+        let current: WordSnapshot = serde_json::from_value(
+            query_file_scalar!("queries/word_snapshot.sql", input.word_id)
+                .fetch_one(&mut tx)
+                .await?,
+        )?;
+        let (first, second) = split_source(&current.source, input.at)?;
+        layout.resize_word(input.word_id, input.at)?;
+        update_word_in(
+            &mut tx,
+            AnnotatedFormUpdate {
+                id: input.word_id,
+                version: input.version,
+                source: MaybeUndefined::Value(first),
+                commentary: MaybeUndefined::Undefined,
+                segments: MaybeUndefined::Undefined,
+            },
+            edited_by,
+        )
+        .await?;
+        // The old segmentation was for the whole word, so it no longer fits.
+        query_file!("queries/delete_word_segments_from.sql", input.word_id, 0)
+            .execute(&mut tx)
+            .await?;
+
+        let new_word = NewWordInput {
+            source: second,
+            simple_phonetics: None,
+            phonemic: None,
+            english_gloss: None,
+            commentary: None,
+        };
+        let word = insert_new_word(&mut tx, document_id, &new_word).await?;
+        layout.insert_word(paragraph_id, Some(input.word_id), word)?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Join a word with the one after it, returning the document ID.
+    pub async fn merge_words(&self, input: MergeWordsInput, edited_by: &Uuid) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = word_document_id(&mut tx, input.word_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        let first = layout.find_word_layout(input.word_id)?;
+        let next = layout.next_word(input.word_id)?;
+
+        let next_version = query_file!("queries/lock_word_versions.sql", &[next.id][..])
+            .fetch_one(&mut tx)
+            .await?
+            .version;
+        if next_version != input.next_version {
+            return Err(DailpError::Conflict(
+                "The next word has changed since this edit was started".into(),
+            )
+            .into());
+        }
+        let mut sources = Vec::new();
+        for id in [first.id, next.id] {
+            let snapshot: WordSnapshot = serde_json::from_value(
+                query_file_scalar!("queries/word_snapshot.sql", id)
+                    .fetch_one(&mut tx)
+                    .await?,
+            )?;
+            sources.push(snapshot.source);
+        }
+        update_word_in(
+            &mut tx,
+            AnnotatedFormUpdate {
+                id: input.word_id,
+                version: input.version,
+                source: MaybeUndefined::Value(sources.concat()),
+                commentary: MaybeUndefined::Undefined,
+                segments: MaybeUndefined::Undefined,
+            },
+            edited_by,
+        )
+        .await?;
+
+        // The first word's segmentation doesn't cover the joined word.
+        query_file!("queries/delete_word_segments_from.sql", input.word_id, 0)
+            .execute(&mut tx)
+            .await?;
+        query_file!("queries/move_comments.sql", next.id, input.word_id)
+            .execute(&mut tx)
+            .await?;
+        query_file!("queries/delete_word.sql", next.id)
+            .execute(&mut tx)
+            .await?;
+        layout.remove_word(next.id)?;
+        layout.resize_word(input.word_id, first.length + next.length)?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Add a paragraph with some words to a page, returning the document ID.
+    pub async fn add_paragraph(&self, input: AddParagraphInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = query_file_scalar!("queries/page_document_id.sql", input.page_id)
+            .fetch_optional(&mut tx)
+            .await?
+            .ok_or_else(|| DailpError::NotFound(format!("Page {} doesn't exist", input.page_id)))?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+
+        let paragraph_id = query_file_scalar!(
+            "queries/add_paragraph.sql",
+            input.page_id,
+            input.translation
+        )
+        .fetch_one(&mut tx)
+        .await?;
+        let mut words = Vec::new();
+        for word in &input.words {
+            words.push(insert_new_word(&mut tx, document_id, word).await?);
+        }
+        layout.insert_paragraph(
+            input.page_id,
+            input.after_paragraph_id,
+            ParagraphLayout {
+                id: paragraph_id,
+                words,
+            },
+        )?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Break a paragraph into two, returning the document ID.
+    pub async fn split_paragraph(&self, input: SplitParagraphInput) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = paragraph_document_id(&mut tx, input.paragraph_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        let page_id = layout.page_of_paragraph(input.paragraph_id)?;
+        let new_paragraph_id = query_file_scalar!("queries/add_paragraph.sql", page_id, "")
+            .fetch_one(&mut tx)
+            .await?;
+        layout.split_paragraph(input.paragraph_id, input.before_word_id, new_paragraph_id)?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    /// Join a paragraph with the one after it, returning the document ID.
+    pub async fn merge_paragraphs(
+        &self,
+        input: MergeParagraphsInput,
+        edited_by: &Uuid,
+    ) -> Result<Uuid> {
+        let mut tx = self.client.begin().await?;
+        let document_id = paragraph_document_id(&mut tx, input.paragraph_id).await?;
+        let mut layout = lock_document_layout(&mut tx, document_id).await?;
+        let next_id = layout.next_paragraph(input.paragraph_id)?;
+
+        let next_version = query_file_scalar!("queries/paragraph_version.sql", next_id)
+            .fetch_one(&mut tx)
+            .await?;
+        if next_version != input.next_version {
+            return Err(DailpError::Conflict(
+                "The next paragraph has changed since this edit was started".into(),
+            )
+            .into());
+        }
+        let mut translations = Vec::new();
+        for id in [input.paragraph_id, next_id] {
+            let snapshot: ParagraphSnapshot = serde_json::from_value(
+                query_file_scalar!("queries/paragraph_snapshot.sql", id)
+                    .fetch_one(&mut tx)
+                    .await?,
+            )?;
+            translations.push(snapshot.translation);
+        }
+        let translation = translations
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .join(" ");
+        update_paragraph_in(
+            &mut tx,
+            ParagraphUpdate {
+                id: input.paragraph_id,
+                version: input.version,
+                translation: MaybeUndefined::Value(translation),
+            },
+            edited_by,
+        )
+        .await?;
+
+        query_file!("queries/move_comments.sql", next_id, input.paragraph_id)
+            .execute(&mut tx)
+            .await?;
+        layout.merge_paragraphs(input.paragraph_id, next_id)?;
+        save_document_layout(&mut tx, document_id, &layout).await?;
+        query_file!("queries/delete_paragraph.sql", next_id)
+            .execute(&mut tx)
+            .await?;
+        tx.commit().await?;
+        Ok(document_id)
+    }
+
+    pub async fn update_paragraph(
+        &self,
+        paragraph: ParagraphUpdate,
+        edited_by: &Uuid,
+    ) -> Result<DocumentParagraph> {
+        let mut tx = self.client.begin().await?;
+        let paragraph_id = paragraph.id;
+        update_paragraph_in(&mut tx, paragraph, edited_by).await?;
+        tx.commit().await?;
+        self.paragraph_by_id(&paragraph_id).await
+    }
+
+    /// Put a paragraph back the way it was before the given revision,
+    /// recording the revert as a new revision.
+    pub async fn revert_paragraph(
+        &self,
+        revision_id: &Uuid,
+        edited_by: &Uuid,
+    ) -> Result<DocumentParagraph> {
+        let mut tx = self.client.begin().await?;
+
+        let revision = query_file!(
+            "queries/edit_revision_by_id.sql",
+            revision_id,
+            EditTargetType::Paragraph as _
+        )
+        .fetch_optional(&mut tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound("No paragraph revision with that ID".into()))?;
+        let paragraph_id = revision.target_id;
+        let restored: ParagraphSnapshot = serde_json::from_value(revision.before)?;
+
+        let before = query_file_scalar!("queries/paragraph_snapshot.sql", paragraph_id)
+            .fetch_one(&mut tx)
+            .await?;
 
         query_file!(
             "queries/update_paragraph.sql",
-            paragraph.id,
-            &translation as _
+            paragraph_id,
+            &vec![Some(restored.translation)] as _,
+            None::<i64>
         )
-        .execute(&self.client)
+        .fetch_one(&mut tx)
         .await?;
 
-        Ok(self.paragraph_by_id(&paragraph.id).await?)
+        let after = query_file_scalar!("queries/paragraph_snapshot.sql", paragraph_id)
+            .fetch_one(&mut tx)
+            .await?;
+        record_revision(
+            &mut tx,
+            EditTargetType::Paragraph,
+            paragraph_id,
+            edited_by,
+            before,
+            after,
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        self.paragraph_by_id(&paragraph_id).await
     }
 
     pub async fn update_contributor_attribution(
@@ -755,12 +2681,80 @@ impl Database {
         Ok(document_id)
     }
 
-    pub async fn all_pages(&self) -> Result<Vec<page::Page>> {
-        todo!("Implement content pages")
+    /// All content pages in site order, including drafts if asked.
+    pub async fn all_pages(&self, include_drafts: bool) -> Result<Vec<page::Page>> {
+        query_file!("queries/pages.sql", None::<&[String]>, include_drafts)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|page| {
+                Ok(page::Page {
+                    id: page.path,
+                    title: page.title,
+                    body: serde_json::from_value(page.body)?,
+                    status: page.status,
+                    published_at: page.published_at.map(DateTime::new),
+                })
+            })
+            .collect()
+    }
+
+    /// Make a new content page at the end of the site order.
+    pub async fn create_page(&self, page: page::CreatePageInput) -> Result<()> {
+        page::validate_page_path(&page.id)?;
+        let mut tx = self.client.begin().await?;
+        query_file!(
+            "queries/insert_page.sql",
+            page.id,
+            page.title,
+            page.status as _
+        )
+        .execute(&mut tx)
+        .await?;
+        insert_page_blocks(&mut tx, &page.id, page.body).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Change the title, content, or status of a content page.
+    pub async fn update_page(&self, page: page::UpdatePageInput) -> Result<()> {
+        let mut tx = self.client.begin().await?;
+        query_file!(
+            "queries/update_page.sql",
+            page.id,
+            page.title,
+            page.status as _
+        )
+        .fetch_optional(&mut tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound(format!("No page at {}", page.id)))?;
+        if let Some(body) = page.body {
+            query_file!("queries/delete_page_blocks.sql", page.id)
+                .execute(&mut tx)
+                .await?;
+            insert_page_blocks(&mut tx, &page.id, body).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn delete_page(&self, path: &str) -> Result<()> {
+        let deleted = query_file!("queries/delete_page.sql", path)
+            .execute(&self.client)
+            .await?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(DailpError::NotFound(format!("No page at {}", path)).into());
+        }
+        Ok(())
     }
 
-    pub async fn update_page(&self, _page: page::Page) -> Result<()> {
-        todo!("Implement content pages")
+    /// Put the given pages first in the site order, followed by any others.
+    pub async fn reorder_pages(&self, paths: &[String]) -> Result<()> {
+        query_file!("queries/reorder_pages.sql", paths)
+            .execute(&self.client)
+            .await?;
+        Ok(())
     }
 
     pub async fn words_in_document(
@@ -768,13 +2762,24 @@ impl Database {
         document_id: DocumentId,
         start: Option<i64>,
         end: Option<i64>,
+        page: &PageRequest<WordCursor>,
     ) -> Result<impl Iterator<Item = AnnotatedForm>> {
+        let (after_document, after_index, after_id) = WordCursor::parts(&page.after);
+        let (before_document, before_index, before_id) = WordCursor::parts(&page.before);
         let words = query_file_as!(
             BasicWord,
             "queries/document_words.sql",
             document_id.0,
             start,
-            end
+            end,
+            after_document,
+            after_index,
+            after_id,
+            before_document,
+            before_index,
+            before_id,
+            page.from_end(),
+            page.limit()
         )
         .fetch_all(&self.client)
         .await?;
@@ -881,7 +2886,8 @@ impl Database {
             &meta.date as &Option<Date>,
             slice_id,
             collection_id,
-            index_in_collection
+            index_in_collection,
+            meta.genre
         )
         .fetch_one(&mut tx)
         .await?;
@@ -1052,7 +3058,7 @@ impl Database {
 
         // Convert the list of stems into a list for each field to prepare for a
         // bulk DB insertion.
-        let (glosses, shapes): (Vec<_>, Vec<_>) = stems
+        let (glosses, shapes, definitions): (Vec<_>, Vec<_>, Vec<_>) = stems
             .into_iter()
             .map(|stem| {
                 (
@@ -1065,7 +3071,13 @@ impl Database {
                 )
             })
             .unique_by(|(gloss, _)| gloss.clone())
-            .map(|(gloss, stem)| (gloss, stem.segments.as_ref().unwrap()[0].morpheme.clone()))
+            .map(|(gloss, stem)| {
+                (
+                    gloss,
+                    stem.segments.as_ref().unwrap()[0].morpheme.clone(),
+                    stem.english_gloss.join("; "),
+                )
+            })
             .multiunzip();
 
         // Insert all the morpheme glosses from this dictionary at once.
@@ -1073,7 +3085,8 @@ impl Database {
             "queries/upsert_dictionary_entry.sql",
             document_id.0,
             &*glosses,
-            &*shapes
+            &*shapes,
+            &*definitions
         )
         .execute(&mut tx)
         .await?;
@@ -1486,6 +3499,7 @@ impl Loader<DocumentId> for Database {
         &self,
         keys: &[DocumentId],
     ) -> Result<HashMap<DocumentId, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         // Turn keys into strings for database request.
         // TODO ideally I'd be able to pass `keys` directly instead of mapping it.
         let keys: Vec<_> = keys.iter().map(|x| x.0).collect();
@@ -1530,7 +3544,7 @@ impl Loader<DocumentId> for Database {
                         .contributors
                         .and_then(|x| serde_json::from_value(x).ok())
                         .unwrap_or_default(),
-                    genre: None,
+                    genre: item.genre,
                     order_index: 0,
                     page_images: None,
                     sources: Vec::new(),
@@ -1551,6 +3565,7 @@ impl Loader<DocumentShortName> for Database {
         &self,
         keys: &[DocumentShortName],
     ) -> Result<HashMap<DocumentShortName, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         // Turn keys into strings for database request.
         // TODO ideally I'd be able to pass `keys` directly instead of mapping it.
         let keys: Vec<_> = keys.iter().map(|x| &x.0 as &str).collect();
@@ -1596,7 +3611,7 @@ impl Loader<DocumentShortName> for Database {
                         .contributors
                         .and_then(|x| serde_json::from_value(x).ok())
                         .unwrap_or_default(),
-                    genre: None,
+                    genre: item.genre,
                     order_index: 0,
                     page_images: None,
                     sources: Vec::new(),
@@ -1618,6 +3633,7 @@ impl Loader<PagesInDocument> for Database {
         &self,
         keys: &[PagesInDocument],
     ) -> Result<HashMap<PagesInDocument, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| (k.0)).collect();
         let items = query_file!("queries/document_pages.sql", &keys[..])
             .fetch_all(&self.client)
@@ -1656,6 +3672,7 @@ impl Loader<ParagraphsInPage> for Database {
         &self,
         keys: &[ParagraphsInPage],
     ) -> Result<HashMap<ParagraphsInPage, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
         let items = query_file!("queries/document_paragraphs.sql", &keys[..])
             .fetch_all(&self.client)
@@ -1689,26 +3706,68 @@ impl Loader<PartsOfWord> for Database {
         &self,
         keys: &[PartsOfWord],
     ) -> Result<HashMap<PartsOfWord, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
         let items = query_file!("queries/word_parts.sql", &keys[..])
             .fetch_all(&self.client)
             .await?;
         Ok(items
             .into_iter()
-            .map(|part| {
-                (
-                    PartsOfWord(part.word_id),
-                    WordSegment {
-                        system: None,
-                        morpheme: part.morpheme,
-                        gloss: part.gloss,
-                        gloss_id: part.gloss_id,
-                        role: part.role,
-                        matching_tag: None,
-                    },
-                )
+            .map(|part| {
+                (
+                    PartsOfWord(part.word_id),
+                    WordSegment {
+                        system: None,
+                        morpheme: part.morpheme,
+                        gloss: part.gloss,
+                        gloss_id: part.gloss_id,
+                        role: part.role,
+                        matching_tag: None,
+                    },
+                )
+            })
+            .into_group_map())
+    }
+}
+
+#[async_trait]
+impl Loader<EditVersion> for Database {
+    type Value = i64;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(
+        &self,
+        keys: &[EditVersion],
+    ) -> Result<HashMap<EditVersion, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let (mut words, mut paragraphs, mut documents) = (Vec::new(), Vec::new(), Vec::new());
+        for key in keys {
+            match *key {
+                EditVersion::Word(id) => words.push(id),
+                EditVersion::Paragraph(id) => paragraphs.push(id),
+                EditVersion::Document(id) => documents.push(id),
+            }
+        }
+        let items = query_file!(
+            "queries/edit_versions.sql",
+            &words[..],
+            &paragraphs[..],
+            &documents[..]
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(items
+            .into_iter()
+            .filter_map(|item| {
+                let key = match (item.kind.as_str(), item.id) {
+                    ("Word", Some(id)) => EditVersion::Word(id),
+                    ("Paragraph", Some(id)) => EditVersion::Paragraph(id),
+                    ("Document", Some(id)) => EditVersion::Document(id),
+                    _ => return None,
+                };
+                Some((key, item.version?))
             })
-            .into_group_map())
+            .collect())
     }
 }
 
@@ -1717,6 +3776,7 @@ impl Loader<TagId> for Database {
     type Value = Vec<MorphemeTag>;
     type Error = Arc<sqlx::Error>;
     async fn load(&self, keys: &[TagId]) -> Result<HashMap<TagId, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         use async_graphql::{InputType, Name, Value};
         let glosses: Vec<_> = keys.iter().map(|k| k.0.clone()).collect();
         let systems: Vec<_> = keys
@@ -1766,6 +3826,7 @@ impl Loader<WordsInParagraph> for Database {
         &self,
         keys: &[WordsInParagraph],
     ) -> Result<HashMap<WordsInParagraph, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
         let items = query_file!("queries/words_in_paragraph.sql", &keys[..])
             .fetch_all(&self.client)
@@ -1814,6 +3875,7 @@ impl Loader<TagForMorpheme> for Database {
         &self,
         keys: &[TagForMorpheme],
     ) -> Result<HashMap<TagForMorpheme, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         use async_graphql::{InputType, Name, Value};
         let gloss_ids: Vec<_> = keys.iter().map(|k| k.0).collect();
         let systems: Vec<_> = keys
@@ -1863,6 +3925,7 @@ impl Loader<ImageSourceId> for Database {
         &self,
         keys: &[ImageSourceId],
     ) -> Result<HashMap<ImageSourceId, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
         let items = query_file!("queries/image_sources.sql", &keys)
             .fetch_all(&self.client)
@@ -1891,6 +3954,7 @@ impl Loader<ContributorsForDocument> for Database {
         &self,
         keys: &[ContributorsForDocument],
     ) -> Result<HashMap<ContributorsForDocument, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
         let items = query_file!("queries/document_contributors.sql", &keys)
             .fetch_all(&self.client)
@@ -1901,6 +3965,7 @@ impl Loader<ContributorsForDocument> for Database {
                 (
                     ContributorsForDocument(x.document_id),
                     Contributor {
+                        id: Some(x.id),
                         name: x.full_name,
                         role: x.contribution_role,
                     },
@@ -1919,19 +3984,51 @@ impl Loader<PersonFullName> for Database {
         &self,
         keys: &[PersonFullName],
     ) -> Result<HashMap<PersonFullName, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0.clone()).collect();
-        let items = query_file!("queries/contributors_by_name.sql", &keys)
+        let items = query_file_as!(
+            ContributorDetails,
+            "queries/contributors.sql",
+            &[] as &[Uuid],
+            &keys,
+            false
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(items
+            .into_iter()
+            .map(|x| (PersonFullName(x.full_name.clone()), x))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Loader<UserProfileById> for Database {
+    type Value = UserProfile;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(
+        &self,
+        keys: &[UserProfileById],
+    ) -> Result<HashMap<UserProfileById, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
+        let items = query_file!("queries/user_profiles.sql", &keys)
             .fetch_all(&self.client)
             .await?;
         Ok(items
             .into_iter()
             .map(|x| {
                 (
-                    PersonFullName(x.full_name.clone()),
-                    ContributorDetails {
-                        full_name: x.full_name,
-                        alternate_name: None,
-                        birth_date: None,
+                    UserProfileById(x.id),
+                    UserProfile {
+                        id: x.id.into(),
+                        display_name: x.display_name,
+                        avatar_url: x.avatar_url,
+                        preferred_orthography: x.preferred_orthography,
+                        interface_language: x.interface_language,
+                        notify_on_comment_replies: x.notify_on_comment_replies,
+                        notify_on_edit_reviews: x.notify_on_edit_reviews,
                     },
                 )
             })
@@ -1939,13 +4036,101 @@ impl Loader<PersonFullName> for Database {
     }
 }
 
+#[async_trait]
+impl Loader<ContributorById> for Database {
+    type Value = ContributorDetails;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(
+        &self,
+        keys: &[ContributorById],
+    ) -> Result<HashMap<ContributorById, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
+        let items = query_file_as!(
+            ContributorDetails,
+            "queries/contributors.sql",
+            &keys,
+            &[] as &[String],
+            false
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(items
+            .into_iter()
+            .map(|x| (ContributorById(x.id), x))
+            .collect())
+    }
+}
+
 #[async_trait]
 impl Loader<PageId> for Database {
     type Value = page::Page;
     type Error = Arc<sqlx::Error>;
 
-    async fn load(&self, _keys: &[PageId]) -> Result<HashMap<PageId, Self::Value>, Self::Error> {
-        todo!("Implement content pages")
+    async fn load(&self, keys: &[PageId]) -> Result<HashMap<PageId, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let paths: Vec<_> = keys.iter().map(|id| id.0.clone()).collect();
+        // Drafts are filtered out for readers when resolving the page.
+        let items = query_file!("queries/pages.sql", Some(&paths[..]), true)
+            .fetch_all(&self.client)
+            .await?;
+        // A body that doesn't parse is an error, just as it is in `all_pages`.
+        Ok(items
+            .into_iter()
+            .map(|page| {
+                Ok((
+                    PageId(page.path.clone()),
+                    page::Page {
+                        id: page.path,
+                        title: page.title,
+                        body: serde_json::from_value(page.body)
+                            .map_err(|err| sqlx::Error::Decode(err.into()))?,
+                        status: page.status,
+                        published_at: page.published_at.map(DateTime::new),
+                    },
+                ))
+            })
+            .collect::<Result<_, sqlx::Error>>()?)
+    }
+}
+
+#[async_trait]
+impl Loader<WordById> for Database {
+    type Value = AnnotatedForm;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[WordById]) -> Result<HashMap<WordById, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
+        let items = query_file_as!(BasicWord, "queries/words_by_id.sql", &keys[..])
+            .fetch_all(&self.client)
+            .await?;
+        Ok(items
+            .into_iter()
+            .map(|w| (WordById(w.id), w.into()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Loader<AudioSliceById> for Database {
+    type Value = AudioSlice;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(
+        &self,
+        keys: &[AudioSliceById],
+    ) -> Result<HashMap<AudioSliceById, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
+        let keys: Vec<_> = keys.iter().map(|k| k.0).collect();
+        let items = query_file_as!(BasicAudioSlice, "queries/audio_slices.sql", &keys[..])
+            .fetch_all(&self.client)
+            .await?;
+        Ok(items
+            .into_iter()
+            .map(|slice| (AudioSliceById(slice.id), slice.into()))
+            .collect())
     }
 }
 
@@ -2074,6 +4259,7 @@ impl Loader<ChaptersInCollection> for Database {
         &self,
         keys: &[ChaptersInCollection],
     ) -> Result<HashMap<ChaptersInCollection, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0.clone()).collect();
         let items = query_file!("queries/collection_chapters.sql", &keys)
             .fetch_all(&self.client)
@@ -2111,6 +4297,7 @@ impl Loader<EditedCollectionDetails> for Database {
         &self,
         keys: &[EditedCollectionDetails],
     ) -> Result<HashMap<EditedCollectionDetails, Self::Value>, Self::Error> {
+        metrics::record_batch(keys);
         let keys: Vec<_> = keys.iter().map(|k| k.0.clone()).collect();
         let items = query_file!("queries/collection_attributes.sql", &keys)
             .fetch_all(&self.client)
@@ -2132,6 +4319,545 @@ impl Loader<EditedCollectionDetails> for Database {
     }
 }
 
+/// Lock a collection against other changes to its chapters.
+async fn lock_edited_collection(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    collection_slug: &str,
+) -> Result<()> {
+    query_file!("queries/lock_edited_collection.sql", collection_slug)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            DailpError::NotFound(format!("Collection {} doesn't exist", collection_slug))
+        })?;
+    Ok(())
+}
+
+/// Lock the collection a chapter is in, then load the chapter.
+async fn locked_chapter(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    chapter_id: Uuid,
+) -> Result<ChapterRow> {
+    let chapter = query_file_as!(ChapterRow, "queries/chapter_by_id.sql", chapter_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound(format!("Chapter {} doesn't exist", chapter_id)))?;
+    lock_edited_collection(tx, &chapter.collection_slug).await?;
+    // Load the chapter again now that nobody else can move it.
+    Ok(
+        query_file_as!(ChapterRow, "queries/chapter_by_id.sql", chapter_id)
+            .fetch_one(&mut *tx)
+            .await?,
+    )
+}
+
+/// Path of the chapter with the given ID, which must be in the given
+/// collection, or the path of the collection itself if there's no chapter.
+async fn chapter_parent_path(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    collection_slug: &str,
+    parent_id: Option<Uuid>,
+) -> Result<PgLTree> {
+    let parent_id = match parent_id {
+        Some(id) => id,
+        None => return Ok(PgLTree::from_iter([collection_slug])?),
+    };
+    let parent = query_file_as!(ChapterRow, "queries/chapter_by_id.sql", parent_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .filter(|parent| parent.collection_slug == collection_slug)
+        .ok_or_else(|| {
+            DailpError::Validation(format!("Chapter {} isn't in this collection", parent_id))
+        })?;
+    Ok(parent.chapter_path)
+}
+
+/// Put a chapter at the given position among the others under the same
+/// parent, renumbering all of them.
+async fn place_chapter_in(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    parent_path: PgLTree,
+    chapter_id: Uuid,
+    index: Option<i64>,
+) -> Result<()> {
+    let siblings = query_file_scalar!("queries/sibling_chapters.sql", parent_path)
+        .fetch_all(&mut *tx)
+        .await?;
+    let ordered = place_chapter(&siblings, chapter_id, index);
+    query_file!("queries/set_chapter_positions.sql", &ordered[..])
+        .execute(&mut *tx)
+        .await?;
+    Ok(())
+}
+
+/// Lock a document against other changes to its structure, and load how its
+/// words are laid out.
+async fn lock_document_layout(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    document_id: Uuid,
+) -> Result<DocumentLayout> {
+    query_file!("queries/lock_document.sql", document_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound(format!("Document {} doesn't exist", document_id)))?;
+    let rows = query_file!("queries/document_layout.sql", document_id)
+        .fetch_all(&mut *tx)
+        .await?;
+    Ok(DocumentLayout::from_rows(rows.into_iter().map(|row| {
+        LayoutRow {
+            page_id: row.page_id,
+            paragraph_id: row.paragraph_id,
+            word_id: row.word_id,
+            page_number: row.page_number,
+            word_length: row.word_source.map(|source| source.chars().count() as i64),
+        }
+    })))
+}
+
+/// Save the position of every word and paragraph in a document.
+async fn save_document_layout(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    document_id: Uuid,
+    layout: &DocumentLayout,
+) -> Result<()> {
+    let (paragraphs, words) = layout.positions();
+
+    let (word_id, page_id, page_number, index, start, end): (
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+    ) = words
+        .into_iter()
+        .map(|w| (w.id, w.page_id, w.page_number, w.index, w.start, w.end))
+        .multiunzip();
+    query_file!(
+        "queries/save_word_positions.sql",
+        &*word_id,
+        &*page_id,
+        &*page_number,
+        &*index,
+        &*start,
+        &*end
+    )
+    .execute(&mut *tx)
+    .await?;
+    // Words without a page or character range aren't in the layout.
+    query_file!(
+        "queries/renumber_unplaced_words.sql",
+        document_id,
+        word_id.len() as i64,
+        &*word_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let (paragraph_id, page_id, start, end): (Vec<_>, Vec<_>, Vec<_>, Vec<_>) = paragraphs
+        .into_iter()
+        .map(|p| (p.id, p.page_id, p.start, p.end))
+        .multiunzip();
+    query_file!(
+        "queries/save_paragraph_positions.sql",
+        &*paragraph_id,
+        &*page_id,
+        &*start,
+        &*end
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Characters are numbered across the whole document, like words.
+    let (page_id, index, character): (Vec<_>, Vec<_>, Vec<_>) =
+        query_file!("queries/document_word_sources.sql", document_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .flat_map(|word| {
+                let start = word.start;
+                let page_id = word.page_id;
+                word.source_text
+                    .chars()
+                    .enumerate()
+                    .map(move |(i, c)| (page_id, start + i as i64, c.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .multiunzip();
+    query_file!(
+        "queries/replace_character_transcriptions.sql",
+        document_id,
+        &*page_id,
+        &*index,
+        &*character
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+/// Add a word to a document without placing it on a page yet.
+async fn insert_new_word(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    document_id: Uuid,
+    word: &NewWordInput,
+) -> Result<WordLayout> {
+    let length = word.length()?;
+    let id = query_file_scalar!(
+        "queries/insert_new_word.sql",
+        word.source,
+        word.simple_phonetics,
+        word.phonemic,
+        word.english_gloss,
+        word.commentary,
+        document_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    Ok(WordLayout { id, length })
+}
+
+async fn word_document_id(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    word_id: Uuid,
+) -> Result<Uuid> {
+    query_file_scalar!("queries/word_document_id.sql", word_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound(format!("Word {} doesn't exist", word_id)).into())
+}
+
+async fn paragraph_document_id(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    paragraph_id: Uuid,
+) -> Result<Uuid> {
+    query_file_scalar!("queries/paragraph_document_id.sql", paragraph_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            DailpError::NotFound(format!("Paragraph {} doesn't exist", paragraph_id)).into()
+        })
+}
+
+/// Apply one word update within a transaction, returning the ID of the
+/// document containing the word.
+async fn update_word_in(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    word: AnnotatedFormUpdate,
+    edited_by: &Uuid,
+) -> Result<Uuid> {
+    let word_id = word.id;
+
+    let before = query_file_scalar!("queries/word_snapshot.sql", word_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let source = word.source.into_vec();
+    let commentary = word.commentary.into_vec();
+
+    let document_id = match query_file!(
+        "queries/update_word.sql",
+        word_id,
+        &source as _,
+        &commentary as _,
+        Some(word.version)
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    {
+        Some(updated) => updated.document_id,
+        None => {
+            let current_version = query_file_scalar!("queries/word_version.sql", word_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            return Err(EditConflict {
+                current_version,
+                current: before,
+            }
+            .into());
+        }
+    };
+
+    // Only touch the segments if a new, non-empty segmentation was given.
+    let segments = word.segments.take().unwrap_or_default();
+    if !segments.is_empty() {
+        let system_name: Option<CherokeeOrthography> = *(&segments[0].system.clone());
+
+        let (doc_id, gloss, word_ids, index, morpheme, role): (
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+        ) = segments
+            .into_iter()
+            .enumerate()
+            .map(move |(index, segment)| {
+                (
+                    document_id,
+                    segment.gloss,
+                    word_id,
+                    index as i64, // index of the segment in the word
+                    segment.morpheme,
+                    segment.role,
+                )
+            })
+            .multiunzip();
+
+        // Convert the given glosses if they have an internal for to add to the database.
+        let internal_glosses = query_file_scalar!(
+            "queries/find_internal_glosses.sql",
+            &*gloss,
+            match system_name {
+                Some(CherokeeOrthography::Taoc) => "TAOC",
+                _ =>
+                    return Err(DailpError::Validation(
+                        "Other Cherokee systems are currently not supported".into()
+                    )
+                    .into()),
+            }
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Add any newly created local glosses into morpheme gloss table.
+        query_file!(
+            "queries/upsert_local_morpheme_glosses.sql",
+            &*doc_id,
+            &*internal_glosses as _,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        query_file!(
+            "queries/upsert_many_word_segments.sql",
+            &*doc_id,
+            &*internal_glosses as _,
+            &*word_ids,
+            &*index,
+            &*morpheme,
+            &*role as _
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let after = query_file_scalar!("queries/word_snapshot.sql", word_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    record_revision(
+        &mut *tx,
+        EditTargetType::Word,
+        word_id,
+        edited_by,
+        before,
+        after,
+    )
+    .await?;
+
+    Ok(document_id)
+}
+
+/// Apply one paragraph update within a transaction.
+async fn update_paragraph_in(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    paragraph: ParagraphUpdate,
+    edited_by: &Uuid,
+) -> Result<()> {
+    let translation = paragraph.translation.into_vec();
+
+    let before = query_file_scalar!("queries/paragraph_snapshot.sql", paragraph.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let updated = query_file!(
+        "queries/update_paragraph.sql",
+        paragraph.id,
+        &translation as _,
+        Some(paragraph.version)
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    if updated.is_none() {
+        let current_version = query_file_scalar!("queries/paragraph_version.sql", paragraph.id)
+            .fetch_one(&mut *tx)
+            .await?;
+        return Err(EditConflict {
+            current_version,
+            current: before,
+        }
+        .into());
+    }
+
+    let after = query_file_scalar!("queries/paragraph_snapshot.sql", paragraph.id)
+        .fetch_one(&mut *tx)
+        .await?;
+    record_revision(
+        &mut *tx,
+        EditTargetType::Paragraph,
+        paragraph.id,
+        edited_by,
+        before,
+        after,
+    )
+    .await
+}
+
+/// Lock a proposed edit for review, failing if it was already reviewed.
+async fn lock_open_proposed_edit(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    proposed_edit_id: &Uuid,
+) -> Result<ProposedEdit> {
+    let proposal: ProposedEdit = query_file_as!(
+        BasicProposedEdit,
+        "queries/lock_proposed_edit.sql",
+        proposed_edit_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| DailpError::NotFound("No proposed edit with that ID".into()))?
+    .into();
+    if proposal.status != ProposedEditStatus::Open {
+        return Err(DailpError::Conflict("This edit has already been reviewed".into()).into());
+    }
+    Ok(proposal)
+}
+
+/// Record the outcome of reviewing a proposed edit and resolve its suggestion.
+async fn review_proposed_edit(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    proposal: &ProposedEdit,
+    status: ProposedEditStatus,
+    reviewed_by: &Uuid,
+    note: Option<String>,
+) -> Result<()> {
+    query_file!(
+        "queries/review_proposed_edit.sql",
+        proposal.id,
+        status as _,
+        reviewed_by,
+        note
+    )
+    .execute(&mut *tx)
+    .await?;
+    query_file!(
+        "queries/set_comment_resolved.sql",
+        proposal.comment_id,
+        true,
+        reviewed_by
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+/// Save the given blocks as the content of a page, which must not have any
+/// blocks yet.
+async fn insert_page_blocks(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    path: &str,
+    blocks: Vec<page::ContentBlockInput>,
+) -> Result<()> {
+    let blocks = blocks
+        .into_iter()
+        .map(|block| serde_json::to_value(page::ContentBlock::from(block)))
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    query_file!("queries/insert_page_blocks.sql", path, &blocks[..])
+        .execute(&mut *tx)
+        .await?;
+    Ok(())
+}
+
+/// Load a contributor, keeping anyone else from changing them until the
+/// transaction ends.
+async fn locked_contributor(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+) -> Result<ContributorDetails> {
+    query_file!("queries/lock_contributor.sql", id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DailpError::NotFound(format!("Contributor {} doesn't exist", id)))?;
+    Ok(query_file_as!(
+        ContributorDetails,
+        "queries/contributors.sql",
+        &[id],
+        &[] as &[String],
+        false
+    )
+    .fetch_one(&mut *tx)
+    .await?)
+}
+
+/// Overwrite the stored details of a contributor.
+async fn save_contributor(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    person: &ContributorDetails,
+) -> Result<()> {
+    query_file!(
+        "queries/save_contributor.sql",
+        person.id,
+        person.full_name,
+        person.alternate_name,
+        person.birth_date as _,
+        person.death_date as _,
+        person.affiliation,
+        person.biography,
+        &person.roles,
+        person.viaf_id,
+        person.orcid,
+        person.wikidata_id
+    )
+    .execute(&mut *tx)
+    .await?;
+    Ok(())
+}
+
+/// Save the given blocks as the content of a chapter, replacing any it had.
+async fn replace_chapter_blocks(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    chapter_id: Uuid,
+    blocks: impl IntoIterator<Item = page::ContentBlock>,
+) -> Result<()> {
+    let blocks = blocks
+        .into_iter()
+        .map(serde_json::to_value)
+        .collect::<serde_json::Result<Vec<_>>>()?;
+    query_file!("queries/delete_chapter_blocks.sql", chapter_id)
+        .execute(&mut *tx)
+        .await?;
+    query_file!("queries/insert_chapter_blocks.sql", chapter_id, &blocks[..])
+        .execute(&mut *tx)
+        .await?;
+    Ok(())
+}
+
+/// Save a revision of some edited content, unless the edit didn't change
+/// anything.
+async fn record_revision(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    target_type: EditTargetType,
+    target_id: Uuid,
+    edited_by: &Uuid,
+    before: serde_json::Value,
+    after: serde_json::Value,
+) -> Result<()> {
+    if before != after {
+        query_file!(
+            "queries/insert_edit_revision.sql",
+            target_type as _,
+            target_id,
+            edited_by,
+            before,
+            after
+        )
+        .execute(tx)
+        .await?;
+    }
+    Ok(())
+}
+
 /// A simplified comment type that is easier to pull out of the database
 struct BasicComment {
     pub id: Uuid,
@@ -2145,6 +4871,13 @@ struct BasicComment {
 
     pub parent_id: Uuid,
     pub parent_type: CommentParentType,
+
+    pub edited_at: Option<NaiveDateTime>,
+    pub hidden_at: Option<NaiveDateTime>,
+    pub hidden_reason: Option<String>,
+    pub resolved_at: Option<NaiveDateTime>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_by_name: Option<String>,
 }
 
 impl Into<Comment> for BasicComment {
@@ -2160,6 +4893,83 @@ impl Into<Comment> for BasicComment {
             comment_type: self.comment_type,
             parent_id: self.parent_id,
             parent_type: self.parent_type,
+            edited_at: self.edited_at.map(DateTime::new),
+            hidden_at: self.hidden_at.map(DateTime::new),
+            hidden_reason: self.hidden_reason,
+            resolved_at: self.resolved_at.map(DateTime::new),
+            resolved_by: self
+                .resolved_by
+                .zip(self.resolved_by_name)
+                .map(|(id, display_name)| User {
+                    id: id.into(),
+                    display_name,
+                }),
+        }
+    }
+}
+
+/// A chapter as stored, with its path still as an ltree
+struct ChapterRow {
+    id: Uuid,
+    title: String,
+    document_id: Option<Uuid>,
+    wordpress_id: Option<i64>,
+    index_in_parent: i64,
+    chapter_path: PgLTree,
+    section: CollectionSection,
+    collection_slug: String,
+}
+
+impl From<ChapterRow> for CollectionChapter {
+    fn from(chapter: ChapterRow) -> Self {
+        Self {
+            id: chapter.id,
+            path: chapter
+                .chapter_path
+                .into_iter()
+                .map(|s| (*s).into())
+                .collect(),
+            index_in_parent: chapter.index_in_parent,
+            title: chapter.title,
+            document_id: chapter.document_id.map(DocumentId),
+            wordpress_id: chapter.wordpress_id,
+            section: chapter.section,
+        }
+    }
+}
+
+/// A proposed edit as it comes out of the database
+struct BasicProposedEdit {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub target_type: EditTargetType,
+    pub target_id: Uuid,
+    pub proposed_update: serde_json::Value,
+    pub status: ProposedEditStatus,
+    pub reviewed_at: Option<NaiveDateTime>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_by_name: Option<String>,
+    pub review_note: Option<String>,
+}
+
+impl From<BasicProposedEdit> for ProposedEdit {
+    fn from(edit: BasicProposedEdit) -> Self {
+        Self {
+            id: edit.id,
+            comment_id: edit.comment_id,
+            target_type: edit.target_type,
+            target_id: edit.target_id,
+            proposed_update: edit.proposed_update,
+            status: edit.status,
+            reviewed_at: edit.reviewed_at.map(DateTime::new),
+            reviewed_by: edit
+                .reviewed_by
+                .zip(edit.reviewed_by_name)
+                .map(|(id, display_name)| User {
+                    id: id.into(),
+                    display_name,
+                }),
+            review_note: edit.review_note,
         }
     }
 }
@@ -2170,9 +4980,26 @@ pub struct TagId(pub String, pub CherokeeOrthography);
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PartsOfWord(pub Uuid);
 
+/// Key to query the current version of some editable content
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum EditVersion {
+    /// Version of the word with this ID
+    Word(Uuid),
+    /// Version of the paragraph with this ID
+    Paragraph(Uuid),
+    /// Version of the document with this ID
+    Document(Uuid),
+}
+
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PersonFullName(pub String);
 
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct ContributorById(pub Uuid);
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct UserProfileById(pub Uuid);
+
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct ContributorsForDocument(pub Uuid);
 
@@ -2188,6 +5015,14 @@ pub struct TagForMorpheme(pub Uuid, pub CherokeeOrthography);
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PageId(pub String);
 
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct WordById(pub Uuid);
+
+/// Key to retrieve an audio slice, unless editors have kept it out of the
+/// edited collection
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct AudioSliceById(pub Uuid);
+
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct ChaptersInCollection(pub String);
 
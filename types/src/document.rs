@@ -1,9 +1,11 @@
 use crate::{
-    auth::UserInfo, comment::Comment, date::DateInput, slugify, AnnotatedForm, AudioSlice,
-    Contributor, Database, Date, SourceAttribution, Translation, TranslationBlock,
+    auth::UserInfo, comment::Comment, date::DateInput, graphql_error, slugify, AnnotatedForm,
+    AudioSlice, Contributor, DailpError, Database, Date, EditRevision, EditTargetType, EditVersion,
+    PageRequest, ParagraphSnapshot, SourceAttribution, Translation, TranslationBlock,
+    WordConnection, WordCursor,
 };
 
-use async_graphql::{dataloader::DataLoader, FieldResult, MaybeUndefined};
+use async_graphql::{connection, dataloader::DataLoader, FieldResult, MaybeUndefined};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -61,6 +63,17 @@ impl AnnotatedDoc {
         self.meta.id
     }
 
+    /// Current version of the document metadata, which must be given when
+    /// editing it
+    async fn version(&self, context: &async_graphql::Context<'_>) -> FieldResult<i64> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .load_one(EditVersion::Document(self.meta.id.0))
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default())
+    }
+
     /// Full title of the document
     async fn title(&self) -> &str {
         &self.meta.title
@@ -81,7 +94,8 @@ impl AnnotatedDoc {
                 .data::<DataLoader<Database>>()?
                 .loader()
                 .get_document_bookmarked_on(&self.meta.id.0, &user.id)
-                .await?)
+                .await
+                .map_err(graphql_error)?)
         } else {
             Ok(None)
         }
@@ -119,7 +133,8 @@ impl AnnotatedDoc {
         Ok(context
             .data::<DataLoader<Database>>()?
             .load_one(crate::ContributorsForDocument(self.meta.id.0))
-            .await?
+            .await
+            .map_err(graphql_error)?
             .unwrap_or_default())
     }
 
@@ -150,10 +165,11 @@ impl AnnotatedDoc {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> FieldResult<Option<Vec<DocumentPage>>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(PagesInDocument(self.meta.id.0))
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// All the words contained in this document, dropping structural formatting
@@ -167,17 +183,48 @@ impl AnnotatedDoc {
         Ok(context
             .data::<DataLoader<Database>>()?
             .loader()
-            .words_in_document(self.meta.id, start, end)
-            .await?
+            .words_in_document(self.meta.id, start, end, &PageRequest::default())
+            .await
+            .map_err(graphql_error)?
             .collect())
     }
 
+    /// The words contained in this document one page at a time, which keeps
+    /// responses small for long documents like dictionaries.
+    async fn forms_connection(
+        &self,
+        context: &async_graphql::Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<WordConnection> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let forms = db
+                    .words_in_document(self.meta.id, None, None, &page)
+                    .await
+                    .map_err(graphql_error)?
+                    .collect();
+                Ok::<_, async_graphql::Error>(page.into_connection(forms, WordCursor::of))
+            },
+        )
+        .await
+    }
+
     async fn form_count(&self, context: &async_graphql::Context<'_>) -> FieldResult<i64> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .count_words_in_document(self.meta.id)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// All words in the document that have unanalyzed or unfamiliar parts.
@@ -189,8 +236,9 @@ impl AnnotatedDoc {
         let forms = context
             .data::<DataLoader<Database>>()?
             .loader()
-            .words_in_document(self.meta.id, None, None)
-            .await?;
+            .words_in_document(self.meta.id, None, None, &PageRequest::default())
+            .await
+            .map_err(graphql_error)?;
         Ok(forms.filter(AnnotatedForm::is_unresolved).collect())
     }
 
@@ -199,11 +247,12 @@ impl AnnotatedDoc {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> FieldResult<Option<Vec<crate::CollectionChapter>>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .chapters_by_document(self.meta.short_name.clone())
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 }
 
@@ -242,9 +291,21 @@ impl DocumentPage {
         Ok(context
             .data::<DataLoader<Database>>()?
             .load_one(ParagraphsInPage(self.id))
-            .await?
+            .await
+            .map_err(graphql_error)?
             .unwrap_or_default())
     }
+
+    /// Annotations on this page or regions of its image, oldest first
+    async fn annotations(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> FieldResult<Vec<crate::annotation::Annotation>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.annotations(crate::annotation::AnnotationsOn::Page(self.id))
+            .await
+            .map_err(graphql_error)
+    }
 }
 
 /// Page ID meant for retrieving all paragraphs within.
@@ -264,11 +325,16 @@ pub struct DocumentParagraph {
 }
 
 /// A paragraph in an annotated document that can be edited.
-#[derive(async_graphql::InputObject)]
+#[derive(async_graphql::InputObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ParagraphUpdate {
     /// Unique identifier of the form
     pub id: Uuid,
+    /// Version of the paragraph this edit is based on, from
+    /// `DocumentParagraph.version`
+    pub version: i64,
     /// English translation of the paragraph
+    #[serde(default, skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub translation: MaybeUndefined<String>,
 }
 
@@ -292,6 +358,9 @@ pub struct DeleteContributorAttribution {
 #[derive(async_graphql::InputObject)]
 pub struct DocumentMetadataUpdate {
     pub id: Uuid,
+    /// Version of the document this edit is based on, from
+    /// `AnnotatedDoc.version`
+    pub version: i64,
     pub title: MaybeUndefined<String>,
     pub written_at: MaybeUndefined<DateInput>,
 }
@@ -303,16 +372,42 @@ impl DocumentParagraph {
         Ok(context
             .data::<DataLoader<Database>>()?
             .load_one(WordsInParagraph(self.id))
-            .await?
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default())
+    }
+
+    /// Current version of this paragraph, which must be given when editing it
+    async fn version(&self, context: &async_graphql::Context<'_>) -> FieldResult<i64> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .load_one(EditVersion::Paragraph(self.id))
+            .await
+            .map_err(graphql_error)?
             .unwrap_or_default())
     }
 
     /// Get comments on this paragraph
     async fn comments(&self, context: &async_graphql::Context<'_>) -> FieldResult<Vec<Comment>> {
         let db = context.data::<DataLoader<Database>>()?.loader();
-        Ok(db
-            .comments_by_parent(&self.id, &crate::comment::CommentParentType::Paragraph)
-            .await?)
+        db.comments_by_parent(
+            &self.id,
+            &crate::comment::CommentParentType::Paragraph,
+            crate::comment::can_moderate(context),
+        )
+        .await
+        .map_err(graphql_error)
+    }
+
+    /// Past edits to this paragraph, newest first
+    async fn history(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> FieldResult<Vec<EditRevision<ParagraphSnapshot>>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.edit_history(EditTargetType::Paragraph, &self.id)
+            .await
+            .map_err(graphql_error)
     }
 }
 
@@ -354,11 +449,13 @@ impl PageImage {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> async_graphql::FieldResult<ImageSource> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(self.source_id.clone())
-            .await?
-            .ok_or_else(|| anyhow::format_err!("Image source not found"))?)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("Image source not found".into()))
+            .map_err(graphql_error)
     }
 
     /// The full IIIF url for this image resource
@@ -511,11 +608,13 @@ impl IiifImages {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> async_graphql::FieldResult<ImageSource> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(self.source.clone())
-            .await?
-            .ok_or_else(|| anyhow::format_err!("Image source not found"))?)
+            .await
+            .map_err(graphql_error)?
+            .ok_or_else(|| DailpError::NotFound("Image source not found".into()))
+            .map_err(graphql_error)
     }
 
     /// List of urls for all the images in this collection
@@ -570,11 +669,12 @@ impl DocumentCollection {
         &self,
         context: &async_graphql::Context<'_>,
     ) -> async_graphql::FieldResult<Vec<DocumentReference>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .loader()
             .documents_in_collection("", &self.slug)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 }
 
@@ -0,0 +1,180 @@
+//! Errors that we report to API clients. Each kind of error has a stable code
+//! that clients can check in the `code` extension of a GraphQL error, and an
+//! HTTP status for routes outside GraphQL.
+//!
+//! Database functions return [`anyhow::Error`], so resolvers should pass those
+//! through [`graphql_error`], which finds the [`DailpError`] inside. Errors we
+//! didn't expect are logged and reported as an [`DailpError::Upstream`]
+//! failure, without the details of what went wrong.
+
+use crate::revision::EditConflict;
+use async_graphql::{ErrorExtensions, ServerError};
+use std::sync::Arc;
+
+/// Something that went wrong while handling a request.
+#[derive(Debug)]
+pub enum DailpError {
+    /// The thing asked for doesn't exist
+    NotFound(String),
+    /// The user isn't allowed to do this
+    Forbidden(String),
+    /// The request doesn't make sense, like a blank name or an empty search
+    Validation(String),
+    /// The request clashes with what's already there, like someone else's
+    /// edit or an existing record with the same name
+    Conflict(String),
+    /// Something we depend on failed, like the database or another service
+    Upstream(anyhow::Error),
+}
+
+impl DailpError {
+    /// Code that clients can match on, in the `code` extension of GraphQL
+    /// errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "NOT_FOUND",
+            Self::Forbidden(_) => "FORBIDDEN",
+            Self::Validation(_) => "BAD_USER_INPUT",
+            Self::Conflict(_) => "CONFLICT",
+            Self::Upstream(_) => "UPSTREAM_FAILURE",
+        }
+    }
+
+    /// HTTP status code for this error on routes outside GraphQL.
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::NotFound(_) => 404,
+            Self::Forbidden(_) => 403,
+            Self::Validation(_) => 400,
+            Self::Conflict(_) => 409,
+            Self::Upstream(_) => 502,
+        }
+    }
+
+    /// Message that is safe to show to clients.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::Forbidden(message)
+            | Self::Validation(message)
+            | Self::Conflict(message) => message,
+            Self::Upstream(_) => "Something went wrong on our end, please try again later",
+        }
+    }
+
+    /// This error as it appears in a GraphQL response, for errors that happen
+    /// outside of a resolver, like before the query runs.
+    pub fn server_error(&self) -> ServerError {
+        let mut err = ServerError::new(self.message(), None);
+        err.extensions = self.extend().extensions;
+        err
+    }
+}
+
+impl std::fmt::Display for DailpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Upstream(err) => write!(f, "{}", err),
+            _ => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for DailpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Upstream(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl ErrorExtensions for DailpError {
+    fn extend(&self) -> async_graphql::Error {
+        if let Self::Upstream(err) = self {
+            log::error!("{:?}", err);
+        }
+        async_graphql::Error::new(self.message()).extend_with(|_, e| e.set("code", self.code()))
+    }
+}
+
+impl From<sqlx::Error> for DailpError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => Self::NotFound("Nothing was found with that ID".into()),
+            sqlx::Error::Database(db_err) => match db_err.code().as_deref() {
+                // unique_violation
+                Some("23505") => {
+                    Self::Conflict("Something with those details already exists".into())
+                }
+                // foreign_key_violation
+                Some("23503") => {
+                    Self::Conflict("That refers to or is used by something else".into())
+                }
+                // check_violation, not_null_violation, invalid_text_representation
+                Some("23514" | "23502" | "22P02") => {
+                    Self::Validation("Some of those values aren't allowed".into())
+                }
+                _ => Self::Upstream(err.into()),
+            },
+            _ => Self::Upstream(err.into()),
+        }
+    }
+}
+
+impl From<anyhow::Error> for DailpError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<DailpError>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<sqlx::Error>() {
+            Ok(err) => return err.into(),
+            Err(err) => err,
+        };
+        // Data loaders share their errors between every request for a key.
+        if let Some(sqlx::Error::RowNotFound) = err.downcast_ref::<Arc<sqlx::Error>>().map(|e| &**e)
+        {
+            return Self::from(sqlx::Error::RowNotFound);
+        }
+        Self::Upstream(err)
+    }
+}
+
+/// Report an error to GraphQL clients with a code saying what kind of error it
+/// is. Edit conflicts keep the current content so clients can show it.
+pub fn graphql_error(err: impl Into<anyhow::Error>) -> async_graphql::Error {
+    match err.into().downcast::<EditConflict>() {
+        Ok(conflict) => conflict.extend(),
+        Err(err) => DailpError::from(err).extend(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(err: anyhow::Error) -> Option<async_graphql::Value> {
+        graphql_error(err).extensions.unwrap().get("code").cloned()
+    }
+
+    #[test]
+    fn keeps_the_kind_of_error_through_anyhow() {
+        let err = anyhow::Error::new(DailpError::NotFound("Document 1 doesn't exist".into()));
+        assert_eq!(code(err), Some("NOT_FOUND".into()));
+
+        let err = anyhow::Error::new(sqlx::Error::RowNotFound).context("Failed to load word");
+        assert_eq!(code(err), Some("NOT_FOUND".into()));
+    }
+
+    #[test]
+    fn hides_details_of_unexpected_errors() {
+        let err = anyhow::format_err!("password authentication failed for user \"dailp\"");
+        let err = graphql_error(err);
+        assert!(!err.message.contains("password"));
+        assert_eq!(
+            err.extensions.unwrap().get("code"),
+            Some(&"UPSTREAM_FAILURE".into())
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Live updates about changes to documents, which let editors working on the
+//! same document see each other's changes without reloading.
+
+use async_graphql::Context;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+/// Something that changed within a document.
+///
+/// Events only carry IDs so that they're small enough to send through
+/// channels like Postgres `NOTIFY`. Subscribers load the current state of
+/// whatever changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DocumentEvent {
+    /// A word was edited
+    #[serde(rename_all = "camelCase")]
+    WordUpdated {
+        /// Document containing the word
+        document_id: Uuid,
+        /// The edited word
+        word_id: Uuid,
+    },
+    /// A paragraph was edited
+    #[serde(rename_all = "camelCase")]
+    ParagraphUpdated {
+        /// Document containing the paragraph
+        document_id: Uuid,
+        /// The edited paragraph
+        paragraph_id: Uuid,
+    },
+    /// Someone commented on a word or paragraph
+    #[serde(rename_all = "camelCase")]
+    CommentPosted {
+        /// Document containing the word or paragraph commented on
+        document_id: Uuid,
+        /// The new comment
+        comment_id: Uuid,
+    },
+    /// Pages, paragraphs or words were added, removed, split or merged
+    #[serde(rename_all = "camelCase")]
+    DocumentRestructured {
+        /// The restructured document
+        document_id: Uuid,
+    },
+}
+
+impl DocumentEvent {
+    /// The document this event happened in.
+    pub fn document_id(&self) -> Uuid {
+        match self {
+            Self::WordUpdated { document_id, .. }
+            | Self::ParagraphUpdated { document_id, .. }
+            | Self::CommentPosted { document_id, .. }
+            | Self::DocumentRestructured { document_id } => *document_id,
+        }
+    }
+}
+
+/// Delivers document events from the mutations that cause them to everyone
+/// subscribed to them.
+///
+/// Implementations other than [`LocalEventBus`] could share events between
+/// servers, for example by sending each published event with Postgres
+/// `NOTIFY` and forwarding everything received with `LISTEN` to local
+/// subscribers.
+pub trait EventBus: Send + Sync {
+    /// Send an event to all current subscribers.
+    fn publish(&self, event: DocumentEvent);
+
+    /// Receive all events published from now on.
+    fn subscribe(&self) -> BoxStream<'static, DocumentEvent>;
+}
+
+/// An [`EventBus`] which only reaches subscribers within this process.
+pub struct LocalEventBus {
+    sender: broadcast::Sender<DocumentEvent>,
+}
+
+impl LocalEventBus {
+    /// Make a new bus that holds onto at most `capacity` events for each
+    /// subscriber that falls behind.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+}
+
+impl Default for LocalEventBus {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl EventBus for LocalEventBus {
+    fn publish(&self, event: DocumentEvent) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        let _ = self.sender.send(event);
+    }
+
+    fn subscribe(&self) -> BoxStream<'static, DocumentEvent> {
+        stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    // Subscribers that fall behind skip the events they
+                    // missed rather than holding up everyone else.
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// Publish an event to the event bus in the schema data. Does nothing if the
+/// schema has no event bus, like when serving requests without subscriptions.
+pub fn publish_event(context: &Context<'_>, event: DocumentEvent) {
+    if let Some(bus) = context.data_opt::<Arc<dyn EventBus>>() {
+        bus.publish(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn subscribers_receive_later_events() {
+        let bus = LocalEventBus::new(4);
+        let event = |n| DocumentEvent::WordUpdated {
+            document_id: Uuid::nil(),
+            word_id: Uuid::from_u128(n),
+        };
+        // Nobody is listening to this one.
+        bus.publish(event(1));
+        let mut events = bus.subscribe();
+        bus.publish(event(2));
+        bus.publish(event(3));
+        assert_eq!(events.next().await, Some(event(2)));
+        assert_eq!(events.next().await, Some(event(3)));
+    }
+}
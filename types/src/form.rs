@@ -1,8 +1,12 @@
 use crate::{
-    comment::Comment, AnnotatedDoc, AudioSlice, CherokeeOrthography, Database, Date, DocumentId,
-    MorphemeSegmentUpdate, PartsOfWord, PositionInDocument, TagId, WordSegment, WordSegmentRole,
+    comment::Comment, graphql_error, AnnotatedDoc, AudioSlice, CherokeeOrthography, DailpError,
+    Database, Date, DocumentId, EditRevision, EditTargetType, EditVersion, MorphemeSegmentUpdate,
+    PageRequest, PartsOfWord, PositionInDocument, TagId, WordConnection, WordCursor, WordSegment,
+    WordSegmentRole, WordSnapshot,
+};
+use async_graphql::{
+    connection, dataloader::DataLoader, ErrorExtensions, FieldResult, MaybeUndefined,
 };
-use async_graphql::{dataloader::DataLoader, FieldResult, MaybeUndefined};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use sqlx::types::Uuid;
@@ -61,7 +65,9 @@ impl AnnotatedForm {
     /// For example, a verb form glossed as "he catches" might have a root morpheme
     /// corresponding to "catch."
     async fn root(&self, context: &async_graphql::Context<'_>) -> FieldResult<Option<WordSegment>> {
-        let segments = self.segments(context, CherokeeOrthography::Taoc).await?;
+        let segments = self
+            .segments(context, Some(CherokeeOrthography::Taoc))
+            .await?;
         for seg in segments {
             if is_root_morpheme(&seg.gloss) {
                 return Ok(Some(seg));
@@ -70,26 +76,37 @@ impl AnnotatedForm {
         Ok(None)
     }
 
-    async fn romanized_source(&self, system: CherokeeOrthography) -> Option<Cow<'_, str>> {
-        self.simple_phonetics.as_ref().map(|phonetic| {
+    /// The source written in the given system, or the reader's preferred one
+    /// if none is given
+    async fn romanized_source(
+        &self,
+        context: &async_graphql::Context<'_>,
+        system: Option<CherokeeOrthography>,
+    ) -> FieldResult<Option<Cow<'_, str>>> {
+        let system = crate::user::preferred_orthography(context, system).await?;
+        Ok(self.simple_phonetics.as_ref().map(|phonetic| {
             if system == CherokeeOrthography::Learner {
                 crate::lexical::simple_phonetics_to_worcester(phonetic).into()
             } else {
                 phonetic.into()
             }
-        })
+        }))
     }
 
+    /// Morphemes of the word written in the given system, or the reader's
+    /// preferred one if none is given
     async fn segments(
         &self,
         context: &async_graphql::Context<'_>,
-        system: CherokeeOrthography,
+        system: Option<CherokeeOrthography>,
     ) -> FieldResult<Vec<WordSegment>> {
+        let system = crate::user::preferred_orthography(context, system).await?;
         let db = context.data::<DataLoader<Database>>()?;
         // 1. To convert to a concrete analysis, start with a list of abstract tags.
         let abstract_segments = db
             .load_one(PartsOfWord(*self.id.as_ref().unwrap()))
-            .await?
+            .await
+            .map_err(graphql_error)?
             .unwrap_or_default();
 
         // 2. Request all concrete tags that start with each abstract tag.
@@ -99,7 +116,8 @@ impl AnnotatedForm {
                     .iter()
                     .map(|seg| TagId(seg.gloss.clone(), system)),
             )
-            .await?;
+            .await
+            .map_err(graphql_error)?;
 
         // 3. Pick the longest match for each abstract segment.
         let mut concrete_segments = Vec::new();
@@ -171,29 +189,67 @@ impl AnnotatedForm {
             // Find the forms with the exact same root.
             // let similar_roots = db.morphemes(id.clone());
             // Find forms with directly linked roots.
-            let connected = db
-                .connected_forms(Some(self.position.document_id), &root.gloss)
-                .await?;
-            // let (connected, similar_roots) = futures::join!(connected, similar_roots);
-            Ok(connected
-                .into_iter()
-                // Only return other similar words.
-                .filter(|word| word.id != self.id)
-                .collect())
+            // Only return other similar words.
+            Ok(db
+                .connected_forms(
+                    Some(self.position.document_id),
+                    &root.gloss,
+                    self.id,
+                    &PageRequest::default(),
+                )
+                .await
+                .map_err(graphql_error)?)
         } else {
             Ok(Vec::new())
         }
     }
 
+    /// Other forms that share the same root, one page at a time.
+    async fn similar_forms_connection(
+        &self,
+        context: &async_graphql::Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> FieldResult<WordConnection> {
+        let root = self.root(context).await?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        connection::query(
+            after,
+            before,
+            first,
+            last,
+            |after, before, first, last| async move {
+                let page = PageRequest::new(after, before, first, last);
+                let forms = match root {
+                    Some(root) => db
+                        .connected_forms(
+                            Some(self.position.document_id),
+                            &root.gloss,
+                            self.id,
+                            &page,
+                        )
+                        .await
+                        .map_err(graphql_error)?,
+                    None => Vec::new(),
+                };
+                Ok::<_, async_graphql::Error>(page.into_connection(forms, WordCursor::of))
+            },
+        )
+        .await
+    }
+
     /// The document that contains this word.
     async fn document(
         &self,
         context: &async_graphql::Context<'_>,
     ) -> FieldResult<Option<AnnotatedDoc>> {
-        Ok(context
+        context
             .data::<DataLoader<Database>>()?
             .load_one(self.position.document_id)
-            .await?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Number of words preceding this one in the containing document
@@ -206,10 +262,20 @@ impl AnnotatedForm {
         self.position.document_id
     }
 
+    /// Current version of this word, which must be given when editing it
+    async fn version(&self, context: &async_graphql::Context<'_>) -> FieldResult<i64> {
+        let id = self.require_id()?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .load_one(EditVersion::Word(id))
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default())
+    }
+
     /// Unique identifier of this form
-    async fn id(&self) -> anyhow::Result<Uuid> {
-        self.id
-            .ok_or_else(|| anyhow::format_err!("No AnnotatedForm ID"))
+    async fn id(&self) -> FieldResult<Uuid> {
+        self.require_id()
     }
 
     /// A slices of audio associated with this word in the context of a document.
@@ -238,22 +304,54 @@ impl AnnotatedForm {
         context: &async_graphql::Context<'_>,
     ) -> FieldResult<Vec<AudioSlice>> {
         let db = context.data::<DataLoader<Database>>()?.loader();
-        Ok(db.word_contributor_audio(self.id.as_ref().unwrap()).await?)
+        db.word_contributor_audio(&self.require_id()?)
+            .await
+            .map_err(graphql_error)
     }
 
     /// Get comments on this word
     async fn comments(&self, context: &async_graphql::Context<'_>) -> FieldResult<Vec<Comment>> {
         let db = context.data::<DataLoader<Database>>()?.loader();
-        Ok(db
-            .comments_by_parent(
-                self.id.as_ref().unwrap(),
-                &crate::comment::CommentParentType::Word,
-            )
-            .await?)
+        db.comments_by_parent(
+            &self.require_id()?,
+            &crate::comment::CommentParentType::Word,
+            crate::comment::can_moderate(context),
+        )
+        .await
+        .map_err(graphql_error)
+    }
+
+    /// Annotations on this word, oldest first
+    async fn annotations(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> FieldResult<Vec<crate::annotation::Annotation>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.annotations(crate::annotation::AnnotationsOn::Word(self.require_id()?))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// Past edits to this word, newest first
+    async fn history(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> FieldResult<Vec<EditRevision<WordSnapshot>>> {
+        let id = self.require_id()?;
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        db.edit_history(EditTargetType::Word, &id)
+            .await
+            .map_err(graphql_error)
     }
 }
 
 impl AnnotatedForm {
+    /// ID of this form, which only forms that haven't been saved yet lack.
+    fn require_id(&self) -> FieldResult<Uuid> {
+        self.id
+            .ok_or_else(|| DailpError::NotFound("No AnnotatedForm ID".into()).extend())
+    }
+
     /// Look for a root morpheme in the word using crude case checks.
     pub fn find_root(&self) -> Option<&WordSegment> {
         self.segments
@@ -291,15 +389,21 @@ pub fn is_root_morpheme(s: &str) -> bool {
 
 /// A single word in an annotated document that can be edited.
 /// All fields except id are optional.
-#[derive(async_graphql::InputObject)]
+#[derive(async_graphql::InputObject, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AnnotatedFormUpdate {
     /// Unique identifier of the form
     pub id: Uuid,
+    /// Version of the word this edit is based on, from `AnnotatedForm.version`
+    pub version: i64,
     /// Possible update to source content
+    #[serde(default, skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub source: MaybeUndefined<String>,
     /// Possible update to commentary
+    #[serde(default, skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub commentary: MaybeUndefined<String>,
     /// Updated segments
+    #[serde(default, skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub segments: MaybeUndefined<Vec<MorphemeSegmentUpdate>>,
 }
 
@@ -15,8 +15,17 @@ use serde::{Deserialize, Serialize};
 /// syllabary characters, notes about the handwriting, etc. Using MongoDB
 /// comparison queries, we can request a list of all spatial annotations
 /// on the same document that lie within or around the geometry of this specific word.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    async_graphql::SimpleObject,
+    async_graphql::InputObject,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(input_name = "GeometryInput")]
 pub struct Geometry {
     x_min: Scalar,
     y_min: Scalar,
@@ -36,6 +45,10 @@ impl Geometry {
             y_max,
         }
     }
+    /// Corners of the rectangle as `(x_min, y_min, x_max, y_max)`
+    pub fn bounds(&self) -> (Scalar, Scalar, Scalar, Scalar) {
+        (self.x_min, self.y_min, self.x_max, self.y_max)
+    }
     /// Total width of the rectangle in percentage points
     pub fn width(&self) -> Scalar {
         (self.x_max - self.x_min).abs()
@@ -6,8 +6,8 @@
 //! allowing any IIIF image viewer to consume and properly display our content.
 
 use crate::{
-    annotation::{AnnotationAttachment, DocumentRegion},
-    AnnotatedDoc, Database,
+    annotation::{AnnotationAttachment, AnnotationsOn, DocumentRegion, WordAttachment},
+    AnnotatedDoc, AnnotatedForm, DailpError, Database, Geometry, PageRequest,
 };
 use futures::join;
 use futures::stream::{self, StreamExt};
@@ -34,26 +34,29 @@ pub struct Manifest {
 }
 impl Manifest {
     /// Make a IIIF manifest from the given document
-    pub async fn from_document(db: &Database, doc: AnnotatedDoc, manifest_uri: String) -> Self {
-        let page_images = doc.meta.page_images.unwrap();
-        let (
-            image_source, // , _annotations
-            words,
-        ) = {
-            // let annot_db = db.annotations();
-            join!(
-                db.image_source_by_id(page_images.source),
-                // annot_db.on_document(&doc.meta.id),
-                db.words_in_document(doc.meta.id, None, None)
-            )
-        };
-        let annotations: Vec<crate::annotation::Annotation> = Vec::new();
-        let words: Vec<_> = words.unwrap().collect();
+    pub async fn from_document(
+        db: &Database,
+        doc: AnnotatedDoc,
+        manifest_uri: String,
+    ) -> anyhow::Result<Self> {
+        let page_images = doc
+            .meta
+            .page_images
+            .ok_or_else(|| DailpError::NotFound("That document has no page images".into()))?;
+        let all_words = PageRequest::default();
+        let (image_source, annotations, words) = join!(
+            db.image_source_by_id(page_images.source),
+            db.annotations(AnnotationsOn::Document(doc.meta.id.0)),
+            db.words_in_document(doc.meta.id, None, None, &all_words)
+        );
+        let annotations = annotations?;
+        let words: Vec<_> = words?.collect();
         let words = &words;
         let annotations = &annotations;
-        let image_source = &image_source.unwrap().unwrap();
+        let image_source = &image_source?
+            .ok_or_else(|| DailpError::NotFound("That image source doesn't exist".into()))?;
         let manifest_uri = &manifest_uri;
-        Self::new(
+        Ok(Self::new(
             manifest_uri.clone(),
             doc.meta.title,
             "The Newberry Library".to_owned(),
@@ -91,35 +94,28 @@ impl Manifest {
                                     }),
                                 })
                             })
-                            .chain(annotations.iter().filter_map(
-                                |annote| match &annote.attached_to {
-                                    AnnotationAttachment::DocumentRegion(DocumentRegion {
-                                        region,
-                                        page: Some(annote_page),
-                                        ..
-                                    }) if *annote_page == page_num => Some(Annotation {
-                                        id: format!("{}/{}", annotations_uri, annote.id.0),
-                                        motivation: "commenting".to_owned(),
-                                        body: AnnotationBody::TextualBody(TextualBody {
-                                            language: "en".to_string(),
-                                            format: "text/html".to_string(),
-                                            value: annote.content.clone(),
-                                        }),
-                                        target: if let Some(region) = &region {
-                                            AnnotationTarget::Selector(TargetSelector {
-                                                id: canvas_uri.clone(),
-                                                selector: FragmentSelector {
-                                                    value: region.to_selector_string(),
-                                                },
-                                            })
-                                        } else {
-                                            AnnotationTarget::Id(canvas_uri.clone())
-                                        },
+                            .chain(annotations.iter().filter_map(|annote| {
+                                let region = region_on_page(annote, page_num, words)?;
+                                Some(Annotation {
+                                    id: format!("{}/{}", annotations_uri, annote.id),
+                                    motivation: "commenting".to_owned(),
+                                    body: AnnotationBody::TextualBody(TextualBody {
+                                        language: "en".to_string(),
+                                        format: "text/html".to_string(),
+                                        value: annote.content.clone(),
                                     }),
-
-                                    _ => None,
-                                },
-                            ))
+                                    target: if let Some(region) = region {
+                                        AnnotationTarget::Selector(TargetSelector {
+                                            id: canvas_uri.clone(),
+                                            selector: FragmentSelector {
+                                                value: region.to_selector_string(),
+                                            },
+                                        })
+                                    } else {
+                                        AnnotationTarget::Id(canvas_uri.clone())
+                                    },
+                                })
+                            }))
                             .collect(),
                         id: annotations_uri,
                     };
@@ -152,7 +148,7 @@ impl Manifest {
                 })
                 .collect()
                 .await,
-        )
+        ))
     }
 
     /// Make a IIIF manifest
@@ -181,6 +177,29 @@ impl Manifest {
     }
 }
 
+/// Where an annotation belongs on the given page, if it belongs there at all:
+/// either a region of the page image, or the whole page if `None`.
+/// Annotations on words go wherever the word is.
+fn region_on_page<'a>(
+    annote: &'a crate::annotation::Annotation,
+    page_num: u32,
+    words: &'a [AnnotatedForm],
+) -> Option<Option<&'a Geometry>> {
+    match &annote.attached_to {
+        AnnotationAttachment::DocumentRegion(DocumentRegion {
+            page: Some(page),
+            region,
+            ..
+        }) if *page == i64::from(page_num) => Some(region.as_ref()),
+        AnnotationAttachment::WordAttachment(WordAttachment { to }) => {
+            let word = words.iter().find(|word| word.id == Some(*to))?;
+            (word.position.page_number == page_num.to_string())
+                .then_some(word.position.geometry.as_ref())
+        }
+        _ => None,
+    }
+}
+
 /// Basic image information including dimensions.
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
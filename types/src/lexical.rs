@@ -1,4 +1,6 @@
-use crate::{AnnotatedForm, Database, Date, DocumentId, Geometry, WordSegment};
+use crate::{
+    graphql_error, AnnotatedForm, DailpError, Database, Date, DocumentId, Geometry, WordSegment,
+};
 use serde::{Deserialize, Serialize};
 
 /// The reference position within a document of one specific form
@@ -91,15 +93,20 @@ impl PositionInDocument {
             let doc = context
                 .data::<DataLoader<Database>>()?
                 .load_one(self.document_id)
-                .await?
+                .await
+                .map_err(graphql_error)?
                 .ok_or_else(|| {
-                    anyhow::format_err!("Document {:?} missing from database.", self.document_id)
-                })?;
+                    DailpError::NotFound(format!(
+                        "Document {:?} missing from database.",
+                        self.document_id
+                    ))
+                })
+                .map_err(graphql_error)?;
             // Only proceed if the document has some associated images.
             if let Some(imgs) = &doc.meta.page_images {
                 // Try to parse the page number as an integer.
                 // Page ranges are allowed, which is why the field is a string.
-                let page_num: usize = self.page_number.parse()?;
+                let page_num: usize = self.page_number.parse().map_err(graphql_error)?;
 
                 // Only proceed if this particular page has an associated image.
                 if let Some(img_id) = imgs.ids.get(page_num - 1) {
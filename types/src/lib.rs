@@ -19,7 +19,9 @@
 //! reference.
 
 pub mod annotation;
+pub mod api_key;
 mod audio;
+mod batch_edit;
 
 /// This module contains types related to authentication
 ///
@@ -34,15 +36,26 @@ pub mod comment;
 mod database_sql;
 mod date;
 mod document;
+mod error;
+mod events;
 mod form;
 mod geometry;
 mod gloss;
 pub mod iiif;
 mod lexical;
+pub mod metrics;
 mod morpheme;
+mod morpheme_pattern;
 pub mod page;
+mod pagination;
+pub mod permission;
 mod person;
+mod proposed_edit;
 pub mod raw;
+mod restructure;
+mod reverse_lookup;
+mod revision;
+mod search;
 pub mod sheet_result;
 mod slugs;
 mod tag;
@@ -55,17 +68,27 @@ pub use chrono;
 pub use sqlx::types::Uuid;
 
 pub use audio::*;
+pub use batch_edit::*;
 pub use cherokee::*;
 pub use collection::*;
 pub use database_sql::*;
 pub use date::*;
 pub use document::*;
+pub use error::*;
+pub use events::*;
 pub use form::*;
 pub use geometry::*;
 pub use gloss::*;
 pub use lexical::*;
 pub use morpheme::*;
+pub use morpheme_pattern::*;
+pub use pagination::*;
 pub use person::*;
+pub use proposed_edit::*;
+pub use restructure::*;
+pub use reverse_lookup::*;
+pub use revision::*;
+pub use search::*;
 pub use sheet_result::*;
 pub use slugs::*;
 pub use tag::*;
@@ -0,0 +1,223 @@
+//! Prometheus metrics for the API: how long each type of GraphQL operation
+//! takes, which resolvers fail, how many keys data loaders batch together, and
+//! how busy the database pool is.
+
+use crate::Database;
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextResolve,
+    ResolveInfo,
+};
+use async_graphql::parser::types::{ExecutableDocument, OperationType};
+use async_graphql::{Response, ServerResult, Value, Variables};
+use lazy_static::lazy_static;
+use prometheus::{
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGaugeVec, TextEncoder,
+};
+use std::sync::{Arc, Mutex};
+
+lazy_static! {
+    static ref OPERATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "dailp_graphql_operation_duration_seconds",
+        "How long each GraphQL operation takes to run, by type of operation",
+        &["operation"]
+    )
+    .unwrap();
+    static ref RESOLVER_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "dailp_graphql_resolver_errors_total",
+        "Errors returned by GraphQL resolvers",
+        &["type", "field", "code"]
+    )
+    .unwrap();
+    static ref LOADER_BATCH_SIZE: HistogramVec = register_histogram_vec!(
+        "dailp_dataloader_batch_size",
+        "How many keys each data loader batch loads at once",
+        &["key"],
+        exponential_buckets(1.0, 2.0, 10).unwrap()
+    )
+    .unwrap();
+    static ref POOL_CONNECTIONS: IntGaugeVec = register_int_gauge_vec!(
+        "dailp_database_pool_connections",
+        "Connections in the database pool: open, idle, and the most it may open",
+        &["state"]
+    )
+    .unwrap();
+}
+
+/// MIME type of the text that [`gather`] returns.
+pub const CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+/// Every metric in Prometheus text format, along with how busy the given
+/// database's pool is right now.
+pub fn gather(database: &Database) -> anyhow::Result<String> {
+    let (pool, max_connections) = database.pool();
+    POOL_CONNECTIONS
+        .with_label_values(&["open"])
+        .set(pool.size().into());
+    POOL_CONNECTIONS
+        .with_label_values(&["idle"])
+        .set(pool.num_idle() as i64);
+    POOL_CONNECTIONS
+        .with_label_values(&["max"])
+        .set(max_connections.into());
+
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// Record how many keys a data loader loads in one batch, labeled by the type
+/// of key.
+pub fn record_batch<K>(keys: &[K]) {
+    let key = std::any::type_name::<K>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+    LOADER_BATCH_SIZE
+        .with_label_values(&[key])
+        .observe(keys.len() as f64);
+}
+
+/// Records how long each GraphQL operation takes and which resolvers return
+/// errors.
+pub struct Metrics;
+
+impl ExtensionFactory for Metrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(MetricsExtension::default())
+    }
+}
+
+/// Made for each request. Operation names come from clients, so durations are
+/// labeled by the type of operation to keep the number of series fixed.
+#[derive(Default)]
+struct MetricsExtension {
+    /// Name and type of each operation in the request's document
+    operations: Mutex<Vec<(Option<String>, OperationType)>>,
+}
+
+/// Label for the type of the named operation, or "other" if the document
+/// doesn't have it.
+fn operation_label(
+    operations: &[(Option<String>, OperationType)],
+    operation_name: Option<&str>,
+) -> &'static str {
+    let operation = match operation_name {
+        Some(name) => operations
+            .iter()
+            .find(|(op_name, _)| op_name.as_deref() == Some(name)),
+        None => operations.first(),
+    };
+    match operation {
+        Some((_, OperationType::Query)) => "query",
+        Some((_, OperationType::Mutation)) => "mutation",
+        Some((_, OperationType::Subscription)) => "subscription",
+        None => "other",
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for MetricsExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        *self.operations.lock().unwrap() = document
+            .operations
+            .iter()
+            .map(|(name, op)| (name.map(|name| name.to_string()), op.node.ty))
+            .collect();
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let label = operation_label(&self.operations.lock().unwrap(), operation_name);
+        let timer = OPERATION_SECONDS.with_label_values(&[label]).start_timer();
+        let res = next.run(ctx, operation_name).await;
+        timer.observe_duration();
+        res
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let (parent_type, field) = (info.parent_type, info.name);
+        let depth = info.path_node.parents().count() + 1;
+        let res = next.run(ctx, info).await;
+        // Errors pass up through the fields above the one that failed, so
+        // only count those with this field's path.
+        if let Err(err) = &res {
+            if err.path.len() == depth {
+                let code = match err.extensions.as_ref().and_then(|e| e.get("code")) {
+                    Some(Value::String(code)) => code.as_str(),
+                    _ => "UNKNOWN",
+                };
+                RESOLVER_ERRORS
+                    .with_label_values(&[parent_type, field, code])
+                    .inc();
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DailpError;
+    use async_graphql::{EmptyMutation, EmptySubscription, ErrorExtensions, FieldResult, Schema};
+
+    struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        async fn outer(&self) -> Outer {
+            Outer
+        }
+    }
+
+    struct Outer;
+
+    #[async_graphql::Object]
+    impl Outer {
+        async fn missing(&self) -> FieldResult<i32> {
+            Err(DailpError::NotFound("Nothing here".into()).extend())
+        }
+    }
+
+    #[tokio::test]
+    async fn counts_errors_only_where_they_happen() {
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(Metrics)
+            .finish();
+        let res = schema.execute("{ outer { missing } }").await;
+        assert_eq!(res.errors.len(), 1);
+
+        let count = |labels: &[&str]| RESOLVER_ERRORS.with_label_values(labels).get();
+        assert_eq!(count(&["Outer", "missing", "NOT_FOUND"]), 1);
+        assert_eq!(count(&["Query", "outer", "NOT_FOUND"]), 0);
+    }
+
+    #[test]
+    fn labels_operations_by_type() {
+        let operations = [
+            (Some("Words".to_owned()), OperationType::Query),
+            (Some("Edit".to_owned()), OperationType::Mutation),
+        ];
+        assert_eq!(operation_label(&operations, Some("Edit")), "mutation");
+        assert_eq!(operation_label(&operations, None), "query");
+        assert_eq!(operation_label(&operations, Some("Made up")), "other");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::graphql_error;
 use crate::*;
 use async_graphql::FieldResult;
 use serde::{Deserialize, Serialize};
@@ -157,7 +158,8 @@ impl WordSegment {
                     gloss_id,
                     self.system.unwrap_or(CherokeeOrthography::Taoc),
                 ))
-                .await?)
+                .await
+                .map_err(graphql_error)?)
         } else {
             Ok(None)
         }
@@ -165,7 +167,7 @@ impl WordSegment {
 }
 
 /// A single unit of meaning and its gloss which can be edited.
-#[derive(async_graphql::InputObject)]
+#[derive(async_graphql::InputObject, Serialize, Deserialize)]
 pub struct MorphemeSegmentUpdate {
     /// Which Cherokee representation system is this segment written with?
     pub system: Option<CherokeeOrthography>,
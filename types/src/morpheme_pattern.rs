@@ -0,0 +1,330 @@
+//! A small pattern language for finding words by the sequence of morphemes in
+//! their segmentation, like "any pronominal prefix, then a root glossed 'go',
+//! then the past tense suffix".
+//!
+//! Patterns are a list of elements separated by whitespace or hyphens:
+//!
+//! - `PST` matches one segment with exactly that gloss.
+//! - `3*` matches one segment with a gloss starting with "3". An asterisk
+//!   stands for any run of characters within a gloss.
+//! - `_` matches any one segment.
+//! - `...` matches any number of segments, including none.
+//! - `(3SG.B | 3PL.B)` matches any one of the alternatives, each of which may
+//!   be a sequence of its own.
+//! - `hno@Clitic` matches a segment only if it has that [`WordSegmentRole`].
+//!   The role may be `Morpheme`, `Clitic`, or `Modifier`.
+//!
+//! By default a pattern may match anywhere within a word. Start the pattern
+//! with `^` or end it with `$` to anchor it to the start or end of the word.
+
+use crate::{AnnotatedForm, WordSegment, WordSegmentRole};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case, take_while1},
+    character::complete::{char, multispace0},
+    combinator::{all_consuming, map, not, opt, value},
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+/// A parsed pattern over the segments of a word.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MorphemePattern {
+    elements: Vec<PatternElement>,
+    anchored_start: bool,
+    anchored_end: bool,
+}
+
+/// One piece of a [`MorphemePattern`].
+#[derive(Clone, Debug, PartialEq)]
+enum PatternElement {
+    /// Exactly one segment, possibly constrained by gloss and role.
+    Segment {
+        gloss: Option<String>,
+        role: Option<WordSegmentRole>,
+    },
+    /// Any number of segments, including none.
+    AnySequence,
+    /// Any one of several alternative sequences.
+    Alternation(Vec<Vec<PatternElement>>),
+}
+
+impl MorphemePattern {
+    /// Parse a pattern from its string form, described in the module docs.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        all_consuming(delimited(multispace0, pattern, multispace0))(input)
+            .map(|(_, pattern)| pattern)
+            .map_err(|_| format!("Invalid morpheme pattern: {}", input))
+    }
+
+    /// Glosses that every match must contain, used to narrow down which words
+    /// are worth checking against the full pattern.
+    pub fn required_glosses(&self) -> Vec<String> {
+        let mut glosses: Vec<String> = self
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                PatternElement::Segment {
+                    gloss: Some(gloss), ..
+                } if !gloss.contains('*') => Some(gloss.clone()),
+                _ => None,
+            })
+            .collect();
+        glosses.sort();
+        glosses.dedup();
+        glosses
+    }
+
+    /// Find the first span of segments matching this pattern, returned as a
+    /// start index and an exclusive end index. The earliest match wins, and
+    /// among matches with the same start the longest wins.
+    pub fn find(&self, segments: &[WordSegment]) -> Option<(usize, usize)> {
+        let last_start = if self.anchored_start {
+            0
+        } else {
+            segments.len()
+        };
+        (0..=last_start).find_map(|start| {
+            match_sequence(&self.elements, segments, start)
+                .into_iter()
+                .filter(|end| !self.anchored_end || *end == segments.len())
+                .max()
+                .map(|end| (start, end))
+        })
+    }
+}
+
+/// All the positions that matching the given elements from `start` could end at.
+fn match_sequence(
+    elements: &[PatternElement],
+    segments: &[WordSegment],
+    start: usize,
+) -> Vec<usize> {
+    let mut positions = vec![start];
+    for element in elements {
+        let mut next: Vec<usize> = positions
+            .iter()
+            .flat_map(|pos| match_element(element, segments, *pos))
+            .collect();
+        next.sort_unstable();
+        next.dedup();
+        if next.is_empty() {
+            return next;
+        }
+        positions = next;
+    }
+    positions
+}
+
+fn match_element(element: &PatternElement, segments: &[WordSegment], pos: usize) -> Vec<usize> {
+    match element {
+        PatternElement::Segment { gloss, role } => match segments.get(pos) {
+            Some(segment)
+                if gloss
+                    .as_ref()
+                    .map_or(true, |g| glob_matches(g, &segment.gloss))
+                    && role.map_or(true, |r| r == segment.role) =>
+            {
+                vec![pos + 1]
+            }
+            _ => Vec::new(),
+        },
+        PatternElement::AnySequence => (pos..=segments.len()).collect(),
+        PatternElement::Alternation(options) => options
+            .iter()
+            .flat_map(|option| match_sequence(option, segments, pos))
+            .collect(),
+    }
+}
+
+/// Does the given text match the pattern, where each `*` stands for any run of
+/// characters?
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // There's always at least one part, even for an empty pattern.
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<_> = parts.collect();
+    if let Some((last, middle)) = parts.split_last() {
+        for part in middle {
+            match rest.find(part) {
+                Some(idx) => rest = &rest[idx + part.len()..],
+                None => return false,
+            }
+        }
+        rest.ends_with(last)
+    } else {
+        rest.is_empty()
+    }
+}
+
+fn pattern(input: &str) -> IResult<&str, MorphemePattern> {
+    map(
+        tuple((
+            opt(terminated(char('^'), multispace0)),
+            sequence,
+            opt(preceded(multispace0, char('$'))),
+        )),
+        |(start, elements, end)| MorphemePattern {
+            elements,
+            anchored_start: start.is_some(),
+            anchored_end: end.is_some(),
+        },
+    )(input)
+}
+
+fn sequence(input: &str) -> IResult<&str, Vec<PatternElement>> {
+    separated_list1(element_separator, element)(input)
+}
+
+fn element_separator(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        alt((
+            delimited(multispace0, tag("-"), multispace0),
+            take_while1(char::is_whitespace),
+        )),
+    )(input)
+}
+
+fn element(input: &str) -> IResult<&str, PatternElement> {
+    alt((
+        value(PatternElement::AnySequence, tag("...")),
+        map(
+            delimited(
+                pair(char('('), multispace0),
+                separated_list1(delimited(multispace0, char('|'), multispace0), sequence),
+                pair(multispace0, char(')')),
+            ),
+            PatternElement::Alternation,
+        ),
+        map(pair(segment_gloss, opt(role)), |(gloss, role)| {
+            PatternElement::Segment { gloss, role }
+        }),
+    ))(input)
+}
+
+fn segment_gloss(input: &str) -> IResult<&str, Option<String>> {
+    alt((
+        value(None, terminated(char('_'), not(gloss_chars))),
+        map(gloss_chars, |g: &str| Some(g.to_owned())),
+    ))(input)
+}
+
+fn gloss_chars(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_whitespace() && !"()|@-^$".contains(c))(input)
+}
+
+fn role(input: &str) -> IResult<&str, WordSegmentRole> {
+    preceded(
+        char('@'),
+        alt((
+            value(WordSegmentRole::Morpheme, tag_no_case("morpheme")),
+            value(WordSegmentRole::Clitic, tag_no_case("clitic")),
+            value(WordSegmentRole::Modifier, tag_no_case("modifier")),
+        )),
+    )(input)
+}
+
+/// A word whose segmentation matches a morpheme pattern.
+#[derive(async_graphql::SimpleObject)]
+pub struct MorphemePatternMatch {
+    /// The matching word
+    pub form: AnnotatedForm,
+    /// All segments of the word, in their internal glossing
+    pub segments: Vec<WordSegment>,
+    /// Index of the first segment in the matched span
+    pub match_start: i64,
+    /// Index just past the last segment in the matched span
+    pub match_end: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(glosses: &[(&str, WordSegmentRole)]) -> Vec<WordSegment> {
+        glosses
+            .iter()
+            .map(|(gloss, role)| WordSegment::new(String::new(), gloss.to_string(), Some(*role)))
+            .collect()
+    }
+
+    fn word() -> Vec<WordSegment> {
+        use WordSegmentRole::*;
+        segments(&[
+            ("DIST", Morpheme),
+            ("3SG.B", Morpheme),
+            ("go", Morpheme),
+            ("PST", Morpheme),
+            ("hno", Clitic),
+        ])
+    }
+
+    #[test]
+    fn parses_every_element() {
+        let pattern = MorphemePattern::parse("^ 3* - (go | walk) _@Clitic ... $").unwrap();
+        assert!(pattern.anchored_start);
+        assert!(pattern.anchored_end);
+        assert_eq!(pattern.elements.len(), 4);
+        assert_eq!(
+            pattern.elements[2],
+            PatternElement::Segment {
+                gloss: None,
+                role: Some(WordSegmentRole::Clitic)
+            }
+        );
+        assert!(MorphemePattern::parse("(go").is_err());
+        assert!(MorphemePattern::parse("go@nonsense").is_err());
+        assert!(MorphemePattern::parse("").is_err());
+    }
+
+    #[test]
+    fn finds_unanchored_span() {
+        let pattern = MorphemePattern::parse("3* go PST").unwrap();
+        assert_eq!(pattern.find(&word()), Some((1, 4)));
+        assert_eq!(pattern.required_glosses(), vec!["PST", "go"]);
+    }
+
+    #[test]
+    fn respects_roles_and_anchors() {
+        let word = word();
+        assert_eq!(
+            MorphemePattern::parse("PST hno@Clitic $")
+                .unwrap()
+                .find(&word),
+            Some((3, 5))
+        );
+        assert_eq!(
+            MorphemePattern::parse("PST hno@Morpheme")
+                .unwrap()
+                .find(&word),
+            None
+        );
+        assert_eq!(MorphemePattern::parse("^ go").unwrap().find(&word), None);
+        assert_eq!(
+            MorphemePattern::parse("^ ... go").unwrap().find(&word),
+            Some((0, 3))
+        );
+    }
+
+    #[test]
+    fn alternatives_may_be_sequences() {
+        let pattern = MorphemePattern::parse("(walk | 3SG.B go) _").unwrap();
+        assert_eq!(pattern.find(&word()), Some((1, 4)));
+        assert!(pattern.required_glosses().is_empty());
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_matches("3*", "3SG.B"));
+        assert!(glob_matches("*.B", "3SG.B"));
+        assert!(glob_matches("3*.*", "3SG.B"));
+        assert!(!glob_matches("3*", "DIST"));
+        assert!(glob_matches("go", "go"));
+        assert!(!glob_matches("go", "gone"));
+    }
+}
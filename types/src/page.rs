@@ -1,15 +1,48 @@
-use async_graphql::{SimpleObject, Union};
+use crate::{
+    graphql_error, AnnotatedDoc, AnnotatedForm, AudioSlice, AudioSliceById, CherokeeOrthography,
+    DailpError, Database, DateTime, DocumentId, DocumentParagraph, MorphemeTag, PagesInDocument,
+    ParagraphsInPage, TagId, WordById,
+};
+use async_graphql::{
+    dataloader::DataLoader, Context, FieldResult, InputObject, OneofObject, SimpleObject, Union,
+};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
 
 /// A website page which lives at a specific URL and has a list of blocks that
 /// define its content.
 #[derive(Clone, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
 pub struct Page {
     /// The path that this page lives at, which also uniquely identifies it.
     /// For example, "/our-team"
     pub id: String,
-    title: String,
-    body: Vec<ContentBlock>,
+    /// Title of the page
+    pub title: String,
+    /// Content of the page, in order
+    pub body: Vec<ContentBlock>,
+    /// Whether readers can see this page yet
+    pub status: PageStatus,
+    /// When this page was first published, if it has been
+    pub published_at: Option<DateTime>,
+}
+
+/// Whether a page is visible to readers. Editors can see every page.
+#[derive(
+    sqlx::Type, async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "page_status")]
+pub enum PageStatus {
+    /// Only editors can see this page
+    Draft,
+    /// Anyone can see this page
+    Published,
+}
+
+impl Default for PageStatus {
+    fn default() -> Self {
+        Self::Draft
+    }
 }
 
 /// A block of content, which may be one of several types.
@@ -24,17 +57,271 @@ pub enum ContentBlock {
     Markdown(Markdown),
     /// Gallery of images
     Gallery(Gallery),
+    /// A word from our collection, with its audio
+    WordEmbed(WordEmbed),
+    /// A range of paragraphs from a document
+    DocumentExcerpt(DocumentExcerpt),
+    /// A morpheme tag and its definition
+    GlossaryEntry(GlossaryEntry),
+    /// A player for one audio recording
+    AudioPlayer(AudioPlayer),
+}
+
+/// A block of content to save on a page, given as exactly one of the kinds of
+/// block.
+#[derive(OneofObject)]
+pub enum ContentBlockInput {
+    /// Block of markdown prose content
+    Markdown(Markdown),
+    /// Gallery of images
+    Gallery(Gallery),
+    /// A word from our collection, with its audio
+    WordEmbed(WordEmbed),
+    /// A range of paragraphs from a document
+    DocumentExcerpt(DocumentExcerpt),
+    /// A morpheme tag and its definition
+    GlossaryEntry(GlossaryEntry),
+    /// A player for one audio recording
+    AudioPlayer(AudioPlayer),
+}
+
+impl From<ContentBlockInput> for ContentBlock {
+    fn from(block: ContentBlockInput) -> Self {
+        match block {
+            ContentBlockInput::Markdown(block) => Self::Markdown(block),
+            ContentBlockInput::Gallery(block) => Self::Gallery(block),
+            ContentBlockInput::WordEmbed(block) => Self::WordEmbed(block),
+            ContentBlockInput::DocumentExcerpt(block) => Self::DocumentExcerpt(block),
+            ContentBlockInput::GlossaryEntry(block) => Self::GlossaryEntry(block),
+            ContentBlockInput::AudioPlayer(block) => Self::AudioPlayer(block),
+        }
+    }
 }
 
 /// A block of prose content, formatted with [Markdown](https://commonmark.org/).
-#[derive(Clone, Serialize, Deserialize, SimpleObject)]
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
+#[graphql(input_name = "MarkdownInput")]
 pub struct Markdown {
-    content: String,
+    /// Markdown source of the block
+    pub content: String,
 }
 
 /// A gallery of images, which may be rendered as a slideshow or lightbox.
-#[derive(Clone, Serialize, Deserialize, SimpleObject)]
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
 #[serde(rename_all = "camelCase")]
+#[graphql(input_name = "GalleryInput")]
 pub struct Gallery {
-    media_urls: Vec<String>,
+    /// URLs of the images, in order
+    pub media_urls: Vec<String>,
+}
+
+/// A word from our collection embedded in a page, shown with its audio.
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex, input_name = "WordEmbedInput")]
+pub struct WordEmbed {
+    /// ID of the word to show
+    pub word_id: Uuid,
+}
+
+#[async_graphql::ComplexObject]
+impl WordEmbed {
+    /// The embedded word, unless it has since been deleted
+    async fn word(&self, context: &Context<'_>) -> FieldResult<Option<AnnotatedForm>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .load_one(WordById(self.word_id))
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+/// A range of paragraphs from a document, shown with their translations.
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex, input_name = "DocumentExcerptInput")]
+pub struct DocumentExcerpt {
+    /// ID of the document to quote
+    pub document_id: Uuid,
+    /// One-indexed position of the first paragraph to show
+    pub start: i64,
+    /// One-indexed position of the last paragraph to show, or the end of the
+    /// document if not given
+    pub end: Option<i64>,
+}
+
+#[async_graphql::ComplexObject]
+impl DocumentExcerpt {
+    /// The quoted document, unless it has since been deleted
+    async fn document(&self, context: &Context<'_>) -> FieldResult<Option<AnnotatedDoc>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .load_one(DocumentId(self.document_id))
+            .await
+            .map_err(graphql_error)
+    }
+
+    /// The quoted paragraphs, in order
+    async fn paragraphs(&self, context: &Context<'_>) -> FieldResult<Vec<DocumentParagraph>> {
+        let loader = context.data::<DataLoader<Database>>()?;
+        let pages = loader
+            .load_one(PagesInDocument(self.document_id))
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default();
+        let mut paragraphs = loader
+            .load_many(pages.iter().map(|page| ParagraphsInPage(page.id)))
+            .await
+            .map_err(graphql_error)?;
+        let all_paragraphs = pages
+            .iter()
+            .filter_map(|page| paragraphs.remove(&ParagraphsInPage(page.id)))
+            .flatten();
+        Ok(excerpt(all_paragraphs, self.start, self.end).collect())
+    }
+}
+
+/// Items from `start` to `end` inclusive, counting from one.
+fn excerpt<T>(
+    items: impl Iterator<Item = T>,
+    start: i64,
+    end: Option<i64>,
+) -> impl Iterator<Item = T> {
+    let start = start.max(1);
+    let count = end.map_or(usize::MAX, |end| (end - start + 1).max(0) as usize);
+    items.skip(start as usize - 1).take(count)
+}
+
+/// A morpheme tag embedded in a page, like an entry in a glossary.
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex, input_name = "GlossaryEntryInput")]
+pub struct GlossaryEntry {
+    /// Internal gloss of the tag, like "3SG.B"
+    pub tag_id: String,
+    /// Which system to show the tag in
+    pub system: CherokeeOrthography,
+}
+
+#[async_graphql::ComplexObject]
+impl GlossaryEntry {
+    /// The tag as it is written in the chosen system, with its definition
+    async fn tag(&self, context: &Context<'_>) -> FieldResult<Option<MorphemeTag>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .load_one(TagId(self.tag_id.clone(), self.system))
+            .await
+            .map_err(graphql_error)?
+            .unwrap_or_default()
+            .into_iter()
+            .next())
+    }
+}
+
+/// A player for one audio recording, such as a word or story read aloud.
+#[derive(Clone, Serialize, Deserialize, SimpleObject, InputObject)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex, input_name = "AudioPlayerInput")]
+pub struct AudioPlayer {
+    /// ID of the audio slice to play
+    pub slice_id: Uuid,
+    /// Text shown alongside the player
+    pub caption: Option<String>,
+}
+
+#[async_graphql::ComplexObject]
+impl AudioPlayer {
+    /// The recording to play, unless it has since been deleted or kept out of
+    /// the edited collection
+    async fn audio(&self, context: &Context<'_>) -> FieldResult<Option<AudioSlice>> {
+        context
+            .data::<DataLoader<Database>>()?
+            .load_one(AudioSliceById(self.slice_id))
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+/// Make a new page
+#[derive(InputObject)]
+pub struct CreatePageInput {
+    /// The path the page will live at, like "/our-team"
+    pub id: String,
+    /// Title of the page
+    pub title: String,
+    /// Content of the page, in order
+    pub body: Vec<ContentBlockInput>,
+    /// Whether readers can see the page right away
+    #[graphql(default)]
+    pub status: PageStatus,
+}
+
+/// Change an existing page. Fields that aren't given stay the same.
+#[derive(InputObject)]
+pub struct UpdatePageInput {
+    /// The path of the page to change
+    pub id: String,
+    /// New title of the page
+    pub title: Option<String>,
+    /// New content of the page, replacing all existing blocks
+    pub body: Option<Vec<ContentBlockInput>>,
+    /// Publish the page, or make it a draft again
+    pub status: Option<PageStatus>,
+}
+
+/// Check that a page path is absolute and made of simple URL segments, like
+/// "/our-team" or "/about/funding".
+pub fn validate_page_path(path: &str) -> anyhow::Result<()> {
+    let valid_segment = |segment: &str| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    };
+    match path.strip_prefix('/') {
+        Some(rest) if rest.split('/').all(valid_segment) => Ok(()),
+        _ => Err(DailpError::Validation(format!(
+            "Page paths look like \"/our-team\", not \"{}\"",
+            path
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_paths() {
+        assert!(validate_page_path("/our-team").is_ok());
+        assert!(validate_page_path("/about/funding_2024").is_ok());
+        assert!(validate_page_path("our-team").is_err());
+        assert!(validate_page_path("/").is_err());
+        assert!(validate_page_path("/about/").is_err());
+        assert!(validate_page_path("/our team").is_err());
+    }
+
+    #[test]
+    fn excerpts_are_inclusive() {
+        let items = || 1..=10;
+        assert_eq!(excerpt(items(), 2, Some(4)).collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(excerpt(items(), 9, None).collect::<Vec<_>>(), [9, 10]);
+        assert_eq!(excerpt(items(), 0, Some(1)).collect::<Vec<_>>(), [1]);
+        assert_eq!(excerpt(items(), 5, Some(3)).count(), 0);
+    }
+
+    #[test]
+    fn blocks_are_tagged_with_their_type() {
+        let block = ContentBlock::Gallery(Gallery {
+            media_urls: vec!["https://example.com/a.jpg".to_owned()],
+        });
+        assert_eq!(
+            serde_json::to_value(block).unwrap(),
+            serde_json::json!({
+                "__typename": "Gallery",
+                "mediaUrls": ["https://example.com/a.jpg"],
+            })
+        );
+    }
 }
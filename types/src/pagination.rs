@@ -0,0 +1,199 @@
+//! Relay-style cursor pagination for long lists, like the words in a
+//! dictionary. Pages are fetched from the database by keyset, meaning each
+//! cursor holds the sort key of an item, so cursors stay valid even as items
+//! are added or removed elsewhere in the list.
+
+use async_graphql::{
+    connection::{Connection, Edge, OpaqueCursor},
+    OutputType,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A page of words, each with a [`WordCursor`].
+pub type WordConnection = Connection<OpaqueCursor<WordCursor>, crate::AnnotatedForm>;
+
+/// Number of items in a page when neither `first` nor `last` is given.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// Largest number of items that may be requested in one page.
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Which items of an ordered list to fetch, given as the arguments of a Relay
+/// connection field. The default page includes every item, which lets the
+/// older list fields share queries with their paginated versions.
+#[derive(Clone, Debug)]
+pub struct PageRequest<K> {
+    /// Only include items after the one with this key
+    pub after: Option<K>,
+    /// Only include items before the one with this key
+    pub before: Option<K>,
+    /// Take this many items from the start of the range
+    pub first: Option<usize>,
+    /// Take this many items from the end of the range
+    pub last: Option<usize>,
+}
+
+impl<K> Default for PageRequest<K> {
+    fn default() -> Self {
+        Self {
+            after: None,
+            before: None,
+            first: None,
+            last: None,
+        }
+    }
+}
+
+impl<K> PageRequest<K> {
+    /// Make a page from the arguments to a connection field, limited to
+    /// [`MAX_PAGE_SIZE`] items.
+    pub fn new(
+        after: Option<OpaqueCursor<K>>,
+        before: Option<OpaqueCursor<K>>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> Self {
+        let (first, last) = match (first, last) {
+            (None, None) => (Some(DEFAULT_PAGE_SIZE), None),
+            (first, last) => (
+                first.map(|n| n.min(MAX_PAGE_SIZE)),
+                last.map(|n| n.min(MAX_PAGE_SIZE)),
+            ),
+        };
+        Self {
+            after: after.map(|c| c.0),
+            before: before.map(|c| c.0),
+            first,
+            last,
+        }
+    }
+
+    /// True if items should be fetched from the end of the range, in reverse
+    /// order. This is only the case when paging backwards with `last`.
+    pub fn from_end(&self) -> bool {
+        self.first.is_none() && self.last.is_some()
+    }
+
+    /// Maximum number of rows to fetch, which is one more than fits on the
+    /// page so we can tell whether there are more. `None` means no limit.
+    pub fn limit(&self) -> Option<i64> {
+        self.first.or(self.last).map(|n| n as i64 + 1)
+    }
+
+    /// Build a connection from the rows fetched for this page, which must be
+    /// in reverse order if [`PageRequest::from_end`] is true.
+    pub fn into_connection<N: OutputType>(
+        self,
+        mut nodes: Vec<N>,
+        cursor: impl Fn(&N) -> K,
+    ) -> Connection<OpaqueCursor<K>, N>
+    where
+        K: Serialize + DeserializeOwned + Send + Sync,
+    {
+        let from_end = self.from_end();
+        let size = self.first.or(self.last);
+        let has_more = size.map_or(false, |n| nodes.len() > n);
+        if let Some(size) = size {
+            nodes.truncate(size);
+        }
+        if from_end {
+            nodes.reverse();
+        }
+        let mut has_previous_page = if from_end {
+            has_more
+        } else {
+            self.after.is_some()
+        };
+        let has_next_page = if from_end {
+            self.before.is_some()
+        } else {
+            has_more
+        };
+        // Given both `first` and `last`, take the end of the first page.
+        if let (Some(_), Some(last)) = (self.first, self.last) {
+            if nodes.len() > last {
+                nodes.drain(..nodes.len() - last);
+                has_previous_page = true;
+            }
+        }
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+        connection.edges = nodes
+            .into_iter()
+            .map(|node| Edge::new(OpaqueCursor(cursor(&node)), node))
+            .collect();
+        connection
+    }
+}
+
+/// Position of a word among all words, ordered by document and then by
+/// position within the document.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordCursor {
+    /// Document containing the word
+    #[serde(rename = "d")]
+    pub document_id: Uuid,
+    /// Index of the word within its document
+    #[serde(rename = "i")]
+    pub index: i64,
+    /// Database ID of the word, to break ties
+    #[serde(rename = "w")]
+    pub id: Uuid,
+}
+
+impl WordCursor {
+    /// The position of the given word.
+    pub fn of(form: &crate::AnnotatedForm) -> Self {
+        Self {
+            document_id: form.position.document_id.0,
+            index: form.position.index,
+            id: form.id.unwrap_or_default(),
+        }
+    }
+
+    /// Separate the parts of an optional cursor to pass them as query
+    /// parameters.
+    pub fn parts(cursor: &Option<Self>) -> (Option<Uuid>, Option<i64>, Option<Uuid>) {
+        match cursor {
+            Some(c) => (Some(c.document_id), Some(c.index), Some(c.id)),
+            None => (None, None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(page: PageRequest<i32>, rows: Vec<i32>) -> (Vec<i32>, bool, bool) {
+        let c = page.into_connection(rows, |n| *n);
+        (
+            c.edges.iter().map(|e| e.node).collect(),
+            c.has_previous_page,
+            c.has_next_page,
+        )
+    }
+
+    #[test]
+    fn pages_forward_and_backward() {
+        let forward = PageRequest::new(Some(OpaqueCursor(2)), None, Some(2), None);
+        assert_eq!(forward.limit(), Some(3));
+        assert_eq!(connection(forward, vec![3, 4, 5]), (vec![3, 4], true, true));
+
+        // Paging backwards fetches rows in reverse, nearest the end first.
+        let backward = PageRequest::new(None, Some(OpaqueCursor(5)), None, Some(2));
+        assert!(backward.from_end());
+        assert_eq!(connection(backward, vec![4, 3]), (vec![3, 4], false, true));
+
+        let both = PageRequest::new(None, None, Some(3), Some(2));
+        assert_eq!(connection(both, vec![1, 2, 3]), (vec![2, 3], true, false));
+    }
+
+    #[test]
+    fn limits_page_size() {
+        let page: PageRequest<i32> = PageRequest::new(None, None, None, None);
+        assert_eq!(page.limit(), Some(DEFAULT_PAGE_SIZE as i64 + 1));
+        let page: PageRequest<i32> = PageRequest::new(None, None, Some(1_000_000), None);
+        assert_eq!(page.limit(), Some(MAX_PAGE_SIZE as i64 + 1));
+        assert_eq!(PageRequest::<i32>::default().limit(), None);
+    }
+}
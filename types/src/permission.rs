@@ -0,0 +1,212 @@
+//! Roles given to users on single collections or documents, and the guard that
+//! checks them before mutations.
+
+use crate::api_key::ApiKeyAccess;
+use crate::auth::{UserGroup, UserInfo};
+use crate::{graphql_error, DailpError, Database, DateTime};
+use async_graphql::{dataloader::DataLoader, ErrorExtensions, Guard};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The kind of thing that a permission grant gives access to.
+#[derive(
+    sqlx::Type, async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "permission_scope")]
+pub enum PermissionScope {
+    /// An edited collection, including every document that one of its
+    /// chapters is about
+    EditedCollection,
+    /// A document collection and every document in it
+    DocumentCollection,
+    /// A single document
+    Document,
+}
+
+/// A role given to one user on a single collection or document, on top of the
+/// groups they belong to everywhere.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+pub struct PermissionGrant {
+    /// Unique ID of the grant
+    pub id: Uuid,
+    /// The user who was given the role
+    pub user_id: Uuid,
+    /// Name of the user who was given the role
+    pub user_name: String,
+    /// What the user may do, as if they were in this group for the target
+    pub role: UserGroup,
+    /// What kind of thing the target is
+    pub scope: PermissionScope,
+    /// ID of the collection or document that the role applies to
+    pub target_id: Uuid,
+    /// Title of the target, if it still exists
+    pub target_title: Option<String>,
+    /// The editor who gave the role
+    pub granted_by: Option<Uuid>,
+    /// When the role was given
+    pub granted_at: DateTime,
+}
+
+/// Give a user a role on one collection or document. A user has at most one
+/// role on each target, so this replaces any role they already had there.
+#[derive(async_graphql::InputObject)]
+pub struct GrantPermissionInput {
+    /// The user to give the role to
+    pub user_id: Uuid,
+    /// What the user may do
+    pub role: UserGroup,
+    /// What kind of thing the target is
+    pub scope: PermissionScope,
+    /// ID of the edited collection, document collection or document
+    pub target_id: Uuid,
+}
+
+/// The thing that a mutation changes, which decides which grants allow it.
+/// Words, paragraphs, pages, annotations and revisions fall under the document
+/// they're in, and chapters fall under their edited collection.
+#[derive(Clone, Debug)]
+pub enum PermissionTarget {
+    /// An edited collection, by slug
+    EditedCollection(String),
+    /// A chapter of an edited collection
+    Chapter(Uuid),
+    /// A document collection
+    DocumentCollection(Uuid),
+    /// A document
+    Document(Uuid),
+    /// A page of a document
+    Page(Uuid),
+    /// A paragraph of a document
+    Paragraph(Uuid),
+    /// A word in a document
+    Word(Uuid),
+    /// An annotation on a document or something in it
+    Annotation(Uuid),
+    /// A revision of a word or paragraph
+    Revision(Uuid),
+}
+
+/// Requires that the user is authenticated and either a member of the given
+/// user group or has been given that role on every target of the mutation.
+pub struct PermissionGuard {
+    group: UserGroup,
+    targets: Vec<PermissionTarget>,
+}
+
+impl PermissionGuard {
+    /// Check for the given group, or the same role on the given target.
+    pub fn new(group: UserGroup, target: PermissionTarget) -> Self {
+        Self::all(group, vec![target])
+    }
+
+    /// Check for the given group, or the same role on each of the targets.
+    /// With no targets, only members of the group pass.
+    pub fn all(group: UserGroup, targets: Vec<PermissionTarget>) -> Self {
+        Self { group, targets }
+    }
+}
+
+/// Roles of grants that let a user act in the given group. An editor role
+/// covers every other role, and requests made with an API key can only use
+/// roles that the key has.
+fn grant_roles(group: UserGroup, api_key: Option<&ApiKeyAccess>) -> Vec<UserGroup> {
+    let mut roles = vec![group];
+    if group != UserGroup::Editors {
+        roles.push(UserGroup::Editors);
+    }
+    if let Some(api_key) = api_key {
+        roles.retain(|role| api_key.groups.contains(role));
+    }
+    roles
+}
+
+#[async_trait::async_trait]
+impl Guard for PermissionGuard {
+    async fn check(&self, ctx: &async_graphql::Context<'_>) -> async_graphql::Result<()> {
+        let user = ctx.data_opt::<UserInfo>().ok_or_else(|| {
+            DailpError::Forbidden("Forbidden, user not authenticated".into()).extend()
+        })?;
+        if user.is_in(self.group) {
+            return Ok(());
+        }
+        let forbidden = |target: &PermissionTarget| {
+            DailpError::Forbidden(format!(
+                "Forbidden, user not in group '{:?}' for {:?}",
+                self.group, target
+            ))
+            .extend()
+        };
+        let first = self.targets.first().ok_or_else(|| {
+            DailpError::Forbidden(format!("Forbidden, user not in group '{:?}'", self.group))
+                .extend()
+        })?;
+        let roles = grant_roles(self.group, ctx.data_opt::<ApiKeyAccess>());
+        if roles.is_empty() {
+            return Err(forbidden(first));
+        }
+        let database = ctx.data::<DataLoader<Database>>()?.loader();
+        for target in &self.targets {
+            let allowed = database
+                .has_permission(user.id, &roles, target)
+                .await
+                .map_err(graphql_error)?;
+            if !allowed {
+                return Err(forbidden(target));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        #[graphql(
+            guard = "PermissionGuard::new(UserGroup::Contributors, PermissionTarget::Document(Uuid::default()))"
+        )]
+        async fn edit(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn api_keys_without_groups_cant_use_grants() {
+        let owner = UserInfo::new(Uuid::default(), "owner@example.com".into(), Vec::new());
+        let key = |groups| ApiKeyAccess {
+            key_id: Uuid::default(),
+            read_only: false,
+            groups,
+        };
+        // No database is given, so the guard has to refuse before it looks up
+        // the grants of the key's owner.
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let req = async_graphql::Request::new("{ edit }")
+            .data(owner)
+            .data(key(Vec::new()));
+        let res = schema.execute(req).await;
+        assert_eq!(res.errors.len(), 1);
+        let code = res.errors[0]
+            .extensions
+            .as_ref()
+            .and_then(|e| e.get("code"));
+        assert_eq!(code, Some(&async_graphql::Value::from("FORBIDDEN")));
+
+        assert_eq!(
+            grant_roles(
+                UserGroup::Contributors,
+                Some(&key(vec![UserGroup::Contributors]))
+            ),
+            [UserGroup::Contributors]
+        );
+        assert_eq!(
+            grant_roles(UserGroup::Contributors, None),
+            [UserGroup::Contributors, UserGroup::Editors]
+        );
+    }
+}
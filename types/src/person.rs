@@ -1,4 +1,8 @@
-use crate::{Database, PersonFullName};
+use crate::{
+    graphql_error, AnnotatedDoc, ContributorById, Database, Date, DateInput, DocumentId,
+    PersonFullName, Uuid,
+};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult, MaybeUndefined};
 use serde::{Deserialize, Serialize};
 
 /// An individual or organization that contributed to the creation or analysis
@@ -7,6 +11,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Serialize, Deserialize, async_graphql::SimpleObject)]
 #[graphql(complex)]
 pub struct Contributor {
+    /// ID of the contributor, if they have a record in the database
+    #[serde(default)]
+    pub id: Option<Uuid>,
     /// Full name of the contributor
     pub name: String,
     /// The role that defines most of their contributions to the associated item
@@ -16,6 +23,7 @@ impl Contributor {
     /// Create new contributor with the role "Author"
     pub fn new_author(name: String) -> Self {
         Self {
+            id: None,
             name,
             role: "Author".to_owned(),
         }
@@ -24,15 +32,19 @@ impl Contributor {
 
 #[async_graphql::ComplexObject]
 impl Contributor {
-    async fn details(
-        &self,
-        ctx: &async_graphql::Context<'_>,
-    ) -> async_graphql::FieldResult<Option<ContributorDetails>> {
-        use async_graphql::dataloader::*;
-        Ok(ctx
-            .data::<DataLoader<Database>>()?
-            .load_one(PersonFullName(self.name.clone()))
-            .await?)
+    async fn details(&self, ctx: &Context<'_>) -> FieldResult<Option<ContributorDetails>> {
+        let loader = ctx.data::<DataLoader<Database>>()?;
+        Ok(if let Some(id) = self.id {
+            loader
+                .load_one(ContributorById(id))
+                .await
+                .map_err(graphql_error)?
+        } else {
+            loader
+                .load_one(PersonFullName(self.name.clone()))
+                .await
+                .map_err(graphql_error)?
+        })
     }
 }
 
@@ -45,15 +57,186 @@ impl Contributor {
 /// each individual document, and track contributions to the archive as a whole.
 #[derive(async_graphql::SimpleObject, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[graphql(complex)]
 pub struct ContributorDetails {
-    /// Full name of this person, this exact string must be used to identify
-    /// them elsewhere, like in the attribution for a particular document.
+    /// Unique identifier of this person, which attributions refer to
+    pub id: Uuid,
+    /// Full name of this person
     pub full_name: String,
     /// Alternate name of this person, may be in a different language or writing
     /// system. Used only for descriptive purposes.
     pub alternate_name: Option<String>,
     /// The optional date that this contributor was born on.
-    pub birth_date: Option<crate::Date>,
+    pub birth_date: Option<Date>,
+    /// The optional date that this contributor died on.
+    pub death_date: Option<Date>,
+    /// Tribal nation or community that this person belongs to
+    pub affiliation: Option<String>,
+    /// Short biography of this person
+    pub biography: Option<String>,
+    /// Kinds of work this person does, like "Translator" or "Annotator"
+    pub roles: Vec<String>,
+    /// Identifier in the [Virtual International Authority File](https://viaf.org/)
+    pub viaf_id: Option<String>,
+    /// [ORCID](https://orcid.org/) of this person
+    pub orcid: Option<String>,
+    /// Identifier of this person on [Wikidata](https://www.wikidata.org/)
+    pub wikidata_id: Option<String>,
+}
+
+#[async_graphql::ComplexObject]
+impl ContributorDetails {
+    /// Every document this person worked on, and how
+    async fn documents(&self, ctx: &Context<'_>) -> FieldResult<Vec<DocumentContribution>> {
+        ctx.data::<DataLoader<Database>>()?
+            .loader()
+            .contributor_documents(self.id)
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+impl ContributorDetails {
+    /// Fill in any details this person is missing from the record of a
+    /// duplicate of them, keeping the roles of both.
+    pub fn absorb(&mut self, duplicate: ContributorDetails) {
+        fn fill<T>(field: &mut Option<T>, other: Option<T>) {
+            if field.is_none() {
+                *field = other;
+            }
+        }
+        fill(&mut self.alternate_name, duplicate.alternate_name);
+        fill(&mut self.birth_date, duplicate.birth_date);
+        fill(&mut self.death_date, duplicate.death_date);
+        fill(&mut self.affiliation, duplicate.affiliation);
+        fill(&mut self.biography, duplicate.biography);
+        fill(&mut self.viaf_id, duplicate.viaf_id);
+        fill(&mut self.orcid, duplicate.orcid);
+        fill(&mut self.wikidata_id, duplicate.wikidata_id);
+        for role in duplicate.roles {
+            if !self.roles.contains(&role) {
+                self.roles.push(role);
+            }
+        }
+    }
+
+    /// Apply the given changes to this person's details.
+    pub fn update(&mut self, update: UpdateContributorInput) {
+        fn apply<T>(field: &mut Option<T>, change: MaybeUndefined<T>) {
+            match change {
+                MaybeUndefined::Value(value) => *field = Some(value),
+                MaybeUndefined::Null => *field = None,
+                MaybeUndefined::Undefined => {}
+            }
+        }
+        if let Some(full_name) = update.full_name {
+            self.full_name = full_name;
+        }
+        apply(&mut self.alternate_name, update.alternate_name);
+        apply(
+            &mut self.birth_date,
+            update.birth_date.map_value(|d| (&d).into()),
+        );
+        apply(
+            &mut self.death_date,
+            update.death_date.map_value(|d| (&d).into()),
+        );
+        apply(&mut self.affiliation, update.affiliation);
+        apply(&mut self.biography, update.biography);
+        if let Some(roles) = update.roles {
+            self.roles = roles;
+        }
+        apply(&mut self.viaf_id, update.viaf_id);
+        apply(&mut self.orcid, update.orcid);
+        apply(&mut self.wikidata_id, update.wikidata_id);
+    }
+}
+
+/// One document that a contributor worked on.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+#[graphql(complex)]
+pub struct DocumentContribution {
+    /// How this person contributed to the document, like "Translator"
+    pub role: String,
+    /// ID of the document they worked on
+    #[graphql(skip)]
+    pub document_id: Uuid,
+}
+
+#[async_graphql::ComplexObject]
+impl DocumentContribution {
+    /// The document this person worked on
+    async fn document(&self, ctx: &Context<'_>) -> FieldResult<Option<AnnotatedDoc>> {
+        ctx.data::<DataLoader<Database>>()?
+            .load_one(DocumentId(self.document_id))
+            .await
+            .map_err(graphql_error)
+    }
+}
+
+/// Make a record for a new contributor
+#[derive(async_graphql::InputObject)]
+pub struct CreateContributorInput {
+    /// Full name of the person
+    pub full_name: String,
+    /// Alternate name of the person, which may be in a different language or
+    /// writing system
+    pub alternate_name: Option<String>,
+    /// The date that the person was born on
+    pub birth_date: Option<DateInput>,
+    /// The date that the person died on
+    pub death_date: Option<DateInput>,
+    /// Tribal nation or community that the person belongs to
+    pub affiliation: Option<String>,
+    /// Short biography of the person
+    pub biography: Option<String>,
+    /// Kinds of work the person does, like "Translator" or "Annotator"
+    #[graphql(default)]
+    pub roles: Vec<String>,
+    /// Identifier in the Virtual International Authority File
+    pub viaf_id: Option<String>,
+    /// ORCID of the person
+    pub orcid: Option<String>,
+    /// Identifier of the person on Wikidata
+    pub wikidata_id: Option<String>,
+}
+
+/// Change the details of a contributor. Fields that aren't given stay the
+/// same, and fields given as null are cleared.
+#[derive(async_graphql::InputObject)]
+pub struct UpdateContributorInput {
+    /// ID of the contributor to change
+    pub id: Uuid,
+    /// New full name of the person
+    pub full_name: Option<String>,
+    /// Alternate name of the person
+    pub alternate_name: MaybeUndefined<String>,
+    /// The date that the person was born on
+    pub birth_date: MaybeUndefined<DateInput>,
+    /// The date that the person died on
+    pub death_date: MaybeUndefined<DateInput>,
+    /// Tribal nation or community that the person belongs to
+    pub affiliation: MaybeUndefined<String>,
+    /// Short biography of the person
+    pub biography: MaybeUndefined<String>,
+    /// Kinds of work the person does, replacing any they had
+    pub roles: Option<Vec<String>>,
+    /// Identifier in the Virtual International Authority File
+    pub viaf_id: MaybeUndefined<String>,
+    /// ORCID of the person
+    pub orcid: MaybeUndefined<String>,
+    /// Identifier of the person on Wikidata
+    pub wikidata_id: MaybeUndefined<String>,
+}
+
+/// Combine duplicate records of the same person into one.
+#[derive(async_graphql::InputObject)]
+pub struct MergeContributorsInput {
+    /// ID of the contributor to keep
+    pub keep_id: Uuid,
+    /// IDs of the duplicate contributors, whose attributions move to the kept
+    /// contributor before they are deleted
+    pub duplicate_ids: Vec<Uuid>,
 }
 
 /// Attribution for a particular source, whether an institution or an individual.
@@ -66,3 +249,55 @@ pub struct SourceAttribution {
     /// URL of this source's homepage, i.e. "https://www.newberry.org/"
     pub link: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_contributors_keep_their_own_details() {
+        let person = |name: &str, roles: &[&str]| ContributorDetails {
+            id: Uuid::new_v4(),
+            full_name: name.to_owned(),
+            alternate_name: None,
+            birth_date: None,
+            death_date: None,
+            affiliation: None,
+            biography: None,
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            viaf_id: None,
+            orcid: None,
+            wikidata_id: None,
+        };
+        let mut kept = ContributorDetails {
+            affiliation: Some("Keetoowah".to_owned()),
+            ..person("Sequoyah", &["Author"])
+        };
+        kept.absorb(ContributorDetails {
+            affiliation: Some("Cherokee Nation".to_owned()),
+            orcid: Some("0000-0001".to_owned()),
+            ..person("Sequoya", &["Author", "Translator"])
+        });
+        assert_eq!(kept.full_name, "Sequoyah");
+        assert_eq!(kept.affiliation.as_deref(), Some("Keetoowah"));
+        assert_eq!(kept.orcid.as_deref(), Some("0000-0001"));
+        assert_eq!(kept.roles, ["Author", "Translator"]);
+
+        kept.update(UpdateContributorInput {
+            id: kept.id,
+            full_name: None,
+            alternate_name: MaybeUndefined::Value("ᏍᏏᏉᏯ".to_owned()),
+            birth_date: MaybeUndefined::Undefined,
+            death_date: MaybeUndefined::Undefined,
+            affiliation: MaybeUndefined::Undefined,
+            biography: MaybeUndefined::Undefined,
+            roles: None,
+            viaf_id: MaybeUndefined::Undefined,
+            orcid: MaybeUndefined::Null,
+            wikidata_id: MaybeUndefined::Undefined,
+        });
+        assert_eq!(kept.alternate_name.as_deref(), Some("ᏍᏏᏉᏯ"));
+        assert_eq!(kept.orcid, None);
+        assert_eq!(kept.affiliation.as_deref(), Some("Keetoowah"));
+    }
+}
@@ -0,0 +1,322 @@
+//! Structured edits suggested in comments. Instead of describing a change in
+//! prose for an editor to retype, a suggestion can carry the exact word or
+//! paragraph update it proposes, which an editor can then accept or reject.
+
+use crate::{
+    graphql_error, user::User, AnnotatedFormUpdate, DailpError, Database, DateTime, EditTargetType,
+    EditVersion, ParagraphSnapshot, ParagraphUpdate, WordSnapshot,
+};
+use async_graphql::{dataloader::DataLoader, FieldResult, MaybeUndefined};
+use sqlx::types::Uuid;
+
+/// Where a proposed edit is in the review process
+#[derive(sqlx::Type, async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+#[sqlx(type_name = "proposed_edit_status")]
+pub enum ProposedEditStatus {
+    /// Waiting for an editor to review it
+    Open,
+    /// Applied by an editor
+    Accepted,
+    /// Turned down by an editor
+    Rejected,
+}
+
+/// A change to a word or paragraph proposed alongside a suggestion comment.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(complex)]
+pub struct ProposedEdit {
+    /// Unique identifier of this proposed edit
+    pub id: Uuid,
+    /// The suggestion comment this edit was proposed in
+    #[graphql(skip)]
+    pub comment_id: Uuid,
+    /// Whether this edit would change a word or a paragraph
+    #[graphql(skip)]
+    pub target_type: EditTargetType,
+    /// ID of the word or paragraph this edit would change
+    pub target_id: Uuid,
+    /// The proposed update as JSON, shaped like the matching update input
+    #[graphql(skip)]
+    pub proposed_update: serde_json::Value,
+    /// Whether this edit is still waiting for review
+    pub status: ProposedEditStatus,
+    /// When an editor accepted or rejected this edit
+    pub reviewed_at: Option<DateTime>,
+    /// Who accepted or rejected this edit
+    pub reviewed_by: Option<User>,
+    /// Why the edit was rejected, if the editor gave a reason
+    pub review_note: Option<String>,
+}
+
+#[async_graphql::ComplexObject]
+impl ProposedEdit {
+    /// Version of the word or paragraph this edit was based on
+    async fn base_version(&self) -> FieldResult<i64> {
+        Ok(self.update().map_err(graphql_error)?.version())
+    }
+
+    /// Whether the word or paragraph has changed since this edit was
+    /// proposed, in which case it can't be accepted as is.
+    async fn outdated(&self, context: &async_graphql::Context<'_>) -> FieldResult<bool> {
+        let key = match self.target_type {
+            EditTargetType::Word => EditVersion::Word(self.target_id),
+            EditTargetType::Paragraph => EditVersion::Paragraph(self.target_id),
+        };
+        let current = context
+            .data::<DataLoader<Database>>()?
+            .load_one(key)
+            .await
+            .map_err(graphql_error)?;
+        Ok(current != Some(self.update().map_err(graphql_error)?.version()))
+    }
+
+    /// Fields this edit would change, compared to their current values
+    async fn changes(
+        &self,
+        context: &async_graphql::Context<'_>,
+    ) -> FieldResult<Vec<ProposedChange>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        Ok(match self.update().map_err(graphql_error)? {
+            ProposedUpdate::Word(update) => word_changes(
+                &db.snapshot(self.target_type, &self.target_id)
+                    .await
+                    .map_err(graphql_error)?,
+                &update,
+            ),
+            ProposedUpdate::Paragraph(update) => paragraph_changes(
+                &db.snapshot(self.target_type, &self.target_id)
+                    .await
+                    .map_err(graphql_error)?,
+                &update,
+            ),
+        })
+    }
+}
+
+impl ProposedEdit {
+    /// The structured update this edit proposes
+    pub fn update(&self) -> anyhow::Result<ProposedUpdate> {
+        ProposedUpdate::from_json(self.target_type, self.proposed_update.clone())
+    }
+}
+
+/// The update carried by a proposed edit
+pub enum ProposedUpdate {
+    /// Changes to a word and its segments
+    Word(AnnotatedFormUpdate),
+    /// Changes to a paragraph translation
+    Paragraph(ParagraphUpdate),
+}
+
+impl ProposedUpdate {
+    /// Read an update stored with a proposed edit of the given type.
+    pub fn from_json(
+        target_type: EditTargetType,
+        value: serde_json::Value,
+    ) -> anyhow::Result<Self> {
+        Ok(match target_type {
+            EditTargetType::Word => Self::Word(serde_json::from_value(value)?),
+            EditTargetType::Paragraph => Self::Paragraph(serde_json::from_value(value)?),
+        })
+    }
+
+    /// The update as JSON, for storing with a proposed edit
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        match self {
+            Self::Word(update) => serde_json::to_value(update),
+            Self::Paragraph(update) => serde_json::to_value(update),
+        }
+    }
+
+    /// Kind of content this update changes
+    pub fn target_type(&self) -> EditTargetType {
+        match self {
+            Self::Word(_) => EditTargetType::Word,
+            Self::Paragraph(_) => EditTargetType::Paragraph,
+        }
+    }
+
+    /// ID of the word or paragraph this update changes
+    pub fn target_id(&self) -> Uuid {
+        match self {
+            Self::Word(update) => update.id,
+            Self::Paragraph(update) => update.id,
+        }
+    }
+
+    /// Version of the content this update is based on
+    pub fn version(&self) -> i64 {
+        match self {
+            Self::Word(update) => update.version,
+            Self::Paragraph(update) => update.version,
+        }
+    }
+}
+
+/// One field a proposed edit would change
+#[derive(Clone, Debug, PartialEq, Eq, async_graphql::SimpleObject)]
+pub struct ProposedChange {
+    /// Name of the changed field, like "source" or "glosses"
+    pub field: String,
+    /// What the field is now. Segments are shown separated by dashes.
+    pub current: Option<String>,
+    /// What the field would be after accepting the edit
+    pub proposed: Option<String>,
+}
+
+/// Suggest a change to a word or paragraph, explained by a suggestion comment
+/// on it. Exactly one of `word` or `paragraph` must be given.
+#[derive(async_graphql::InputObject)]
+pub struct ProposeEditInput {
+    /// Explanation of the change, posted as a suggestion comment
+    pub text_content: String,
+    /// Update to a word
+    pub word: Option<AnnotatedFormUpdate>,
+    /// Update to a paragraph
+    pub paragraph: Option<ParagraphUpdate>,
+}
+
+impl ProposeEditInput {
+    /// Split this input into the comment text and the proposed update.
+    pub fn into_parts(self) -> anyhow::Result<(String, ProposedUpdate)> {
+        let update = match (self.word, self.paragraph) {
+            (Some(word), None) => ProposedUpdate::Word(word),
+            (None, Some(paragraph)) => ProposedUpdate::Paragraph(paragraph),
+            _ => {
+                return Err(DailpError::Validation(
+                    "Propose an edit to exactly one word or paragraph".into(),
+                )
+                .into())
+            }
+        };
+        Ok((self.text_content, update))
+    }
+}
+
+/// Compare a proposed word update against the word as it is now.
+pub fn word_changes(current: &WordSnapshot, update: &AnnotatedFormUpdate) -> Vec<ProposedChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        "source",
+        Some(current.source.clone()),
+        &update.source,
+    );
+    push_change(
+        &mut changes,
+        "commentary",
+        current.commentary.clone(),
+        &update.commentary,
+    );
+    // Empty segmentations are ignored when saving, so they change nothing.
+    if let Some(segments) = update.segments.value().filter(|s| !s.is_empty()) {
+        let join = |parts: Vec<&str>| Some(parts.join("-"));
+        let current_morphemes = join(current.segments.iter().map(|s| &*s.morpheme).collect());
+        let current_glosses = join(current.segments.iter().map(|s| &*s.gloss).collect());
+        let morphemes = join(segments.iter().map(|s| &*s.morpheme).collect());
+        let glosses = join(segments.iter().map(|s| &*s.gloss).collect());
+        if morphemes != current_morphemes {
+            changes.push(ProposedChange {
+                field: "morphemes".to_owned(),
+                current: current_morphemes,
+                proposed: morphemes,
+            });
+        }
+        if glosses != current_glosses {
+            changes.push(ProposedChange {
+                field: "glosses".to_owned(),
+                current: current_glosses,
+                proposed: glosses,
+            });
+        }
+    }
+    changes
+}
+
+/// Compare a proposed paragraph update against the paragraph as it is now.
+pub fn paragraph_changes(
+    current: &ParagraphSnapshot,
+    update: &ParagraphUpdate,
+) -> Vec<ProposedChange> {
+    let mut changes = Vec::new();
+    push_change(
+        &mut changes,
+        "translation",
+        Some(current.translation.clone()),
+        &update.translation,
+    );
+    changes
+}
+
+fn push_change(
+    changes: &mut Vec<ProposedChange>,
+    field: &str,
+    current: Option<String>,
+    proposed: &MaybeUndefined<String>,
+) {
+    let proposed = match proposed {
+        MaybeUndefined::Undefined => return,
+        MaybeUndefined::Null => None,
+        MaybeUndefined::Value(value) => Some(value.clone()),
+    };
+    if proposed != current {
+        changes.push(ProposedChange {
+            field: field.to_owned(),
+            current,
+            proposed,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CherokeeOrthography, MorphemeSegmentUpdate, SegmentSnapshot, WordSegmentRole};
+
+    #[test]
+    fn lists_changed_word_fields() {
+        let current = WordSnapshot {
+            source: "ᎠᏍᎦᏯ".to_owned(),
+            commentary: None,
+            segments: vec![SegmentSnapshot {
+                morpheme: "asgaya".to_owned(),
+                gloss: "mna".to_owned(),
+                gloss_id: Some(Uuid::nil()),
+                role: WordSegmentRole::Morpheme,
+            }],
+        };
+        let update = AnnotatedFormUpdate {
+            id: Uuid::nil(),
+            version: 0,
+            source: MaybeUndefined::Value("ᎠᏍᎦᏯ".to_owned()),
+            commentary: MaybeUndefined::Undefined,
+            segments: MaybeUndefined::Value(vec![MorphemeSegmentUpdate {
+                system: Some(CherokeeOrthography::Taoc),
+                morpheme: "asgaya".to_owned(),
+                gloss: "man".to_owned(),
+                role: WordSegmentRole::Morpheme,
+            }]),
+        };
+        assert_eq!(
+            word_changes(&current, &update),
+            vec![ProposedChange {
+                field: "glosses".to_owned(),
+                current: Some("mna".to_owned()),
+                proposed: Some("man".to_owned()),
+            }]
+        );
+    }
+
+    #[test]
+    fn stores_updates_without_undefined_fields() {
+        let update = ProposedUpdate::Paragraph(ParagraphUpdate {
+            id: Uuid::nil(),
+            version: 4,
+            translation: MaybeUndefined::Undefined,
+        });
+        let json = update.to_json().unwrap();
+        assert_eq!(json.get("translation"), None);
+        let read = ProposedUpdate::from_json(EditTargetType::Paragraph, json).unwrap();
+        assert_eq!(read.version(), 4);
+    }
+}
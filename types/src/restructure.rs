@@ -0,0 +1,604 @@
+//! Creating documents and changing their structure from within the app, rather
+//! than by importing them from spreadsheets.
+//!
+//! Which paragraph a word belongs to isn't stored directly. Words and
+//! paragraphs each cover a range of characters in their document, and a word
+//! belongs to the paragraph its range starts in. Rather than patching those
+//! ranges by hand, every structural edit loads the [`DocumentLayout`], changes
+//! it, and saves the positions it works out for every word and paragraph.
+
+use crate::DailpError;
+use crate::DateInput;
+use sqlx::types::Uuid;
+
+/// Pages of a document with the paragraphs on each page and the words in
+/// each paragraph, all in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DocumentLayout {
+    /// Pages in the document
+    pub pages: Vec<PageLayout>,
+}
+
+/// One page of a [`DocumentLayout`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageLayout {
+    /// Database ID of the page
+    pub id: Uuid,
+    /// Page number that words on this page already have, which may differ from
+    /// its position when a document starts partway through a source.
+    pub page_number: Option<String>,
+    /// Paragraphs on the page
+    pub paragraphs: Vec<ParagraphLayout>,
+}
+
+/// One paragraph of a [`DocumentLayout`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParagraphLayout {
+    /// Database ID of the paragraph
+    pub id: Uuid,
+    /// Words in the paragraph
+    pub words: Vec<WordLayout>,
+}
+
+/// One word of a [`DocumentLayout`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordLayout {
+    /// Database ID of the word
+    pub id: Uuid,
+    /// Number of characters in the source text of the word
+    pub length: i64,
+}
+
+/// One row of a layout as loaded from the database, ordered by page, then
+/// paragraph, then word.
+pub struct LayoutRow {
+    /// ID of the page
+    pub page_id: Uuid,
+    /// ID of a paragraph on the page, unless the page is empty
+    pub paragraph_id: Option<Uuid>,
+    /// ID of a word in the paragraph, unless the paragraph is empty
+    pub word_id: Option<Uuid>,
+    /// Page number of the word
+    pub page_number: Option<String>,
+    /// Length of the word source
+    pub word_length: Option<i64>,
+}
+
+/// Where one paragraph ends up after laying out a document
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParagraphPosition {
+    /// ID of the paragraph
+    pub id: Uuid,
+    /// Page the paragraph is on
+    pub page_id: Uuid,
+    /// First character in the paragraph
+    pub start: i64,
+    /// First character after the paragraph
+    pub end: i64,
+}
+
+/// Where one word ends up after laying out a document
+#[derive(Debug, PartialEq, Eq)]
+pub struct WordPosition {
+    /// ID of the word
+    pub id: Uuid,
+    /// Page the word is on
+    pub page_id: Uuid,
+    /// Page number shown for the word
+    pub page_number: String,
+    /// One-indexed position of the word in the document
+    pub index: i64,
+    /// First character of the word
+    pub start: i64,
+    /// First character after the word
+    pub end: i64,
+}
+
+impl DocumentLayout {
+    /// Build a layout from database rows, which must already be in order.
+    pub fn from_rows(rows: impl IntoIterator<Item = LayoutRow>) -> Self {
+        let mut pages: Vec<PageLayout> = Vec::new();
+        for row in rows {
+            if pages.last().map(|page| page.id) != Some(row.page_id) {
+                pages.push(PageLayout {
+                    id: row.page_id,
+                    page_number: None,
+                    paragraphs: Vec::new(),
+                });
+            }
+            let page = pages.last_mut().unwrap();
+            if page.page_number.is_none() {
+                page.page_number = row.page_number;
+            }
+            if let Some(paragraph_id) = row.paragraph_id {
+                if page.paragraphs.last().map(|p| p.id) != Some(paragraph_id) {
+                    page.paragraphs.push(ParagraphLayout {
+                        id: paragraph_id,
+                        words: Vec::new(),
+                    });
+                }
+                if let (Some(id), Some(length)) = (row.word_id, row.word_length) {
+                    let paragraph = page.paragraphs.last_mut().unwrap();
+                    paragraph.words.push(WordLayout { id, length });
+                }
+            }
+        }
+        Self { pages }
+    }
+
+    fn find_paragraph(&self, paragraph_id: Uuid) -> anyhow::Result<(usize, usize)> {
+        self.pages
+            .iter()
+            .enumerate()
+            .find_map(|(page_index, page)| {
+                let index = page.paragraphs.iter().position(|p| p.id == paragraph_id)?;
+                Some((page_index, index))
+            })
+            .ok_or_else(|| {
+                DailpError::Validation(format!("Paragraph {} isn't in this document", paragraph_id))
+                    .into()
+            })
+    }
+
+    fn find_word(&self, word_id: Uuid) -> anyhow::Result<(usize, usize, usize)> {
+        self.pages
+            .iter()
+            .enumerate()
+            .find_map(|(page_index, page)| {
+                page.paragraphs
+                    .iter()
+                    .enumerate()
+                    .find_map(|(paragraph_index, paragraph)| {
+                        let index = paragraph.words.iter().position(|w| w.id == word_id)?;
+                        Some((page_index, paragraph_index, index))
+                    })
+            })
+            .ok_or_else(|| {
+                DailpError::Validation(format!("Word {} isn't on a page of its document", word_id))
+                    .into()
+            })
+    }
+
+    /// The ID and length of a word
+    pub fn find_word_layout(&self, word_id: Uuid) -> anyhow::Result<WordLayout> {
+        let (page, paragraph, index) = self.find_word(word_id)?;
+        Ok(self.pages[page].paragraphs[paragraph].words[index])
+    }
+
+    /// The page a paragraph is on
+    pub fn page_of_paragraph(&self, paragraph_id: Uuid) -> anyhow::Result<Uuid> {
+        let (page, _) = self.find_paragraph(paragraph_id)?;
+        Ok(self.pages[page].id)
+    }
+
+    /// The paragraph a word is in
+    pub fn paragraph_of_word(&self, word_id: Uuid) -> anyhow::Result<Uuid> {
+        let (page, paragraph, _) = self.find_word(word_id)?;
+        Ok(self.pages[page].paragraphs[paragraph].id)
+    }
+
+    fn paragraph_mut(&mut self, (page, index): (usize, usize)) -> &mut ParagraphLayout {
+        &mut self.pages[page].paragraphs[index]
+    }
+
+    /// Add a word to a paragraph, right after another word in it or at the
+    /// start if no word is given.
+    pub fn insert_word(
+        &mut self,
+        paragraph_id: Uuid,
+        after_word_id: Option<Uuid>,
+        word: WordLayout,
+    ) -> anyhow::Result<()> {
+        let position = self.find_paragraph(paragraph_id)?;
+        let paragraph = self.paragraph_mut(position);
+        let index = match after_word_id {
+            Some(after) => {
+                paragraph
+                    .words
+                    .iter()
+                    .position(|w| w.id == after)
+                    .ok_or_else(|| {
+                        DailpError::Validation(format!(
+                            "Word {} isn't in paragraph {}",
+                            after, paragraph_id
+                        ))
+                    })?
+                    + 1
+            }
+            None => 0,
+        };
+        paragraph.words.insert(index, word);
+        Ok(())
+    }
+
+    /// Take a word out of its paragraph. The last word of a paragraph can't be
+    /// removed, since paragraphs are positioned by the words in them.
+    pub fn remove_word(&mut self, word_id: Uuid) -> anyhow::Result<()> {
+        let (page, paragraph, index) = self.find_word(word_id)?;
+        let paragraph = self.paragraph_mut((page, paragraph));
+        if paragraph.words.len() == 1 {
+            return Err(DailpError::Validation(
+                "This is the only word in its paragraph; merge the paragraph instead".into(),
+            )
+            .into());
+        }
+        paragraph.words.remove(index);
+        Ok(())
+    }
+
+    /// Change the length of a word whose source text changed.
+    pub fn resize_word(&mut self, word_id: Uuid, length: i64) -> anyhow::Result<()> {
+        let (page, paragraph, index) = self.find_word(word_id)?;
+        self.paragraph_mut((page, paragraph)).words[index].length = length;
+        Ok(())
+    }
+
+    /// The word after this one in the same paragraph
+    pub fn next_word(&self, word_id: Uuid) -> anyhow::Result<WordLayout> {
+        let (page, paragraph, index) = self.find_word(word_id)?;
+        self.pages[page].paragraphs[paragraph]
+            .words
+            .get(index + 1)
+            .copied()
+            .ok_or_else(|| {
+                DailpError::Validation("This is the last word in its paragraph".into()).into()
+            })
+    }
+
+    /// Add a paragraph to a page, right after another paragraph on it or at
+    /// the top if no paragraph is given.
+    pub fn insert_paragraph(
+        &mut self,
+        page_id: Uuid,
+        after_paragraph_id: Option<Uuid>,
+        paragraph: ParagraphLayout,
+    ) -> anyhow::Result<()> {
+        if paragraph.words.is_empty() {
+            return Err(
+                DailpError::Validation("A new paragraph needs at least one word".into()).into(),
+            );
+        }
+        let page = self
+            .pages
+            .iter_mut()
+            .find(|page| page.id == page_id)
+            .ok_or_else(|| {
+                DailpError::Validation(format!("Page {} isn't in this document", page_id))
+            })?;
+        let index = match after_paragraph_id {
+            Some(after) => {
+                page.paragraphs
+                    .iter()
+                    .position(|p| p.id == after)
+                    .ok_or_else(|| {
+                        DailpError::Validation(format!(
+                            "Paragraph {} isn't on page {}",
+                            after, page_id
+                        ))
+                    })?
+                    + 1
+            }
+            None => 0,
+        };
+        page.paragraphs.insert(index, paragraph);
+        Ok(())
+    }
+
+    /// Move the words of a paragraph starting with the given one into a new
+    /// paragraph that follows it.
+    pub fn split_paragraph(
+        &mut self,
+        paragraph_id: Uuid,
+        before_word_id: Uuid,
+        new_paragraph_id: Uuid,
+    ) -> anyhow::Result<()> {
+        let (page, index) = self.find_paragraph(paragraph_id)?;
+        let paragraph = self.paragraph_mut((page, index));
+        let split_at = paragraph
+            .words
+            .iter()
+            .position(|w| w.id == before_word_id)
+            .ok_or_else(|| {
+                DailpError::Validation(format!(
+                    "Word {} isn't in paragraph {}",
+                    before_word_id, paragraph_id
+                ))
+            })?;
+        if split_at == 0 {
+            return Err(DailpError::Validation(
+                "Can't split a paragraph before its first word".into(),
+            )
+            .into());
+        }
+        let words = paragraph.words.split_off(split_at);
+        self.pages[page].paragraphs.insert(
+            index + 1,
+            ParagraphLayout {
+                id: new_paragraph_id,
+                words,
+            },
+        );
+        Ok(())
+    }
+
+    /// The paragraph after this one, which may be on the next page
+    pub fn next_paragraph(&self, paragraph_id: Uuid) -> anyhow::Result<Uuid> {
+        let (page, index) = self.find_paragraph(paragraph_id)?;
+        self.pages[page]
+            .paragraphs
+            .iter()
+            .skip(index + 1)
+            .chain(self.pages[page + 1..].iter().flat_map(|p| &p.paragraphs))
+            .next()
+            .map(|p| p.id)
+            .ok_or_else(|| {
+                DailpError::Validation("This is the last paragraph in the document".into()).into()
+            })
+    }
+
+    /// Move all words of the second paragraph onto the end of the first, and
+    /// remove the second.
+    pub fn merge_paragraphs(&mut self, first_id: Uuid, second_id: Uuid) -> anyhow::Result<()> {
+        let first = self.find_paragraph(first_id)?;
+        let (page, index) = self.find_paragraph(second_id)?;
+        let second = self.pages[page].paragraphs.remove(index);
+        self.paragraph_mut(first).words.extend(second.words);
+        Ok(())
+    }
+
+    /// Work out where every paragraph and word goes, numbering characters and
+    /// words in order across the whole document. Pages without a known page
+    /// number are numbered by their position.
+    pub fn positions(&self) -> (Vec<ParagraphPosition>, Vec<WordPosition>) {
+        let mut paragraphs = Vec::new();
+        let mut words = Vec::new();
+        let mut next_char = 0;
+        for (page_index, page) in self.pages.iter().enumerate() {
+            let page_number = page
+                .page_number
+                .clone()
+                .unwrap_or_else(|| (page_index + 1).to_string());
+            for paragraph in &page.paragraphs {
+                let start = next_char;
+                for word in &paragraph.words {
+                    words.push(WordPosition {
+                        id: word.id,
+                        page_id: page.id,
+                        page_number: page_number.clone(),
+                        index: words.len() as i64 + 1,
+                        start: next_char,
+                        end: next_char + word.length,
+                    });
+                    next_char += word.length;
+                }
+                paragraphs.push(ParagraphPosition {
+                    id: paragraph.id,
+                    page_id: page.id,
+                    start,
+                    end: next_char,
+                });
+            }
+        }
+        (paragraphs, words)
+    }
+}
+
+/// Make a new, empty document
+#[derive(async_graphql::InputObject)]
+pub struct CreateDocumentInput {
+    /// Short unique name for the document, like "DF1975"
+    pub short_name: String,
+    /// Full title of the document
+    pub title: String,
+    /// ID of the document group to add the document to
+    pub group_id: Uuid,
+    /// Whether this is a reference source rather than a manuscript
+    #[graphql(default)]
+    pub is_reference: bool,
+    /// When the document was written
+    pub written_at: Option<DateInput>,
+    /// Genre of the document
+    pub genre: Option<String>,
+}
+
+/// Add a page to the end of a document
+#[derive(async_graphql::InputObject)]
+pub struct AddDocumentPageInput {
+    /// ID of the document
+    pub document_id: Uuid,
+    /// IIIF image source of the page image, if there is one
+    pub iiif_source_id: Option<Uuid>,
+    /// ID of the page image within its IIIF source
+    pub iiif_oid: Option<String>,
+}
+
+/// A word to add to a document
+#[derive(async_graphql::InputObject)]
+pub struct NewWordInput {
+    /// Source text of the word
+    pub source: String,
+    /// Romanized form of the word
+    pub simple_phonetics: Option<String>,
+    /// Phonemic form of the word
+    pub phonemic: Option<String>,
+    /// English translation of the word
+    pub english_gloss: Option<String>,
+    /// Further notes about the word
+    pub commentary: Option<String>,
+}
+
+impl NewWordInput {
+    /// Number of characters in the source text, which may not be empty.
+    pub fn length(&self) -> anyhow::Result<i64> {
+        match self.source.chars().count() {
+            0 => Err(DailpError::Validation("A word needs some source text".into()).into()),
+            length => Ok(length as i64),
+        }
+    }
+}
+
+/// Add a word to a paragraph
+#[derive(async_graphql::InputObject)]
+pub struct InsertWordInput {
+    /// ID of the paragraph to add the word to
+    pub paragraph_id: Uuid,
+    /// ID of the word the new word follows, or nothing to add it at the start
+    /// of the paragraph
+    pub after_word_id: Option<Uuid>,
+    /// The new word
+    pub word: NewWordInput,
+}
+
+/// Break one word into two. The first keeps all of its annotations, and the
+/// second has only source text.
+#[derive(async_graphql::InputObject)]
+pub struct SplitWordInput {
+    /// ID of the word to split
+    pub word_id: Uuid,
+    /// Version of the word this edit is based on
+    pub version: i64,
+    /// Number of characters of source text to keep in the first word
+    pub at: i64,
+}
+
+/// Join a word with the one after it in the same paragraph. The merged word
+/// keeps the annotations of the first word, and gains any comments and
+/// annotations on the second.
+#[derive(async_graphql::InputObject)]
+pub struct MergeWordsInput {
+    /// ID of the first word
+    pub word_id: Uuid,
+    /// Version of the first word this edit is based on
+    pub version: i64,
+    /// Version of the second word this edit is based on
+    pub next_version: i64,
+}
+
+/// Add a paragraph to a page
+#[derive(async_graphql::InputObject)]
+pub struct AddParagraphInput {
+    /// ID of the page to add the paragraph to
+    pub page_id: Uuid,
+    /// ID of the paragraph the new one follows, or nothing to add it at the
+    /// top of the page
+    pub after_paragraph_id: Option<Uuid>,
+    /// English translation of the paragraph
+    #[graphql(default)]
+    pub translation: String,
+    /// Words in the paragraph, of which there must be at least one
+    pub words: Vec<NewWordInput>,
+}
+
+/// Break one paragraph into two. The new second paragraph starts with no
+/// translation.
+#[derive(async_graphql::InputObject)]
+pub struct SplitParagraphInput {
+    /// ID of the paragraph to split
+    pub paragraph_id: Uuid,
+    /// ID of the first word to move into the new paragraph
+    pub before_word_id: Uuid,
+}
+
+/// Join a paragraph with the one after it, which may start on the next page.
+/// Their translations are joined, and comments on the second paragraph move
+/// to the merged one.
+#[derive(async_graphql::InputObject)]
+pub struct MergeParagraphsInput {
+    /// ID of the first paragraph
+    pub paragraph_id: Uuid,
+    /// Version of the first paragraph this edit is based on
+    pub version: i64,
+    /// Version of the second paragraph this edit is based on
+    pub next_version: i64,
+}
+
+/// Split source text after the given number of characters. Both parts must
+/// have at least one character.
+pub fn split_source(source: &str, at: i64) -> anyhow::Result<(String, String)> {
+    let length = source.chars().count() as i64;
+    if at <= 0 || at >= length {
+        return Err(DailpError::Validation(format!(
+            "Split a word between two of its {} characters",
+            length
+        ))
+        .into());
+    }
+    let split = source.char_indices().nth(at as usize).unwrap().0;
+    Ok((source[..split].to_owned(), source[split..].to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(n: u128) -> Uuid {
+        Uuid::from_u128(n)
+    }
+
+    fn word(n: u128, length: i64) -> WordLayout {
+        WordLayout { id: id(n), length }
+    }
+
+    fn layout() -> DocumentLayout {
+        DocumentLayout {
+            pages: vec![
+                PageLayout {
+                    id: id(1),
+                    page_number: Some("12".to_owned()),
+                    paragraphs: vec![ParagraphLayout {
+                        id: id(10),
+                        words: vec![word(100, 3), word(101, 2)],
+                    }],
+                },
+                PageLayout {
+                    id: id(2),
+                    page_number: None,
+                    paragraphs: vec![ParagraphLayout {
+                        id: id(20),
+                        words: vec![word(200, 4)],
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn numbers_words_across_pages() {
+        let (paragraphs, words) = layout().positions();
+        assert_eq!(
+            paragraphs
+                .iter()
+                .map(|p| (p.start, p.end))
+                .collect::<Vec<_>>(),
+            [(0, 5), (5, 9)]
+        );
+        let last = words.last().unwrap();
+        assert_eq!((last.index, last.start, last.end), (3, 5, 9));
+        assert_eq!(words[0].page_number, "12");
+        assert_eq!(last.page_number, "2");
+    }
+
+    #[test]
+    fn splits_and_merges_paragraphs() {
+        let mut layout = layout();
+        layout.split_paragraph(id(10), id(101), id(11)).unwrap();
+        assert!(layout.split_paragraph(id(11), id(101), id(12)).is_err());
+        assert_eq!(layout.next_paragraph(id(11)).unwrap(), id(20));
+        layout.merge_paragraphs(id(11), id(20)).unwrap();
+        let (paragraphs, words) = layout.positions();
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(words[2].page_id, id(1));
+        assert!(layout.remove_word(id(100)).is_err());
+        assert!(layout.remove_word(id(200)).is_ok());
+    }
+
+    #[test]
+    fn splits_source_between_characters() {
+        assert_eq!(
+            split_source("ᎠᏍᎦᏯ", 2).unwrap(),
+            ("ᎠᏍ".to_owned(), "ᎦᏯ".to_owned())
+        );
+        assert!(split_source("ᎠᏍᎦᏯ", 4).is_err());
+        assert!(split_source("ᎠᏍᎦᏯ", 0).is_err());
+    }
+}
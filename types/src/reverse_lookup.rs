@@ -0,0 +1,184 @@
+//! Reverse dictionary lookup, which finds Cherokee words by their English
+//! meaning. English is tokenized and stemmed by the database's full text
+//! search, and matching results are grouped by the root they're built on.
+
+use crate::AnnotatedForm;
+use itertools::Itertools;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// How much more a match on a dictionary headword counts towards the ranking
+/// of its root than a match on one of the attested forms of that root.
+pub const HEADWORD_WEIGHT: f32 = 2.0;
+
+/// Turn an English phrase into a full text query that matches any of its
+/// words, or `None` if there are no words to search for.
+pub fn english_lookup_query(english: &str) -> Option<String> {
+    let query = english
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .unique()
+        .join(" | ");
+    if query.is_empty() {
+        None
+    } else {
+        Some(query)
+    }
+}
+
+/// An entry in a dictionary for a root, including its English definitions.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq)]
+pub struct DictionaryHeadword {
+    /// Internal gloss of the root, like "walk"
+    pub gloss: String,
+    /// Romanized shape of the root
+    pub shape: Option<String>,
+    /// English translations of the headword, as given by the dictionary
+    pub definition: Option<String>,
+    /// Short name of the dictionary this headword is from
+    pub dictionary: String,
+}
+
+/// A group of results from a reverse lookup that all share the same root.
+#[derive(async_graphql::SimpleObject)]
+pub struct ReverseLookupEntry {
+    /// Headword for the shared root, if the results have a known root
+    pub headword: Option<DictionaryHeadword>,
+    /// Attested forms of the root whose English matches the lookup, best
+    /// matches first
+    pub forms: Vec<AnnotatedForm>,
+    /// How well this entry matches the lookup, where higher is better
+    pub score: f32,
+}
+
+/// One dictionary headword matching a reverse lookup.
+pub struct HeadwordMatch {
+    /// Database ID of the headword's gloss
+    pub id: Uuid,
+    /// The matching headword
+    pub headword: DictionaryHeadword,
+    /// Full text search rank of the match
+    pub rank: f32,
+}
+
+/// One attested form matching a reverse lookup.
+pub struct FormMatch<F> {
+    /// Database ID and headword of the root of this form, if known
+    pub root: Option<(Uuid, DictionaryHeadword)>,
+    /// The matching form
+    pub form: F,
+    /// Full text search rank of the match
+    pub rank: f32,
+}
+
+/// Group matching headwords and forms together by root, ordered from best to
+/// worst match. Each group is scored by its headword match, weighted by
+/// [`HEADWORD_WEIGHT`], plus its best form match. Forms without a known root
+/// each make up their own group.
+pub fn group_by_root<F>(
+    headwords: Vec<HeadwordMatch>,
+    forms: Vec<FormMatch<F>>,
+) -> Vec<(Option<DictionaryHeadword>, Vec<F>, f32)> {
+    let mut roots: HashMap<_, _> = headwords
+        .into_iter()
+        .map(|m| (m.id, (m.headword, m.rank * HEADWORD_WEIGHT, Vec::new())))
+        .collect();
+    let mut rootless = Vec::new();
+    for m in forms {
+        match m.root {
+            Some((id, headword)) => roots
+                .entry(id)
+                .or_insert_with(|| (headword, 0.0, Vec::new()))
+                .2
+                .push((m.form, m.rank)),
+            None => rootless.push((None, vec![m.form], m.rank)),
+        }
+    }
+    roots
+        .into_values()
+        .map(|(headword, headword_score, forms)| {
+            let forms: Vec<_> = forms
+                .into_iter()
+                .sorted_by(|a, b| b.1.total_cmp(&a.1))
+                .collect();
+            let best_form = forms.first().map_or(0.0, |(_, rank)| *rank);
+            (
+                Some(headword),
+                forms.into_iter().map(|(form, _)| form).collect(),
+                headword_score + best_form,
+            )
+        })
+        .chain(rootless)
+        .sorted_by(|a, b| {
+            b.2.total_cmp(&a.2).then_with(|| {
+                let gloss = |h: &Option<DictionaryHeadword>| h.as_ref().map(|h| h.gloss.clone());
+                gloss(&a.0).cmp(&gloss(&b.0))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headword(gloss: &str) -> DictionaryHeadword {
+        DictionaryHeadword {
+            gloss: gloss.to_owned(),
+            shape: None,
+            definition: None,
+            dictionary: "DF1975".to_owned(),
+        }
+    }
+
+    #[test]
+    fn query_matches_any_word() {
+        assert_eq!(
+            english_lookup_query("He's walking, walking fast!").as_deref(),
+            Some("he | s | walking | fast")
+        );
+        assert_eq!(english_lookup_query(" ?! "), None);
+    }
+
+    #[test]
+    fn headwords_outrank_forms() {
+        let walk = Uuid::from_u128(1);
+        let run = Uuid::from_u128(2);
+        let groups = group_by_root(
+            vec![HeadwordMatch {
+                id: walk,
+                headword: headword("walk"),
+                rank: 0.5,
+            }],
+            vec![
+                FormMatch {
+                    root: Some((run, headword("run"))),
+                    form: "run.1",
+                    rank: 0.8,
+                },
+                FormMatch {
+                    root: Some((walk, headword("walk"))),
+                    form: "walk.1",
+                    rank: 0.1,
+                },
+                FormMatch {
+                    root: Some((walk, headword("walk"))),
+                    form: "walk.2",
+                    rank: 0.3,
+                },
+                FormMatch {
+                    root: None,
+                    form: "other",
+                    rank: 0.2,
+                },
+            ],
+        );
+        let order: Vec<_> = groups.iter().map(|(_, forms, _)| forms.clone()).collect();
+        assert_eq!(
+            order,
+            vec![vec!["walk.2", "walk.1"], vec!["run.1"], vec!["other"]]
+        );
+        assert_eq!(groups[0].2, 0.5 * HEADWORD_WEIGHT + 0.3);
+    }
+}
@@ -0,0 +1,151 @@
+//! Edit history for words and paragraphs. Every edit records what the edited
+//! fields looked like before and after, so any change can be reviewed and
+//! undone later.
+//!
+//! Editable content also carries a version number which increases with each
+//! edit. Edits must say which version they were based on, so that two people
+//! editing the same thing at once can't silently overwrite each other.
+
+use crate::{user::User, DateTime, WordSegmentRole};
+use async_graphql::{ErrorExtensions, OutputType};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+/// The kinds of content that keep an edit history
+#[derive(sqlx::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[sqlx(type_name = "edit_target_type")]
+pub enum EditTargetType {
+    /// Edits to a word and its segments
+    Word,
+    /// Edits to a paragraph translation
+    Paragraph,
+}
+
+/// One change made to a word or paragraph.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+#[graphql(
+    concrete(name = "WordRevision", params(WordSnapshot)),
+    concrete(name = "ParagraphRevision", params(ParagraphSnapshot))
+)]
+pub struct EditRevision<S: OutputType> {
+    /// Unique identifier of this revision
+    pub id: Uuid,
+    /// When the edit was made
+    pub edited_at: DateTime,
+    /// Who made the edit, unless their account has since been deleted
+    pub edited_by: Option<User>,
+    /// Editable fields before the change
+    pub before: S,
+    /// Editable fields after the change
+    pub after: S,
+}
+
+/// The editable fields of a word at one point in time
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct WordSnapshot {
+    /// Original source text
+    pub source: String,
+    /// Further details about the annotation layers
+    pub commentary: Option<String>,
+    /// Morphemic segmentation of the word
+    pub segments: Vec<SegmentSnapshot>,
+}
+
+/// One segment of a word at one point in time
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentSnapshot {
+    /// Phonemic representation of the morpheme
+    pub morpheme: String,
+    /// Gloss of the morpheme, as stored in the database, or empty if the
+    /// segment has no gloss
+    pub gloss: String,
+    /// Database ID of the gloss, used to restore the segment exactly
+    #[graphql(skip)]
+    pub gloss_id: Option<Uuid>,
+    /// Role of the segment within the word
+    pub role: WordSegmentRole,
+}
+
+/// The editable fields of a paragraph at one point in time
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, async_graphql::SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct ParagraphSnapshot {
+    /// English translation of the whole paragraph
+    pub translation: String,
+}
+
+/// An edit rejected because the content changed since the editor loaded it.
+#[derive(Clone, Debug)]
+pub struct EditConflict {
+    /// Version of the content on the server
+    pub current_version: i64,
+    /// Editable fields on the server, shaped like the snapshots in the edit
+    /// history
+    pub current: serde_json::Value,
+}
+
+impl std::fmt::Display for EditConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Someone else changed this since you loaded it, it is now at version {}",
+            self.current_version
+        )
+    }
+}
+
+impl std::error::Error for EditConflict {}
+
+impl ErrorExtensions for EditConflict {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(self.to_string()).extend_with(|_, e| {
+            e.set("code", "EDIT_CONFLICT");
+            e.set("currentVersion", self.current_version);
+            if let Ok(current) = async_graphql::Value::from_json(self.current.clone()) {
+                e.set("current", current);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_word_snapshot_json() {
+        // Same shape as the object built in `queries/word_snapshot.sql`
+        let snapshot: WordSnapshot = serde_json::from_value(serde_json::json!({
+            "source": "ᎠᏍᎦᏯ",
+            "commentary": null,
+            "segments": [{
+                "morpheme": "asgaya",
+                "gloss": "man",
+                "glossId": Uuid::nil(),
+                "role": "Morpheme",
+            }],
+        }))
+        .unwrap();
+        assert_eq!(snapshot.commentary, None);
+        assert_eq!(snapshot.segments[0].role, WordSegmentRole::Morpheme);
+    }
+
+    #[test]
+    fn conflicts_carry_current_content() {
+        let err = anyhow::Error::new(EditConflict {
+            current_version: 3,
+            current: serde_json::json!({ "translation": "Hello" }),
+        });
+        let extensions = crate::graphql_error(err).extensions.unwrap();
+        assert_eq!(
+            extensions.get("code"),
+            Some(&async_graphql::Value::from("EDIT_CONFLICT"))
+        );
+        assert_eq!(
+            extensions.get("currentVersion"),
+            Some(&async_graphql::Value::from(3))
+        );
+    }
+}
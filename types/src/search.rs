@@ -0,0 +1,264 @@
+//! Faceted search over words and documents, which lets readers narrow a
+//! search down by collection, genre, decade, contributor, and whether audio
+//! is available.
+
+use crate::{AnnotatedForm, Date, DocumentReference};
+use chrono::Datelike;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// Narrows a search down to results matching every given facet.
+/// Facets that are omitted or empty don't restrict the results at all.
+#[derive(async_graphql::InputObject, Clone, Debug, Default)]
+pub struct SearchFilter {
+    /// Only include results from these document collections, by slug
+    pub collections: Option<Vec<String>>,
+    /// Only include results from documents of these genres
+    pub genres: Option<Vec<String>>,
+    /// Only include results from documents written in these decades, each
+    /// given as its first year, like 1830
+    pub decades: Option<Vec<i32>>,
+    /// Only include results from documents these people contributed to, by
+    /// full name
+    pub contributors: Option<Vec<String>>,
+    /// When given, only include results which do or do not have audio
+    pub has_audio: Option<bool>,
+}
+
+impl SearchFilter {
+    /// Collection slugs to filter by, empty for any collection.
+    pub fn collection_slugs(&self) -> Vec<String> {
+        self.collections.clone().unwrap_or_default()
+    }
+
+    /// Genres to filter by, empty for any genre.
+    pub fn genre_names(&self) -> Vec<String> {
+        self.genres.clone().unwrap_or_default()
+    }
+
+    /// First year of each decade to filter by, empty for any date.
+    pub fn decade_starts(&self) -> Vec<i64> {
+        self.decades
+            .iter()
+            .flatten()
+            .map(|decade| i64::from(*decade))
+            .collect()
+    }
+
+    /// Contributor names to filter by, empty for any contributor.
+    pub fn contributor_names(&self) -> Vec<String> {
+        self.contributors.clone().unwrap_or_default()
+    }
+}
+
+/// The number of search results that share one value of a facet.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct FacetCount {
+    /// Value to pass back in a [`SearchFilter`] to narrow by this facet
+    pub value: String,
+    /// Human-readable name of this facet value
+    pub label: String,
+    /// Number of results with this value
+    pub count: i64,
+}
+
+/// The number of search results from documents written in one decade.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct DecadeCount {
+    /// First year of the decade, like 1830
+    pub decade: i32,
+    /// Number of results from this decade
+    pub count: i64,
+}
+
+/// The number of search results with and without audio.
+#[derive(async_graphql::SimpleObject, Clone, Debug, Default, PartialEq, Eq)]
+pub struct AudioCount {
+    /// Number of results that have audio
+    pub with_audio: i64,
+    /// Number of results that have no audio
+    pub without_audio: i64,
+}
+
+/// Aggregate counts of search results for each value of each facet.
+/// Counts are taken after any [`SearchFilter`] has been applied.
+#[derive(async_graphql::SimpleObject, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchFacets {
+    /// Results per document collection, most results first
+    pub collections: Vec<FacetCount>,
+    /// Results per document genre, most results first
+    pub genres: Vec<FacetCount>,
+    /// Results per decade the containing document was written in, oldest first
+    pub decades: Vec<DecadeCount>,
+    /// Results per contributor to the containing document, most results first
+    pub contributors: Vec<FacetCount>,
+    /// Results with and without audio
+    pub audio: AudioCount,
+}
+
+/// The facet values of a single search result.
+#[derive(Clone, Debug)]
+pub struct FacetValues {
+    /// Slug of the collection containing the result
+    pub collection_slug: String,
+    /// Title of the collection containing the result
+    pub collection_title: String,
+    /// Genre of the containing document, if known
+    pub genre: Option<String>,
+    /// When the containing document was written, if known
+    pub date: Option<Date>,
+    /// Full names of everyone who contributed to the containing document
+    pub contributors: Vec<String>,
+    /// Whether the result has audio
+    pub has_audio: bool,
+}
+
+impl SearchFacets {
+    /// Count up the facet values of every search result.
+    pub fn tally<'a>(results: impl IntoIterator<Item = &'a FacetValues>) -> Self {
+        let mut collections = HashMap::new();
+        let mut genres = HashMap::new();
+        let mut decades = HashMap::new();
+        let mut contributors = HashMap::new();
+        let mut audio = AudioCount::default();
+        for result in results {
+            *collections
+                .entry((&result.collection_slug, &result.collection_title))
+                .or_insert(0) += 1;
+            if let Some(genre) = &result.genre {
+                *genres.entry((genre, genre)).or_insert(0) += 1;
+            }
+            if let Some(date) = &result.date {
+                *decades.entry(decade_of(date)).or_insert(0) += 1;
+            }
+            for name in result.contributors.iter().unique() {
+                *contributors.entry((name, name)).or_insert(0) += 1;
+            }
+            if result.has_audio {
+                audio.with_audio += 1;
+            } else {
+                audio.without_audio += 1;
+            }
+        }
+        Self {
+            collections: facet_counts(collections),
+            genres: facet_counts(genres),
+            decades: decades
+                .into_iter()
+                .map(|(decade, count)| DecadeCount { decade, count })
+                .sorted_by_key(|d| d.decade)
+                .collect(),
+            contributors: facet_counts(contributors),
+            audio,
+        }
+    }
+}
+
+/// The first year of the decade containing the given date.
+pub fn decade_of(date: &Date) -> i32 {
+    date.0.year().div_euclid(10) * 10
+}
+
+/// Order facet counts with the most results first, then alphabetically.
+fn facet_counts(counts: HashMap<(&String, &String), i64>) -> Vec<FacetCount> {
+    counts
+        .into_iter()
+        .map(|((value, label), count)| FacetCount {
+            value: value.clone(),
+            label: label.clone(),
+            count,
+        })
+        .sorted_by(|a, b| b.count.cmp(&a.count).then_with(|| a.label.cmp(&b.label)))
+        .collect()
+}
+
+/// Words matching a search, along with facet counts to narrow it by.
+#[derive(async_graphql::SimpleObject)]
+pub struct WordSearchResults {
+    /// Words matching the search and filter
+    pub forms: Vec<AnnotatedForm>,
+    /// Counts of the matching words by collection, genre, decade, contributor,
+    /// and audio
+    pub facets: SearchFacets,
+}
+
+/// Documents matching a search, along with facet counts to narrow it by.
+#[derive(async_graphql::SimpleObject)]
+pub struct DocumentSearchResults {
+    /// Documents matching the search and filter
+    pub documents: Vec<DocumentReference>,
+    /// Counts of the matching documents by collection, genre, decade,
+    /// contributor, and audio
+    pub facets: SearchFacets,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(collection: &str, genre: Option<&str>, year: Option<i32>, audio: bool) -> FacetValues {
+        FacetValues {
+            collection_slug: collection.to_lowercase(),
+            collection_title: collection.to_owned(),
+            genre: genre.map(str::to_owned),
+            date: year.map(|y| Date::from_ymd(y, 1, 1)),
+            contributors: vec!["Jeff Bourns".to_owned(), "Jeff Bourns".to_owned()],
+            has_audio: audio,
+        }
+    }
+
+    #[test]
+    fn decades_start_on_round_years() {
+        assert_eq!(decade_of(&Date::from_ymd(1839, 12, 31)), 1830);
+        assert_eq!(decade_of(&Date::from_ymd(1840, 1, 1)), 1840);
+    }
+
+    #[test]
+    fn tally_counts_each_facet() {
+        let hits = vec![
+            hit("Letters", Some("Letter"), Some(1835), true),
+            hit("Letters", Some("Letter"), Some(1841), false),
+            hit("Stories", None, None, false),
+        ];
+        let facets = SearchFacets::tally(&hits);
+        assert_eq!(
+            facets.collections,
+            vec![
+                FacetCount {
+                    value: "letters".to_owned(),
+                    label: "Letters".to_owned(),
+                    count: 2
+                },
+                FacetCount {
+                    value: "stories".to_owned(),
+                    label: "Stories".to_owned(),
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(facets.genres.len(), 1);
+        assert_eq!(facets.genres[0].count, 2);
+        assert_eq!(
+            facets.decades,
+            vec![
+                DecadeCount {
+                    decade: 1830,
+                    count: 1
+                },
+                DecadeCount {
+                    decade: 1840,
+                    count: 1
+                },
+            ]
+        );
+        // Duplicate attributions on one document only count once.
+        assert_eq!(facets.contributors[0].count, 3);
+        assert_eq!(
+            facets.audio,
+            AudioCount {
+                with_audio: 1,
+                without_audio: 2
+            }
+        );
+    }
+}
@@ -1,3 +1,8 @@
+use crate::{
+    auth::{UserGroup, UserInfo},
+    graphql_error, CherokeeOrthography, DailpError, Database, DateTime, UserProfileById,
+};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult, MaybeUndefined};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -20,3 +25,152 @@ pub struct User {
     /// User-facing name for this contributor/curator
     pub display_name: String,
 }
+
+/// Profile details and reading preferences of the signed-in user, which they
+/// can change themselves.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+pub struct UserProfile {
+    /// Id of the user, which must be a AWS Cognito `sub` claim
+    pub id: UserId,
+    /// User-facing name for this contributor/curator
+    pub display_name: String,
+    /// URL of an image to show next to the user's name
+    pub avatar_url: Option<String>,
+    /// How to write Cherokee words for this user when a query doesn't pick a
+    /// system
+    pub preferred_orthography: Option<CherokeeOrthography>,
+    /// Which language to show the website in
+    pub interface_language: InterfaceLanguage,
+    /// Whether to email the user when someone replies to their comments
+    pub notify_on_comment_replies: bool,
+    /// Whether to email the user when an editor reviews their proposed edits
+    pub notify_on_edit_reviews: bool,
+}
+
+/// A language that the website can be shown in.
+#[derive(
+    sqlx::Type, async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "interface_language")]
+pub enum InterfaceLanguage {
+    /// English, the default
+    English,
+    /// Cherokee
+    Cherokee,
+}
+
+/// Change the profile of the signed-in user. Fields that aren't given stay the
+/// same, and fields given as null are cleared.
+#[derive(async_graphql::InputObject)]
+pub struct UpdateProfileInput {
+    /// New name to show for the user
+    pub display_name: Option<String>,
+    /// URL of an image to show next to the user's name
+    pub avatar_url: MaybeUndefined<String>,
+    /// How to write Cherokee words when a query doesn't pick a system
+    pub preferred_orthography: MaybeUndefined<CherokeeOrthography>,
+    /// Which language to show the website in
+    pub interface_language: Option<InterfaceLanguage>,
+    /// Whether to email the user when someone replies to their comments
+    pub notify_on_comment_replies: Option<bool>,
+    /// Whether to email the user when an editor reviews their proposed edits
+    pub notify_on_edit_reviews: Option<bool>,
+}
+
+impl UpdateProfileInput {
+    /// Make sure the new display name isn't blank and the avatar is a web
+    /// address.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(name) = &self.display_name {
+            if name.trim().is_empty() {
+                return Err(DailpError::Validation("Display name can't be blank".into()).into());
+            }
+        }
+        if let MaybeUndefined::Value(url) = &self.avatar_url {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(DailpError::Validation(format!(
+                    "Avatar must be a web address, not \"{}\"",
+                    url
+                ))
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Someone who will be put in a user group when they confirm their account.
+#[derive(Clone, Debug, async_graphql::SimpleObject)]
+pub struct RoleInvitation {
+    /// Email address the person will sign up with, in lowercase
+    pub email: String,
+    /// The group to put them in
+    pub role: UserGroup,
+    /// The editor who invited them
+    pub invited_by: Option<Uuid>,
+    /// When they were invited
+    pub invited_at: DateTime,
+    /// When they confirmed their account and were put in the group
+    pub accepted_at: Option<DateTime>,
+}
+
+/// Invite someone to a user group, or change the group they were invited to.
+#[derive(async_graphql::InputObject)]
+pub struct InviteUserInput {
+    /// Email address the person will sign up with
+    pub email: String,
+    /// The group to put them in
+    pub role: UserGroup,
+}
+
+impl InviteUserInput {
+    /// Make sure the email looks like an email address.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let email = self.email.trim();
+        match email.split_once('@') {
+            Some((name, domain)) if !name.is_empty() && domain.contains('.') => Ok(()),
+            _ => {
+                Err(DailpError::Validation(format!("\"{}\" is not an email address", email)).into())
+            }
+        }
+    }
+}
+
+/// The orthography that a field should use: the one given as an argument,
+/// then the preference of the signed-in user, then TAOC.
+pub async fn preferred_orthography(
+    context: &Context<'_>,
+    system: Option<CherokeeOrthography>,
+) -> FieldResult<CherokeeOrthography> {
+    if let Some(system) = system {
+        return Ok(system);
+    }
+    let preferred = if let Some(user) = context.data_opt::<UserInfo>() {
+        context
+            .data::<DataLoader<Database>>()?
+            .load_one(UserProfileById(user.id))
+            .await
+            .map_err(graphql_error)?
+            .and_then(|profile| profile.preferred_orthography)
+    } else {
+        None
+    };
+    Ok(preferred.unwrap_or(CherokeeOrthography::Taoc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invitations_need_an_email_address() {
+        let invite = |email: &str| InviteUserInput {
+            email: email.to_owned(),
+            role: UserGroup::Contributors,
+        };
+        assert!(invite(" someone@example.com ").validate().is_ok());
+        assert!(invite("someone").validate().is_err());
+        assert!(invite("@example.com").validate().is_err());
+        assert!(invite("someone@localhost").validate().is_err());
+    }
+}
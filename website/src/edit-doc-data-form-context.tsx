@@ -47,6 +47,7 @@ export const FormProvider = (props: { children: any }) => {
       runUpdate({
         document: {
           id: values.document["id"][0][0],
+          version: values.document["version"],
           title: values.document["title"],
           writtenAt: values.document["date"]
             ? (values.document["date"] as unknown as Array<any>)[
@@ -90,6 +90,11 @@ export const EditDocPanel = (props: { document?: Dailp.AnnotatedDoc }) => {
     }
   }
 
+  // Edits are checked against the version of the document they started from.
+  useEffect(() => {
+    form.update(["document", "version"], docData.version)
+  }, [docData.version])
+
   // Use form.push to update the form state manually
   useEffect(() => {
     form.push(["document", "id"], [docData.id.toString()]) // push manually
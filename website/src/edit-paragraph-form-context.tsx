@@ -41,6 +41,7 @@ export const FormProvider = (props: { children: ReactNode }) => {
       runUpdate({
         paragraph: {
           id: values.paragraph["id"],
+          version: values.paragraph["version"],
           translation: values.paragraph["translation"],
         },
       })
@@ -60,6 +60,7 @@ export const FormProvider = (props: { children: ReactNode }) => {
         runUpdate({
           word: {
             id: values.word["id"],
+            version: values.word["version"],
             source: values.word["source"],
             commentary: values.word["commentary"],
             segments: updatedSegments,
@@ -20,8 +20,6 @@ export type Scalars = {
   Boolean: boolean
   Int: number
   Float: number
-  /** A scalar that can represent any JSON value. */
-  JSON: any
   /**
    * A UUID is a unique 128-bit number, stored as 16 octets. UUIDs are parsed as
    * Strings within GraphQL. UUIDs are used to assign unique identifiers to
@@ -35,6 +33,62 @@ export type Scalars = {
   UUID: any
 }
 
+/** Add a chapter to an edited collection */
+export type AddChapterInput = {
+  /** Content of the chapter, in order */
+  readonly body: ReadonlyArray<ContentBlockInput>
+  /** Slug of the collection to add the chapter to */
+  readonly collectionSlug: Scalars["String"]
+  /** ID of a document to show in the chapter */
+  readonly documentId: InputMaybe<Scalars["UUID"]>
+  /**
+   * Zero-indexed position among the other chapters with the same parent,
+   * or nothing to add it after all of them
+   */
+  readonly index: InputMaybe<Scalars["Int"]>
+  /**
+   * ID of the chapter to nest this one under, or nothing to add it directly
+   * to the collection
+   */
+  readonly parentId: InputMaybe<Scalars["UUID"]>
+  /** Which section of the collection the chapter is in */
+  readonly section: CollectionSection
+  /**
+   * URL slug for the chapter, unique within the collection. Made from the
+   * title if not given.
+   */
+  readonly slug: InputMaybe<Scalars["String"]>
+  /** Full title of the chapter */
+  readonly title: Scalars["String"]
+  /** ID of WordPress page with text of the chapter */
+  readonly wordpressId: InputMaybe<Scalars["Int"]>
+}
+
+/** Add a page to the end of a document */
+export type AddDocumentPageInput = {
+  /** ID of the document */
+  readonly documentId: Scalars["UUID"]
+  /** ID of the page image within its IIIF source */
+  readonly iiifOid: InputMaybe<Scalars["String"]>
+  /** IIIF image source of the page image, if there is one */
+  readonly iiifSourceId: InputMaybe<Scalars["UUID"]>
+}
+
+/** Add a paragraph to a page */
+export type AddParagraphInput = {
+  /**
+   * ID of the paragraph the new one follows, or nothing to add it at the
+   * top of the page
+   */
+  readonly afterParagraphId: InputMaybe<Scalars["UUID"]>
+  /** ID of the page to add the paragraph to */
+  readonly pageId: Scalars["UUID"]
+  /** English translation of the paragraph */
+  readonly translation: Scalars["String"]
+  /** Words in the paragraph, of which there must be at least one */
+  readonly words: ReadonlyArray<NewWordInput>
+}
+
 export type AnnotatedDoc = {
   readonly __typename?: "AnnotatedDoc"
   /** The audio recording resource for this entire document */
@@ -58,6 +112,11 @@ export type AnnotatedDoc = {
    * like line and page breaks.
    */
   readonly forms: ReadonlyArray<AnnotatedForm>
+  /**
+   * The words contained in this document one page at a time, which keeps
+   * responses small for long documents like dictionaries.
+   */
+  readonly formsConnection: AnnotatedFormConnection
   /** The genre of the document, used to group similar ones */
   readonly genre: Maybe<Scalars["String"]>
   /** Official short identifier for this document */
@@ -87,6 +146,11 @@ export type AnnotatedDoc = {
    * These words need to be corrected or reviewed further.
    */
   readonly unresolvedForms: ReadonlyArray<AnnotatedForm>
+  /**
+   * Current version of the document metadata, which must be given when
+   * editing it
+   */
+  readonly version: Scalars["Int"]
 }
 
 export type AnnotatedDocFormsArgs = {
@@ -94,6 +158,32 @@ export type AnnotatedDocFormsArgs = {
   start: InputMaybe<Scalars["Int"]>
 }
 
+export type AnnotatedDocFormsConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
+}
+
+export type AnnotatedDocConnection = {
+  readonly __typename?: "AnnotatedDocConnection"
+  /** A list of edges. */
+  readonly edges: ReadonlyArray<AnnotatedDocEdge>
+  /** A list of nodes. */
+  readonly nodes: ReadonlyArray<AnnotatedDoc>
+  /** Information to aid in pagination. */
+  readonly pageInfo: PageInfo
+}
+
+/** An edge in a connection. */
+export type AnnotatedDocEdge = {
+  readonly __typename?: "AnnotatedDocEdge"
+  /** A cursor for use in pagination */
+  readonly cursor: Scalars["String"]
+  /** The item at the end of the edge */
+  readonly node: AnnotatedDoc
+}
+
 /**
  * A single word in an annotated document.
  * One word contains several layers of interpretation, including the original
@@ -102,6 +192,8 @@ export type AnnotatedDocFormsArgs = {
  */
 export type AnnotatedForm = {
   readonly __typename?: "AnnotatedForm"
+  /** Annotations on this word, oldest first */
+  readonly annotations: ReadonlyArray<Annotation>
   /** Further details about the annotation layers, including uncertainty */
   readonly commentary: Maybe<Scalars["String"]>
   /** Get comments on this word */
@@ -120,6 +212,8 @@ export type AnnotatedForm = {
   readonly editedAudio: ReadonlyArray<AudioSlice>
   /** English gloss for the whole word */
   readonly englishGloss: ReadonlyArray<Scalars["String"]>
+  /** Past edits to this word, newest first */
+  readonly history: ReadonlyArray<WordRevision>
   /** Unique identifier of this form */
   readonly id: Scalars["UUID"]
   /** Number of words preceding this one in the containing document */
@@ -136,6 +230,10 @@ export type AnnotatedForm = {
   readonly phonemic: Maybe<Scalars["String"]>
   /** Position of the form within the context of its parent document */
   readonly position: PositionInDocument
+  /**
+   * The source written in the given system, or the reader's preferred one
+   * if none is given
+   */
   readonly romanizedSource: Maybe<Scalars["String"]>
   /**
    * The root morpheme of the word.
@@ -143,9 +241,15 @@ export type AnnotatedForm = {
    * corresponding to "catch."
    */
   readonly root: Maybe<WordSegment>
+  /**
+   * Morphemes of the word written in the given system, or the reader's
+   * preferred one if none is given
+   */
   readonly segments: ReadonlyArray<WordSegment>
   /** All other observed words with the same root morpheme as this word. */
   readonly similarForms: ReadonlyArray<AnnotatedForm>
+  /** Other forms that share the same root, one page at a time. */
+  readonly similarFormsConnection: AnnotatedFormConnection
   /** Original source text */
   readonly source: Scalars["String"]
   /**
@@ -154,6 +258,8 @@ export type AnnotatedForm = {
    * TODO! User guard for contributors only
    */
   readonly userContributedAudio: ReadonlyArray<AudioSlice>
+  /** Current version of this word, which must be given when editing it */
+  readonly version: Scalars["Int"]
 }
 
 /**
@@ -163,7 +269,7 @@ export type AnnotatedForm = {
  * TODO Split into two types, one for migration and one for SQL + GraphQL
  */
 export type AnnotatedFormRomanizedSourceArgs = {
-  system: CherokeeOrthography
+  system: InputMaybe<CherokeeOrthography>
 }
 
 /**
@@ -173,7 +279,39 @@ export type AnnotatedFormRomanizedSourceArgs = {
  * TODO Split into two types, one for migration and one for SQL + GraphQL
  */
 export type AnnotatedFormSegmentsArgs = {
-  system: CherokeeOrthography
+  system: InputMaybe<CherokeeOrthography>
+}
+
+/**
+ * A single word in an annotated document.
+ * One word contains several layers of interpretation, including the original
+ * source text, multiple layers of linguistic annotation, and annotator notes.
+ * TODO Split into two types, one for migration and one for SQL + GraphQL
+ */
+export type AnnotatedFormSimilarFormsConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
+}
+
+export type AnnotatedFormConnection = {
+  readonly __typename?: "AnnotatedFormConnection"
+  /** A list of edges. */
+  readonly edges: ReadonlyArray<AnnotatedFormEdge>
+  /** A list of nodes. */
+  readonly nodes: ReadonlyArray<AnnotatedForm>
+  /** Information to aid in pagination. */
+  readonly pageInfo: PageInfo
+}
+
+/** An edge in a connection. */
+export type AnnotatedFormEdge = {
+  readonly __typename?: "AnnotatedFormEdge"
+  /** A cursor for use in pagination */
+  readonly cursor: Scalars["String"]
+  /** The item at the end of the edge */
+  readonly node: AnnotatedForm
 }
 
 /**
@@ -189,11 +327,86 @@ export type AnnotatedFormUpdate = {
   readonly segments: InputMaybe<ReadonlyArray<MorphemeSegmentUpdate>>
   /** Possible update to source content */
   readonly source: InputMaybe<Scalars["String"]>
+  /** Version of the word this edit is based on, from `AnnotatedForm.version` */
+  readonly version: Scalars["Int"]
 }
 
 /** Element within a spreadsheet before being transformed into a full document. */
 export type AnnotatedSeg = AnnotatedForm | LineBreak
 
+/**
+ * An annotation is a piece of information that provides details about a word,
+ * document, image, slice of an image, or audio recording.
+ */
+export type Annotation = {
+  readonly __typename?: "Annotation"
+  /**
+   * What is this annotation attached to? Perhaps a word or this could be a reply to another
+   * annotation.
+   */
+  readonly attachedTo: AnnotationAttachment
+  /** The text content of this annotation */
+  readonly content: Scalars["String"]
+  /** When this annotation was written */
+  readonly createdAt: DateTime
+  /** Who wrote this annotation, unless their account has since been deleted */
+  readonly createdBy: Maybe<User>
+  /** Database ID */
+  readonly id: Scalars["UUID"]
+  /** Replies to this annotation, oldest first */
+  readonly replies: ReadonlyArray<Annotation>
+  /** When this annotation was last changed, if it has been */
+  readonly updatedAt: Maybe<DateTime>
+}
+
+/** What an annotation is attached to */
+export type AnnotationAttachment = DocumentRegion | Reply | WordAttachment
+
+/**
+ * A key that a user made for calling the API from scripts. The key itself is
+ * only shown once, when it is made.
+ */
+export type ApiKey = {
+  readonly __typename?: "ApiKey"
+  /** When the key was made */
+  readonly createdAt: DateTime
+  /** When the key stops working, if ever */
+  readonly expiresAt: Maybe<DateTime>
+  /** Groups that requests made with the key act in */
+  readonly groups: ReadonlyArray<UserGroup>
+  /** Unique ID of the key */
+  readonly id: Scalars["UUID"]
+  /** When the key was last used */
+  readonly lastUsedAt: Maybe<DateTime>
+  /** What the owner called the key */
+  readonly name: Scalars["String"]
+  /** The first few characters of the key, to tell keys apart */
+  readonly prefix: Scalars["String"]
+  /** Whether the key can only be used for queries */
+  readonly readOnly: Scalars["Boolean"]
+  /** When the owner revoked the key */
+  readonly revokedAt: Maybe<DateTime>
+  /** Most recent requests made with this key */
+  readonly usage: ReadonlyArray<ApiKeyUse>
+}
+
+/**
+ * A key that a user made for calling the API from scripts. The key itself is
+ * only shown once, when it is made.
+ */
+export type ApiKeyUsageArgs = {
+  limit?: Scalars["Int"]
+}
+
+/** One request made with an API key. */
+export type ApiKeyUse = {
+  readonly __typename?: "ApiKeyUse"
+  /** Name of the GraphQL operation, if the request gave one */
+  readonly operationName: Maybe<Scalars["String"]>
+  /** When the request was made */
+  readonly usedAt: DateTime
+}
+
 /** Request to attach user-recorded audio to a word */
 export type AttachAudioToWordInput = {
   /**
@@ -205,6 +418,37 @@ export type AttachAudioToWordInput = {
   readonly wordId: Scalars["UUID"]
 }
 
+/** The number of search results with and without audio. */
+export type AudioCount = {
+  readonly __typename?: "AudioCount"
+  /** Number of results that have audio */
+  readonly withAudio: Scalars["Int"]
+  /** Number of results that have no audio */
+  readonly withoutAudio: Scalars["Int"]
+}
+
+/** A player for one audio recording, such as a word or story read aloud. */
+export type AudioPlayer = {
+  readonly __typename?: "AudioPlayer"
+  /**
+   * The recording to play, unless it has since been deleted or kept out of
+   * the edited collection
+   */
+  readonly audio: Maybe<AudioSlice>
+  /** Text shown alongside the player */
+  readonly caption: Maybe<Scalars["String"]>
+  /** ID of the audio slice to play */
+  readonly sliceId: Scalars["UUID"]
+}
+
+/** A player for one audio recording, such as a word or story read aloud. */
+export type AudioPlayerInput = {
+  /** Text shown alongside the player */
+  readonly caption: InputMaybe<Scalars["String"]>
+  /** ID of the audio slice to play */
+  readonly sliceId: Scalars["UUID"]
+}
+
 /**
  * A segment of audio representing a document, word, phrase,
  * or other audio unit
@@ -249,12 +493,17 @@ export enum CherokeeOrthography {
 /** Structure to represent a single chapter. Used to send data to the front end. */
 export type CollectionChapter = {
   readonly __typename?: "CollectionChapter"
+  /** Content of the chapter, in order */
+  readonly body: ReadonlyArray<ContentBlock>
   /** Breadcrumbs from the top-level archive down to where this document lives. */
   readonly breadcrumbs: ReadonlyArray<DocumentCollection>
   readonly document: Maybe<AnnotatedDoc>
   /** UUID for the chapter */
   readonly id: Scalars["UUID"]
-  /** Order within the parent chapter or collection */
+  /**
+   * How deeply nested this chapter is, starting from 1 for chapters directly
+   * in the collection
+   */
   readonly indexInParent: Scalars["Int"]
   /** Full path of the chapter */
   readonly path: ReadonlyArray<Scalars["String"]>
@@ -263,7 +512,10 @@ export type CollectionChapter = {
   readonly slug: Scalars["String"]
   /** Full title of the chapter */
   readonly title: Scalars["String"]
-  /** ID of WordPress page with text of the chapter */
+  /**
+   * ID of WordPress page with text of the chapter
+   * @deprecated Use `body` instead
+   */
   readonly wordpressId: Maybe<Scalars["Int"]>
 }
 
@@ -279,21 +531,38 @@ export type Comment = {
   readonly __typename?: "Comment"
   /** An optional classification of the comment's content */
   readonly commentType: Maybe<CommentType>
+  /** When the comment was last edited, if it has been */
+  readonly editedAt: Maybe<DateTime>
+  /** When an editor hid this comment from readers, if they did */
+  readonly hiddenAt: Maybe<DateTime>
+  /** Why the comment was hidden, which only editors can see */
+  readonly hiddenReason: Maybe<Scalars["String"]>
   /** Unique identifier of this comment */
   readonly id: Scalars["UUID"]
+  /** The parent entity of this comment */
+  readonly parent: CommentParent
   /** When the comment was posted */
   readonly postedAt: DateTime
   /** Who posted the comment */
   readonly postedBy: User
+  /** The change this suggestion proposes, if it came with one */
+  readonly proposedEdit: Maybe<ProposedEdit>
+  /** Replies to this comment, oldest first */
+  readonly replies: ReadonlyArray<Comment>
+  /** When this suggestion or question was resolved, if it has been */
+  readonly resolvedAt: Maybe<DateTime>
+  /** Who resolved this suggestion or question */
+  readonly resolvedBy: Maybe<User>
   /** The text of the comment */
   readonly textContent: Scalars["String"]
 }
 
 /** Type representing the object that a comment is attached to */
-export type CommentParent = AnnotatedForm | DocumentParagraph
+export type CommentParent = AnnotatedForm | Comment | DocumentParagraph
 
 /** An enum listing the possible types that a comment could be attached to */
 export enum CommentParentType {
+  Comment = "COMMENT",
   Paragraph = "PARAGRAPH",
   Word = "WORD",
 }
@@ -312,7 +581,50 @@ export enum CommentType {
  * This type is intended to enable a custom page builder on the front-end for
  * content editors.
  */
-export type ContentBlock = Gallery | Markdown
+export type ContentBlock =
+  | AudioPlayer
+  | DocumentExcerpt
+  | Gallery
+  | GlossaryEntry
+  | Markdown
+  | WordEmbed
+
+/**
+ * A block of content to save on a page, given as exactly one of the kinds of
+ * block.
+ */
+export type ContentBlockInput = {
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly audioPlayer: InputMaybe<AudioPlayerInput>
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly documentExcerpt: InputMaybe<DocumentExcerptInput>
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly gallery: InputMaybe<GalleryInput>
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly glossaryEntry: InputMaybe<GlossaryEntryInput>
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly markdown: InputMaybe<MarkdownInput>
+  /**
+   * A block of content to save on a page, given as exactly one of the kinds of
+   * block.
+   */
+  readonly wordEmbed: InputMaybe<WordEmbedInput>
+}
 
 /**
  * An individual or organization that contributed to the creation or analysis
@@ -322,6 +634,8 @@ export type ContentBlock = Gallery | Markdown
 export type Contributor = {
   readonly __typename?: "Contributor"
   readonly details: Maybe<ContributorDetails>
+  /** ID of the contributor, if they have a record in the database */
+  readonly id: Maybe<Scalars["UUID"]>
   /** Full name of the contributor */
   readonly name: Scalars["String"]
   /** The role that defines most of their contributions to the associated item */
@@ -339,18 +653,134 @@ export type Contributor = {
  */
 export type ContributorDetails = {
   readonly __typename?: "ContributorDetails"
+  /** Tribal nation or community that this person belongs to */
+  readonly affiliation: Maybe<Scalars["String"]>
   /**
    * Alternate name of this person, may be in a different language or writing
    * system. Used only for descriptive purposes.
    */
   readonly alternateName: Maybe<Scalars["String"]>
+  /** Short biography of this person */
+  readonly biography: Maybe<Scalars["String"]>
   /** The optional date that this contributor was born on. */
   readonly birthDate: Maybe<Date>
+  /** The optional date that this contributor died on. */
+  readonly deathDate: Maybe<Date>
+  /** Every document this person worked on, and how */
+  readonly documents: ReadonlyArray<DocumentContribution>
+  /** Full name of this person */
+  readonly fullName: Scalars["String"]
+  /** Unique identifier of this person, which attributions refer to */
+  readonly id: Scalars["UUID"]
+  /** [ORCID](https://orcid.org/) of this person */
+  readonly orcid: Maybe<Scalars["String"]>
+  /** Kinds of work this person does, like "Translator" or "Annotator" */
+  readonly roles: ReadonlyArray<Scalars["String"]>
+  /** Identifier in the [Virtual International Authority File](https://viaf.org/) */
+  readonly viafId: Maybe<Scalars["String"]>
+  /** Identifier of this person on [Wikidata](https://www.wikidata.org/) */
+  readonly wikidataId: Maybe<Scalars["String"]>
+}
+
+/**
+ * Write a new annotation. Exactly one of `replyTo`, `wordId`, or
+ * `documentRegion` must be given.
+ */
+export type CreateAnnotationInput = {
+  /** The text content of the annotation */
+  readonly content: Scalars["String"]
+  /** Document, page, or image region to annotate */
+  readonly documentRegion: InputMaybe<DocumentRegionInput>
+  /** ID of the annotation to reply to */
+  readonly replyTo: InputMaybe<Scalars["UUID"]>
+  /** ID of the word to annotate */
+  readonly wordId: InputMaybe<Scalars["UUID"]>
+}
+
+/** Make an API key for the signed-in user. */
+export type CreateApiKeyInput = {
+  /**
+   * How many days until the key stops working. Keys without this work
+   * until they're revoked.
+   */
+  readonly expiresInDays: InputMaybe<Scalars["Int"]>
+  /**
+   * Groups that requests made with the key act in, which must be ones the
+   * user is in
+   */
+  readonly groups: ReadonlyArray<UserGroup>
+  /** What to call the key, like the script it's for */
+  readonly name: Scalars["String"]
+  /** Only allow queries with this key */
+  readonly readOnly: Scalars["Boolean"]
+}
+
+/** Make a record for a new contributor */
+export type CreateContributorInput = {
+  /** Tribal nation or community that the person belongs to */
+  readonly affiliation: InputMaybe<Scalars["String"]>
   /**
-   * Full name of this person, this exact string must be used to identify
-   * them elsewhere, like in the attribution for a particular document.
+   * Alternate name of the person, which may be in a different language or
+   * writing system
    */
+  readonly alternateName: InputMaybe<Scalars["String"]>
+  /** Short biography of the person */
+  readonly biography: InputMaybe<Scalars["String"]>
+  /** The date that the person was born on */
+  readonly birthDate: InputMaybe<DateInput>
+  /** The date that the person died on */
+  readonly deathDate: InputMaybe<DateInput>
+  /** Full name of the person */
   readonly fullName: Scalars["String"]
+  /** ORCID of the person */
+  readonly orcid: InputMaybe<Scalars["String"]>
+  /** Kinds of work the person does, like "Translator" or "Annotator" */
+  readonly roles: ReadonlyArray<Scalars["String"]>
+  /** Identifier in the Virtual International Authority File */
+  readonly viafId: InputMaybe<Scalars["String"]>
+  /** Identifier of the person on Wikidata */
+  readonly wikidataId: InputMaybe<Scalars["String"]>
+}
+
+/** Make a new, empty document */
+export type CreateDocumentInput = {
+  /** Genre of the document */
+  readonly genre: InputMaybe<Scalars["String"]>
+  /** ID of the document group to add the document to */
+  readonly groupId: Scalars["UUID"]
+  /** Whether this is a reference source rather than a manuscript */
+  readonly isReference: Scalars["Boolean"]
+  /** Short unique name for the document, like "DF1975" */
+  readonly shortName: Scalars["String"]
+  /** Full title of the document */
+  readonly title: Scalars["String"]
+  /** When the document was written */
+  readonly writtenAt: InputMaybe<DateInput>
+}
+
+/** Make a new edited collection with no chapters */
+export type CreateEditedCollectionInput = {
+  /**
+   * URL slug for the collection, like "cwkw". Made from the title if not
+   * given.
+   */
+  readonly slug: InputMaybe<Scalars["String"]>
+  /** Full title of the collection */
+  readonly title: Scalars["String"]
+  /** ID of WordPress menu for navigating the collection */
+  readonly wordpressMenuId: InputMaybe<Scalars["Int"]>
+}
+
+/** Make a new page */
+export type CreatePageInput = {
+  /** Content of the page, in order */
+  readonly body: ReadonlyArray<ContentBlockInput>
+  /** The path the page will live at, like "/our-team" */
+  readonly id: Scalars["String"]
+  /** Whether readers can see the page right away */
+  readonly status: PageStatus
+  /** Title of the page */
+  readonly title: Scalars["String"]
 }
 
 /** Request to update if a piece of audio should be included in an edited collection */
@@ -389,6 +819,15 @@ export type DateTime = {
   readonly timestamp: Scalars["Int"]
 }
 
+/** The number of search results from documents written in one decade. */
+export type DecadeCount = {
+  readonly __typename?: "DecadeCount"
+  /** Number of results from this decade */
+  readonly count: Scalars["Int"]
+  /** First year of the decade, like 1830 */
+  readonly decade: Scalars["Int"]
+}
+
 /** Input object for deleting an existing comment */
 export type DeleteCommentInput = {
   /** ID of the comment to delete */
@@ -401,6 +840,19 @@ export type DeleteContributorAttribution = {
   readonly documentId: Scalars["UUID"]
 }
 
+/** An entry in a dictionary for a root, including its English definitions. */
+export type DictionaryHeadword = {
+  readonly __typename?: "DictionaryHeadword"
+  /** English translations of the headword, as given by the dictionary */
+  readonly definition: Maybe<Scalars["String"]>
+  /** Short name of the dictionary this headword is from */
+  readonly dictionary: Scalars["String"]
+  /** Internal gloss of the root, like "walk" */
+  readonly gloss: Scalars["String"]
+  /** Romanized shape of the root */
+  readonly shape: Maybe<Scalars["String"]>
+}
+
 export type DocumentCollection = {
   readonly __typename?: "DocumentCollection"
   /**
@@ -416,6 +868,46 @@ export type DocumentCollection = {
   readonly slug: Scalars["String"]
 }
 
+/** One document that a contributor worked on. */
+export type DocumentContribution = {
+  readonly __typename?: "DocumentContribution"
+  /** The document this person worked on */
+  readonly document: Maybe<AnnotatedDoc>
+  /** How this person contributed to the document, like "Translator" */
+  readonly role: Scalars["String"]
+}
+
+/** A range of paragraphs from a document, shown with their translations. */
+export type DocumentExcerpt = {
+  readonly __typename?: "DocumentExcerpt"
+  /** The quoted document, unless it has since been deleted */
+  readonly document: Maybe<AnnotatedDoc>
+  /** ID of the document to quote */
+  readonly documentId: Scalars["UUID"]
+  /**
+   * One-indexed position of the last paragraph to show, or the end of the
+   * document if not given
+   */
+  readonly end: Maybe<Scalars["Int"]>
+  /** The quoted paragraphs, in order */
+  readonly paragraphs: ReadonlyArray<DocumentParagraph>
+  /** One-indexed position of the first paragraph to show */
+  readonly start: Scalars["Int"]
+}
+
+/** A range of paragraphs from a document, shown with their translations. */
+export type DocumentExcerptInput = {
+  /** ID of the document to quote */
+  readonly documentId: Scalars["UUID"]
+  /**
+   * One-indexed position of the last paragraph to show, or the end of the
+   * document if not given
+   */
+  readonly end: InputMaybe<Scalars["Int"]>
+  /** One-indexed position of the first paragraph to show */
+  readonly start: Scalars["Int"]
+}
+
 /**
  * Used for updating document metadata.
  * All fields except id are optional.
@@ -423,11 +915,18 @@ export type DocumentCollection = {
 export type DocumentMetadataUpdate = {
   readonly id: Scalars["UUID"]
   readonly title: InputMaybe<Scalars["String"]>
+  /**
+   * Version of the document this edit is based on, from
+   * `AnnotatedDoc.version`
+   */
+  readonly version: Scalars["Int"]
   readonly writtenAt: InputMaybe<DateInput>
 }
 
 export type DocumentPage = {
   readonly __typename?: "DocumentPage"
+  /** Annotations on this page or regions of its image, oldest first */
+  readonly annotations: ReadonlyArray<Annotation>
   /** Scan of this page as a IIIF resource, if there is one */
   readonly image: Maybe<PageImage>
   /** One-indexed page number */
@@ -441,6 +940,8 @@ export type DocumentParagraph = {
   readonly __typename?: "DocumentParagraph"
   /** Get comments on this paragraph */
   readonly comments: ReadonlyArray<Comment>
+  /** Past edits to this paragraph, newest first */
+  readonly history: ReadonlyArray<ParagraphRevision>
   /** Unique identifier for this paragraph */
   readonly id: Scalars["UUID"]
   /** 1-indexed position of this paragraph in a document */
@@ -449,6 +950,8 @@ export type DocumentParagraph = {
   readonly source: ReadonlyArray<AnnotatedSeg>
   /** English translation of the whole paragraph */
   readonly translation: Scalars["String"]
+  /** Current version of this paragraph, which must be given when editing it */
+  readonly version: Scalars["Int"]
 }
 
 /**
@@ -471,6 +974,45 @@ export type DocumentReference = {
   readonly title: Scalars["String"]
 }
 
+/**
+ * Attachment of an annotation on a document, one of its page images, or a
+ * region of a page image
+ */
+export type DocumentRegion = {
+  readonly __typename?: "DocumentRegion"
+  /** ID of the annotated document */
+  readonly documentId: Scalars["UUID"]
+  /** Number of the page this annotation applies to. */
+  readonly page: Maybe<Scalars["Int"]>
+  /**
+   * An image annotation without a region applies to the whole image.
+   * A page number is required to specify a region.
+   */
+  readonly region: Maybe<Geometry>
+}
+
+/** A document, one of its pages, or a region of a page image */
+export type DocumentRegionInput = {
+  /** ID of the document */
+  readonly documentId: Scalars["UUID"]
+  /** One-indexed page number, if the annotation is about one page */
+  readonly page: InputMaybe<Scalars["Int"]>
+  /** Region of the page image, if the annotation is about part of it */
+  readonly region: InputMaybe<GeometryInput>
+}
+
+/** Documents matching a search, along with facet counts to narrow it by. */
+export type DocumentSearchResults = {
+  readonly __typename?: "DocumentSearchResults"
+  /** Documents matching the search and filter */
+  readonly documents: ReadonlyArray<DocumentReference>
+  /**
+   * Counts of the matching documents by collection, genre, decade,
+   * contributor, and audio
+   */
+  readonly facets: SearchFacets
+}
+
 /**
  * The kind of a document in terms of what body it lives within. A reference
  * document is a dictionary or grammar for example, while a corpus document
@@ -481,6 +1023,16 @@ export enum DocumentType {
   Reference = "REFERENCE",
 }
 
+/** Input object for changing the content of an existing comment */
+export type EditCommentInput = {
+  /** ID of the comment to edit */
+  readonly commentId: Scalars["UUID"]
+  /** New classification for the comment (optional) */
+  readonly commentType: InputMaybe<CommentType>
+  /** New content of the comment */
+  readonly textContent: Scalars["String"]
+}
+
 /**
  * Structure to represent an edited collection. Missing certain fields and chapters in it.
  * Used for sending data to the front end
@@ -490,14 +1042,49 @@ export type EditedCollection = {
   readonly chapters: Maybe<ReadonlyArray<CollectionChapter>>
   /** UUID for the collection */
   readonly id: Scalars["UUID"]
+  /**
+   * Links to every chapter of the collection, nested the same way as the
+   * chapters themselves
+   */
+  readonly navigation: ReadonlyArray<NavigationItem>
   /** URL slug for the collection, like "cwkw" */
   readonly slug: Scalars["String"]
   /** Full title of the collection */
   readonly title: Scalars["String"]
-  /** ID of WordPress menu for navigating the collection */
+  /**
+   * ID of WordPress menu for navigating the collection
+   * @deprecated Use `navigation` instead
+   */
   readonly wordpressMenuId: Maybe<Scalars["Int"]>
 }
 
+/** The number of search results that share one value of a facet. */
+export type FacetCount = {
+  readonly __typename?: "FacetCount"
+  /** Number of results with this value */
+  readonly count: Scalars["Int"]
+  /** Human-readable name of this facet value */
+  readonly label: Scalars["String"]
+  /** Value to pass back in a [`SearchFilter`] to narrow by this facet */
+  readonly value: Scalars["String"]
+}
+
+/** Replace some text in every word of a document or collection */
+export type FindAndReplaceInput = {
+  /** Only change words in documents of this collection, by slug */
+  readonly collection: InputMaybe<Scalars["String"]>
+  /** Only change words in this document */
+  readonly documentId: InputMaybe<Scalars["UUID"]>
+  /** Only list the words that would change, without changing them */
+  readonly dryRun: Scalars["Boolean"]
+  /** Which part of each word to change */
+  readonly field: ReplaceField
+  /** Text to look for */
+  readonly find: Scalars["String"]
+  /** Text to put in its place */
+  readonly replace: Scalars["String"]
+}
+
 export type FormsInTime = {
   readonly __typename?: "FormsInTime"
   readonly end: Maybe<Date>
@@ -508,6 +1095,13 @@ export type FormsInTime = {
 /** A gallery of images, which may be rendered as a slideshow or lightbox. */
 export type Gallery = {
   readonly __typename?: "Gallery"
+  /** URLs of the images, in order */
+  readonly mediaUrls: ReadonlyArray<Scalars["String"]>
+}
+
+/** A gallery of images, which may be rendered as a slideshow or lightbox. */
+export type GalleryInput = {
+  /** URLs of the images, in order */
   readonly mediaUrls: ReadonlyArray<Scalars["String"]>
 }
 
@@ -536,6 +1130,72 @@ export type Geometry = {
   readonly yMin: Scalars["Float"]
 }
 
+/**
+ * A rectangle slice of something, usually a large document image.
+ *
+ * Units are a percentage of the containing document.
+ * This is more useful than pixels because we can more easily compare
+ * geometries between images of different resolutions. For example, we could identify
+ * all items in any bottom-right corner with Geometry(90%, 90%, 100%, 100%).
+ * Physical units would be better, but IIIF only allows pixels and percentages.
+ *
+ * Potential use case:
+ * Each document is represented by an ordered list of [AnnotatedForm]s. Each
+ * form has some geometry on the source image. There are a bunch of other
+ * annotations on the source image that are unordered. These may be specific
+ * syllabary characters, notes about the handwriting, etc. Using MongoDB
+ * comparison queries, we can request a list of all spatial annotations
+ * on the same document that lie within or around the geometry of this specific word.
+ */
+export type GeometryInput = {
+  readonly xMax: Scalars["Float"]
+  readonly xMin: Scalars["Float"]
+  readonly yMax: Scalars["Float"]
+  readonly yMin: Scalars["Float"]
+}
+
+/** A morpheme tag embedded in a page, like an entry in a glossary. */
+export type GlossaryEntry = {
+  readonly __typename?: "GlossaryEntry"
+  /** Which system to show the tag in */
+  readonly system: CherokeeOrthography
+  /** The tag as it is written in the chosen system, with its definition */
+  readonly tag: Maybe<MorphemeTag>
+  /** Internal gloss of the tag, like "3SG.B" */
+  readonly tagId: Scalars["String"]
+}
+
+/** A morpheme tag embedded in a page, like an entry in a glossary. */
+export type GlossaryEntryInput = {
+  /** Which system to show the tag in */
+  readonly system: CherokeeOrthography
+  /** Internal gloss of the tag, like "3SG.B" */
+  readonly tagId: Scalars["String"]
+}
+
+/**
+ * Give a user a role on one collection or document. A user has at most one
+ * role on each target, so this replaces any role they already had there.
+ */
+export type GrantPermissionInput = {
+  /** What the user may do */
+  readonly role: UserGroup
+  /** What kind of thing the target is */
+  readonly scope: PermissionScope
+  /** ID of the edited collection, document collection or document */
+  readonly targetId: Scalars["UUID"]
+  /** The user to give the role to */
+  readonly userId: Scalars["UUID"]
+}
+
+/** Input object for hiding a comment from readers */
+export type HideCommentInput = {
+  /** ID of the comment to hide */
+  readonly commentId: Scalars["UUID"]
+  /** Why the comment is being hidden, shown to other editors */
+  readonly reason: Scalars["String"]
+}
+
 export type IiifImages = {
   readonly __typename?: "IiifImages"
   /** Information about the data source for this set of images */
@@ -550,6 +1210,33 @@ export type ImageSource = {
   readonly url: Scalars["String"]
 }
 
+/** Add a word to a paragraph */
+export type InsertWordInput = {
+  /**
+   * ID of the word the new word follows, or nothing to add it at the start
+   * of the paragraph
+   */
+  readonly afterWordId: InputMaybe<Scalars["UUID"]>
+  /** ID of the paragraph to add the word to */
+  readonly paragraphId: Scalars["UUID"]
+  /** The new word */
+  readonly word: NewWordInput
+}
+
+/** A language that the website can be shown in. */
+export enum InterfaceLanguage {
+  Cherokee = "CHEROKEE",
+  English = "ENGLISH",
+}
+
+/** Invite someone to a user group, or change the group they were invited to. */
+export type InviteUserInput = {
+  /** Email address the person will sign up with */
+  readonly email: Scalars["String"]
+  /** The group to put them in */
+  readonly role: UserGroup
+}
+
 /** Start of a new line */
 export type LineBreak = {
   readonly __typename?: "LineBreak"
@@ -563,9 +1250,68 @@ export type LineBreak = {
 /** A block of prose content, formatted with [Markdown](https://commonmark.org/). */
 export type Markdown = {
   readonly __typename?: "Markdown"
+  /** Markdown source of the block */
+  readonly content: Scalars["String"]
+}
+
+/** A block of prose content, formatted with [Markdown](https://commonmark.org/). */
+export type MarkdownInput = {
+  /** Markdown source of the block */
   readonly content: Scalars["String"]
 }
 
+/** Combine duplicate records of the same person into one. */
+export type MergeContributorsInput = {
+  /**
+   * IDs of the duplicate contributors, whose attributions move to the kept
+   * contributor before they are deleted
+   */
+  readonly duplicateIds: ReadonlyArray<Scalars["UUID"]>
+  /** ID of the contributor to keep */
+  readonly keepId: Scalars["UUID"]
+}
+
+/**
+ * Join a paragraph with the one after it, which may start on the next page.
+ * Their translations are joined, and comments on the second paragraph move
+ * to the merged one.
+ */
+export type MergeParagraphsInput = {
+  /** Version of the second paragraph this edit is based on */
+  readonly nextVersion: Scalars["Int"]
+  /** ID of the first paragraph */
+  readonly paragraphId: Scalars["UUID"]
+  /** Version of the first paragraph this edit is based on */
+  readonly version: Scalars["Int"]
+}
+
+/**
+ * Join a word with the one after it in the same paragraph. The merged word
+ * keeps the annotations of the first word, and gains any comments and
+ * annotations on the second.
+ */
+export type MergeWordsInput = {
+  /** Version of the second word this edit is based on */
+  readonly nextVersion: Scalars["Int"]
+  /** Version of the first word this edit is based on */
+  readonly version: Scalars["Int"]
+  /** ID of the first word */
+  readonly wordId: Scalars["UUID"]
+}
+
+/** A word whose segmentation matches a morpheme pattern. */
+export type MorphemePatternMatch = {
+  readonly __typename?: "MorphemePatternMatch"
+  /** The matching word */
+  readonly form: AnnotatedForm
+  /** Index just past the last segment in the matched span */
+  readonly matchEnd: Scalars["Int"]
+  /** Index of the first segment in the matched span */
+  readonly matchStart: Scalars["Int"]
+  /** All segments of the word, in their internal glossing */
+  readonly segments: ReadonlyArray<WordSegment>
+}
+
 /** One particular morpheme and all the known words that contain that exact morpheme. */
 export type MorphemeReference = {
   readonly __typename?: "MorphemeReference"
@@ -621,10 +1367,43 @@ export type MorphemeTag = {
   readonly title: Scalars["String"]
 }
 
+/**
+ * Move a chapter, along with its subchapters, to a new place in its
+ * collection. Moving a chapter within the same parent reorders it.
+ */
+export type MoveChapterInput = {
+  /** ID of the chapter to move */
+  readonly id: Scalars["UUID"]
+  /**
+   * Zero-indexed position among the chapters with the new parent, or
+   * nothing to put it after all of them
+   */
+  readonly index: InputMaybe<Scalars["Int"]>
+  /**
+   * ID of the chapter to nest this one under, or nothing to move it
+   * directly into the collection
+   */
+  readonly parentId: InputMaybe<Scalars["UUID"]>
+}
+
 export type Mutation = {
   readonly __typename?: "Mutation"
+  /**
+   * Apply a proposed edit and resolve the suggestion it came with.
+   * Fails if the word or paragraph changed since the edit was proposed.
+   */
+  readonly acceptProposedEdit: ProposedEdit
   /** Adds a bookmark to the user's list of bookmarks. */
   readonly addBookmark: AnnotatedDoc
+  /**
+   * Add a chapter to an edited collection. Showing a document in it also
+   * needs rights on that document.
+   */
+  readonly addChapter: CollectionChapter
+  /** Add an empty page to the end of a document. */
+  readonly addDocumentPage: AnnotatedDoc
+  /** Add a paragraph of new words to a page. */
+  readonly addParagraph: AnnotatedDoc
   /**
    * Mutation must have at least one visible field for introspection to work
    * correctly, so we just provide an API version which might be useful in
@@ -636,59 +1415,356 @@ export type Mutation = {
    * Assumes user requesting mutation recoreded the audio
    */
   readonly attachAudioToWord: AnnotatedForm
+  /**
+   * Write a new annotation on a word, a page image or a region of one, or
+   * in reply to another annotation.
+   */
+  readonly createAnnotation: Annotation
+  /**
+   * Make an API key that acts as the signed-in user. The key is only shown
+   * in the response to this mutation.
+   */
+  readonly createApiKey: NewApiKey
+  /** Make a record for a new contributor. */
+  readonly createContributor: ContributorDetails
+  /** Make a new, empty document. Add pages to it with `addDocumentPage`. */
+  readonly createDocument: AnnotatedDoc
+  /** Make a new edited collection with no chapters. */
+  readonly createEditedCollection: EditedCollection
+  /** Make a new content page, as a draft unless told otherwise. */
+  readonly createPage: Page
   /** Decide if a piece audio should be included in edited collection */
   readonly curateWordAudio: AnnotatedForm
+  /** Delete an annotation along with all replies to it, returning its ID. */
+  readonly deleteAnnotation: Scalars["UUID"]
   /**
-   * Delete a comment.
-   * Will fail if the user making the request is not the poster.
+   * Delete a chapter along with all of its subchapters. Documents in them
+   * are kept.
+   */
+  readonly deleteChapter: Scalars["UUID"]
+  /**
+   * Delete a comment and its replies.
+   * Will fail if the user making the request is not the poster, or if
+   * others have replied and the user is not an editor.
    */
   readonly deleteComment: CommentParent
+  /** Delete a contributor along with their attributions, returning their ID. */
+  readonly deleteContributor: Scalars["UUID"]
   /** Mutation for deleting contributor attributions */
   readonly deleteContributorAttribution: Scalars["UUID"]
+  /** Delete a content page, returning its path. */
+  readonly deletePage: Scalars["String"]
+  /**
+   * Delete a word and any comments on it. The only word in a paragraph
+   * can't be deleted.
+   */
+  readonly deleteWord: AnnotatedDoc
+  /**
+   * Change the content of a comment.
+   * Will fail if the user making the request is not the poster.
+   */
+  readonly editComment: Comment
+  /**
+   * Replace text in one field of every word in a document or collection.
+   * Use `dryRun` to preview which words would change. Replacing across a
+   * whole collection needs the Editors group, not just a role on it.
+   */
+  readonly findAndReplace: ReadonlyArray<Replacement>
+  /**
+   * Give a user a role on one edited collection, document collection or
+   * document, replacing any role they already had there.
+   */
+  readonly grantPermission: PermissionGrant
+  /** Hide a comment from readers, giving a reason for other editors. */
+  readonly hideComment: Comment
+  /** Add a new word to a paragraph. */
+  readonly insertWord: AnnotatedDoc
+  /**
+   * Put someone in a user group once they sign up and confirm their
+   * account with the given email, replacing any earlier invitation.
+   */
+  readonly inviteUser: RoleInvitation
+  /**
+   * Combine duplicate records of the same person, moving all of their
+   * attributions to the record that is kept.
+   */
+  readonly mergeContributors: ContributorDetails
+  /** Join a paragraph with the one after it. */
+  readonly mergeParagraphs: AnnotatedDoc
+  /** Join a word with the one after it in the same paragraph. */
+  readonly mergeWords: AnnotatedDoc
+  /**
+   * Move a chapter under a different parent, or reorder it among its
+   * siblings.
+   */
+  readonly moveChapter: CollectionChapter
   /** Post a new comment on a given object */
   readonly postComment: CommentParent
+  /**
+   * Suggest an exact change to a word or paragraph, posted as a suggestion
+   * comment for an editor to accept or reject.
+   */
+  readonly proposeEdit: CommentParent
+  /**
+   * Turn down a proposed edit, explaining why in a note, and resolve the
+   * suggestion it came with.
+   */
+  readonly rejectProposedEdit: ProposedEdit
   /** Removes a bookmark from a user's list of bookmarks */
   readonly removeBookmark: AnnotatedDoc
-  readonly updateAnnotation: Scalars["Boolean"]
+  /**
+   * Take back an invitation, returning its email. Anyone who already
+   * accepted it stays in their group.
+   */
+  readonly removeInvitation: Scalars["String"]
+  /**
+   * Put the given pages first in the site order, followed by any others,
+   * and return all pages in their new order.
+   */
+  readonly reorderPages: ReadonlyArray<Page>
+  /**
+   * Undo the given paragraph revision, putting the translation back the
+   * way it was before that edit.
+   */
+  readonly revertParagraph: DocumentParagraph
+  /**
+   * Undo the given word revision, putting the word and its segments back
+   * the way they were before that edit.
+   */
+  readonly revertWord: AnnotatedForm
+  /**
+   * Stop one of the signed-in user's API keys from working, returning its
+   * ID.
+   */
+  readonly revokeApiKey: Scalars["UUID"]
+  /** Take away a role given with `grantPermission`, returning its ID. */
+  readonly revokePermission: Scalars["UUID"]
+  /**
+   * Mark a suggestion or question as resolved, or open it again.
+   * Will fail unless the user making the request is the poster or an editor.
+   */
+  readonly setCommentResolved: Comment
+  /** Break a paragraph into two before the given word. */
+  readonly splitParagraph: AnnotatedDoc
+  /** Break a word into two. */
+  readonly splitWord: AnnotatedDoc
+  /** Show a hidden comment to readers again. */
+  readonly unhideComment: Comment
+  /** Change the content or region of an annotation. */
+  readonly updateAnnotation: Annotation
+  /**
+   * Rename a chapter or change its section, document or text. Showing a
+   * different document also needs rights on that document.
+   */
+  readonly updateChapter: CollectionChapter
+  /** Change the name or other details of a contributor. */
+  readonly updateContributor: ContributorDetails
   /** Mutation for adding/changing contributor attributions */
   readonly updateContributorAttribution: Scalars["UUID"]
   readonly updateDocumentMetadata: Scalars["UUID"]
-  readonly updatePage: Scalars["Boolean"]
+  /** Change the title, content, or status of a content page. */
+  readonly updatePage: Page
   /** Mutation for paragraph and translation editing */
   readonly updateParagraph: DocumentParagraph
+  /** Change the profile and reading preferences of the signed-in user. */
+  readonly updateProfile: UserProfile
   readonly updateWord: AnnotatedForm
+  /**
+   * Update many words at once in one transaction, such as to fix the same
+   * mistake across a document. Every update is checked before any are
+   * saved, and if any of them can't be saved then none are.
+   */
+  readonly updateWords: ReadonlyArray<WordUpdateResult>
+}
+
+export type MutationAcceptProposedEditArgs = {
+  proposedEditId: Scalars["UUID"]
+}
+
+export type MutationAddBookmarkArgs = {
+  documentId: Scalars["UUID"]
+}
+
+export type MutationAddChapterArgs = {
+  input: AddChapterInput
+}
+
+export type MutationAddDocumentPageArgs = {
+  input: AddDocumentPageInput
+}
+
+export type MutationAddParagraphArgs = {
+  input: AddParagraphInput
+}
+
+export type MutationAttachAudioToWordArgs = {
+  input: AttachAudioToWordInput
+}
+
+export type MutationCreateAnnotationArgs = {
+  input: CreateAnnotationInput
+}
+
+export type MutationCreateApiKeyArgs = {
+  input: CreateApiKeyInput
+}
+
+export type MutationCreateContributorArgs = {
+  input: CreateContributorInput
+}
+
+export type MutationCreateDocumentArgs = {
+  input: CreateDocumentInput
+}
+
+export type MutationCreateEditedCollectionArgs = {
+  input: CreateEditedCollectionInput
+}
+
+export type MutationCreatePageArgs = {
+  input: CreatePageInput
+}
+
+export type MutationCurateWordAudioArgs = {
+  input: CurateWordAudioInput
+}
+
+export type MutationDeleteAnnotationArgs = {
+  annotationId: Scalars["UUID"]
+}
+
+export type MutationDeleteChapterArgs = {
+  id: Scalars["UUID"]
+}
+
+export type MutationDeleteCommentArgs = {
+  input: DeleteCommentInput
+}
+
+export type MutationDeleteContributorArgs = {
+  id: Scalars["UUID"]
+}
+
+export type MutationDeleteContributorAttributionArgs = {
+  contribution: DeleteContributorAttribution
+}
+
+export type MutationDeletePageArgs = {
+  id: Scalars["String"]
+}
+
+export type MutationDeleteWordArgs = {
+  wordId: Scalars["UUID"]
+}
+
+export type MutationEditCommentArgs = {
+  input: EditCommentInput
+}
+
+export type MutationFindAndReplaceArgs = {
+  input: FindAndReplaceInput
+}
+
+export type MutationGrantPermissionArgs = {
+  input: GrantPermissionInput
+}
+
+export type MutationHideCommentArgs = {
+  input: HideCommentInput
+}
+
+export type MutationInsertWordArgs = {
+  input: InsertWordInput
+}
+
+export type MutationInviteUserArgs = {
+  input: InviteUserInput
+}
+
+export type MutationMergeContributorsArgs = {
+  input: MergeContributorsInput
+}
+
+export type MutationMergeParagraphsArgs = {
+  input: MergeParagraphsInput
+}
+
+export type MutationMergeWordsArgs = {
+  input: MergeWordsInput
+}
+
+export type MutationMoveChapterArgs = {
+  input: MoveChapterInput
+}
+
+export type MutationPostCommentArgs = {
+  input: PostCommentInput
 }
 
-export type MutationAddBookmarkArgs = {
+export type MutationProposeEditArgs = {
+  input: ProposeEditInput
+}
+
+export type MutationRejectProposedEditArgs = {
+  note: Scalars["String"]
+  proposedEditId: Scalars["UUID"]
+}
+
+export type MutationRemoveBookmarkArgs = {
   documentId: Scalars["UUID"]
 }
 
-export type MutationAttachAudioToWordArgs = {
-  input: AttachAudioToWordInput
+export type MutationRemoveInvitationArgs = {
+  email: Scalars["String"]
 }
 
-export type MutationCurateWordAudioArgs = {
-  input: CurateWordAudioInput
+export type MutationReorderPagesArgs = {
+  ids: ReadonlyArray<Scalars["String"]>
 }
 
-export type MutationDeleteCommentArgs = {
-  input: DeleteCommentInput
+export type MutationRevertParagraphArgs = {
+  revisionId: Scalars["UUID"]
 }
 
-export type MutationDeleteContributorAttributionArgs = {
-  contribution: DeleteContributorAttribution
+export type MutationRevertWordArgs = {
+  revisionId: Scalars["UUID"]
 }
 
-export type MutationPostCommentArgs = {
-  input: PostCommentInput
+export type MutationRevokeApiKeyArgs = {
+  id: Scalars["UUID"]
 }
 
-export type MutationRemoveBookmarkArgs = {
-  documentId: Scalars["UUID"]
+export type MutationRevokePermissionArgs = {
+  id: Scalars["UUID"]
+}
+
+export type MutationSetCommentResolvedArgs = {
+  commentId: Scalars["UUID"]
+  resolved: Scalars["Boolean"]
+}
+
+export type MutationSplitParagraphArgs = {
+  input: SplitParagraphInput
+}
+
+export type MutationSplitWordArgs = {
+  input: SplitWordInput
+}
+
+export type MutationUnhideCommentArgs = {
+  commentId: Scalars["UUID"]
 }
 
 export type MutationUpdateAnnotationArgs = {
-  data: Scalars["JSON"]
+  input: UpdateAnnotationInput
+}
+
+export type MutationUpdateChapterArgs = {
+  input: UpdateChapterInput
+}
+
+export type MutationUpdateContributorArgs = {
+  input: UpdateContributorInput
 }
 
 export type MutationUpdateContributorAttributionArgs = {
@@ -700,29 +1776,83 @@ export type MutationUpdateDocumentMetadataArgs = {
 }
 
 export type MutationUpdatePageArgs = {
-  data: Scalars["JSON"]
+  input: UpdatePageInput
 }
 
 export type MutationUpdateParagraphArgs = {
   paragraph: ParagraphUpdate
 }
 
+export type MutationUpdateProfileArgs = {
+  input: UpdateProfileInput
+}
+
 export type MutationUpdateWordArgs = {
   word: AnnotatedFormUpdate
 }
 
+export type MutationUpdateWordsArgs = {
+  words: ReadonlyArray<AnnotatedFormUpdate>
+}
+
+/** A link to one chapter in the navigation menu of an edited collection. */
+export type NavigationItem = {
+  readonly __typename?: "NavigationItem"
+  /** UUID of the linked chapter */
+  readonly chapterId: Scalars["UUID"]
+  /** Links to the subchapters of this chapter, in order */
+  readonly children: ReadonlyArray<NavigationItem>
+  /** Full path of the chapter */
+  readonly path: ReadonlyArray<Scalars["String"]>
+  /** Whether the chapter is an "Intro" or "Body" chapter */
+  readonly section: CollectionSection
+  /** URL slug of the chapter */
+  readonly slug: Scalars["String"]
+  /** Full title of the chapter */
+  readonly title: Scalars["String"]
+}
+
+/** A newly made API key, along with the key itself. */
+export type NewApiKey = {
+  readonly __typename?: "NewApiKey"
+  /** Details of the key */
+  readonly apiKey: ApiKey
+  /** The key to send in the `X-API-Key` header. It can't be shown again. */
+  readonly key: Scalars["String"]
+}
+
+/** A word to add to a document */
+export type NewWordInput = {
+  /** Further notes about the word */
+  readonly commentary: InputMaybe<Scalars["String"]>
+  /** English translation of the word */
+  readonly englishGloss: InputMaybe<Scalars["String"]>
+  /** Phonemic form of the word */
+  readonly phonemic: InputMaybe<Scalars["String"]>
+  /** Romanized form of the word */
+  readonly simplePhonetics: InputMaybe<Scalars["String"]>
+  /** Source text of the word */
+  readonly source: Scalars["String"]
+}
+
 /**
  * A website page which lives at a specific URL and has a list of blocks that
  * define its content.
  */
 export type Page = {
   readonly __typename?: "Page"
+  /** Content of the page, in order */
   readonly body: ReadonlyArray<ContentBlock>
   /**
    * The path that this page lives at, which also uniquely identifies it.
    * For example, "/our-team"
    */
   readonly id: Scalars["String"]
+  /** When this page was first published, if it has been */
+  readonly publishedAt: Maybe<DateTime>
+  /** Whether readers can see this page yet */
+  readonly status: PageStatus
+  /** Title of the page */
   readonly title: Scalars["String"]
 }
 
@@ -734,12 +1864,91 @@ export type PageImage = {
   readonly url: Scalars["String"]
 }
 
+/** Information about pagination in a connection */
+export type PageInfo = {
+  readonly __typename?: "PageInfo"
+  /** When paginating forwards, the cursor to continue. */
+  readonly endCursor: Maybe<Scalars["String"]>
+  /** When paginating forwards, are there more items? */
+  readonly hasNextPage: Scalars["Boolean"]
+  /** When paginating backwards, are there more items? */
+  readonly hasPreviousPage: Scalars["Boolean"]
+  /** When paginating backwards, the cursor to continue. */
+  readonly startCursor: Maybe<Scalars["String"]>
+}
+
+/** Whether a page is visible to readers. Editors can see every page. */
+export enum PageStatus {
+  Draft = "DRAFT",
+  Published = "PUBLISHED",
+}
+
+/** One change made to a word or paragraph. */
+export type ParagraphRevision = {
+  readonly __typename?: "ParagraphRevision"
+  /** Editable fields after the change */
+  readonly after: ParagraphSnapshot
+  /** Editable fields before the change */
+  readonly before: ParagraphSnapshot
+  /** When the edit was made */
+  readonly editedAt: DateTime
+  /** Who made the edit, unless their account has since been deleted */
+  readonly editedBy: Maybe<User>
+  /** Unique identifier of this revision */
+  readonly id: Scalars["UUID"]
+}
+
+/** The editable fields of a paragraph at one point in time */
+export type ParagraphSnapshot = {
+  readonly __typename?: "ParagraphSnapshot"
+  /** English translation of the whole paragraph */
+  readonly translation: Scalars["String"]
+}
+
 /** A paragraph in an annotated document that can be edited. */
 export type ParagraphUpdate = {
   /** Unique identifier of the form */
   readonly id: Scalars["UUID"]
   /** English translation of the paragraph */
   readonly translation: InputMaybe<Scalars["String"]>
+  /**
+   * Version of the paragraph this edit is based on, from
+   * `DocumentParagraph.version`
+   */
+  readonly version: Scalars["Int"]
+}
+
+/**
+ * A role given to one user on a single collection or document, on top of the
+ * groups they belong to everywhere.
+ */
+export type PermissionGrant = {
+  readonly __typename?: "PermissionGrant"
+  /** When the role was given */
+  readonly grantedAt: DateTime
+  /** The editor who gave the role */
+  readonly grantedBy: Maybe<Scalars["UUID"]>
+  /** Unique ID of the grant */
+  readonly id: Scalars["UUID"]
+  /** What the user may do, as if they were in this group for the target */
+  readonly role: UserGroup
+  /** What kind of thing the target is */
+  readonly scope: PermissionScope
+  /** ID of the collection or document that the role applies to */
+  readonly targetId: Scalars["UUID"]
+  /** Title of the target, if it still exists */
+  readonly targetTitle: Maybe<Scalars["String"]>
+  /** The user who was given the role */
+  readonly userId: Scalars["UUID"]
+  /** Name of the user who was given the role */
+  readonly userName: Scalars["String"]
+}
+
+/** The kind of thing that a permission grant gives access to. */
+export enum PermissionScope {
+  Document = "DOCUMENT",
+  Document_Collection = "DOCUMENT_COLLECTION",
+  Edited_Collection = "EDITED_COLLECTION",
 }
 
 /** The reference position within a document of one specific form */
@@ -785,27 +1994,109 @@ export type PostCommentInput = {
   readonly textContent: Scalars["String"]
 }
 
+/**
+ * Suggest a change to a word or paragraph, explained by a suggestion comment
+ * on it. Exactly one of `word` or `paragraph` must be given.
+ */
+export type ProposeEditInput = {
+  /** Update to a paragraph */
+  readonly paragraph: InputMaybe<ParagraphUpdate>
+  /** Explanation of the change, posted as a suggestion comment */
+  readonly textContent: Scalars["String"]
+  /** Update to a word */
+  readonly word: InputMaybe<AnnotatedFormUpdate>
+}
+
+/** One field a proposed edit would change */
+export type ProposedChange = {
+  readonly __typename?: "ProposedChange"
+  /** What the field is now. Segments are shown separated by dashes. */
+  readonly current: Maybe<Scalars["String"]>
+  /** Name of the changed field, like "source" or "glosses" */
+  readonly field: Scalars["String"]
+  /** What the field would be after accepting the edit */
+  readonly proposed: Maybe<Scalars["String"]>
+}
+
+/** A change to a word or paragraph proposed alongside a suggestion comment. */
+export type ProposedEdit = {
+  readonly __typename?: "ProposedEdit"
+  /** Version of the word or paragraph this edit was based on */
+  readonly baseVersion: Scalars["Int"]
+  /** Fields this edit would change, compared to their current values */
+  readonly changes: ReadonlyArray<ProposedChange>
+  /** Unique identifier of this proposed edit */
+  readonly id: Scalars["UUID"]
+  /**
+   * Whether the word or paragraph has changed since this edit was
+   * proposed, in which case it can't be accepted as is.
+   */
+  readonly outdated: Scalars["Boolean"]
+  /** Why the edit was rejected, if the editor gave a reason */
+  readonly reviewNote: Maybe<Scalars["String"]>
+  /** When an editor accepted or rejected this edit */
+  readonly reviewedAt: Maybe<DateTime>
+  /** Who accepted or rejected this edit */
+  readonly reviewedBy: Maybe<User>
+  /** Whether this edit is still waiting for review */
+  readonly status: ProposedEditStatus
+  /** ID of the word or paragraph this edit would change */
+  readonly targetId: Scalars["UUID"]
+}
+
+/** Where a proposed edit is in the review process */
+export enum ProposedEditStatus {
+  Accepted = "ACCEPTED",
+  Open = "OPEN",
+  Rejected = "REJECTED",
+}
+
 export type Query = {
   readonly __typename?: "Query"
   /** List of all the document collections available. */
   readonly allCollections: ReadonlyArray<DocumentCollection>
+  /** All contributors, ordered by full name */
+  readonly allContributors: ReadonlyArray<ContributorDetails>
   /** Listing of all documents excluding their contents by default */
   readonly allDocuments: ReadonlyArray<AnnotatedDoc>
+  /** Documents ordered by short name, one page at a time */
+  readonly allDocumentsConnection: AnnotatedDocConnection
   readonly allEditedCollections: ReadonlyArray<EditedCollection>
-  /** List of all content pages */
+  /** List of all content pages in site order. Only editors see drafts. */
   readonly allPages: ReadonlyArray<Page>
-  /** List of all the functional morpheme tags available */
+  /**
+   * List of all the functional morpheme tags available, written in the given
+   * system or the reader's preferred one
+   */
   readonly allTags: ReadonlyArray<MorphemeTag>
   /** Retrieves all documents that are bookmarked by the current user. */
   readonly bookmarkedDocuments: ReadonlyArray<AnnotatedDoc>
   /** Retrieves a chapter and its contents by its collection and chapter slug. */
   readonly chapter: Maybe<CollectionChapter>
   readonly collection: DocumentCollection
+  /** Details of one contributor, including every document they worked on */
+  readonly contributor: Maybe<ContributorDetails>
   /** Retrieves a full document from its unique name. */
   readonly document: Maybe<AnnotatedDoc>
   /** Retrieves a full document from its unique identifier. */
   readonly documentByUuid: Maybe<AnnotatedDoc>
   readonly editedCollection: Maybe<EditedCollection>
+  /**
+   * Find words whose segmentation matches a pattern over their morpheme
+   * glosses, like `3* - go - PST`. Each match reports which segments of
+   * the word matched so they can be highlighted.
+   *
+   * Patterns are a sequence of glosses separated by spaces or hyphens.
+   * A `*` within a gloss matches any characters, `_` matches any single
+   * segment, `...` matches any number of segments, and `(A | B)` matches
+   * either alternative. Add `@Morpheme`, `@Clitic`, or `@Modifier` to a
+   * segment to require that role. Start with `^` or end with `$` to anchor
+   * the pattern to the start or end of the word.
+   *
+   * Unless limited to one document, the pattern must contain at least one
+   * exact gloss outside of any alternatives.
+   */
+  readonly morphemePatternSearch: ReadonlyArray<MorphemePatternMatch>
   /**
    * Retrieve information for the morpheme that corresponds to the given tag
    * string. For example, "3PL.B" is the standard string referring to a 3rd
@@ -819,20 +2110,65 @@ export type Query = {
    * Groups these words by the document containing them.
    */
   readonly morphemesByDocument: ReadonlyArray<WordsInDocument>
+  /**
+   * Lists all words containing a morpheme with the given gloss, grouped by
+   * the document containing them, one page of documents at a time.
+   * Each document includes all of its matching words, so to find words in
+   * a single document use `morphemesByDocument` instead.
+   */
+  readonly morphemesByDocumentConnection: WordsInDocumentConnection
   /**
    * Lists all forms containing a morpheme with the given gloss.
    * Groups these words by the phonemic shape of the target morpheme.
    */
   readonly morphemesByShape: ReadonlyArray<MorphemeReference>
-  /** Retrieves a full document from its unique identifier. */
+  /**
+   * Suggestions on the words and paragraphs of a document that nobody has
+   * resolved yet, oldest first.
+   */
+  readonly openSuggestions: ReadonlyArray<Comment>
+  /** Retrieves a content page by its path. Drafts are only shown to editors. */
   readonly page: Maybe<Page>
   /** Get a single paragraph given the paragraph ID */
   readonly paragraphById: DocumentParagraph
+  /**
+   * Roles given on single collections or documents, either to one user or
+   * to everyone
+   */
+  readonly permissionGrants: ReadonlyArray<PermissionGrant>
+  /**
+   * Look up Cherokee words by their English meaning, for learners.
+   * English words are matched regardless of inflection, so "walked" also
+   * finds "walking". Results are grouped by the root they're built on, with
+   * roots whose dictionary definition matches ranked above those with only
+   * matching attested forms.
+   */
+  readonly reverseLookup: ReadonlyArray<ReverseLookupEntry>
+  /** People who will be put in a user group when they confirm their account */
+  readonly roleInvitations: ReadonlyArray<RoleInvitation>
+  /**
+   * Search for documents with a title or short name containing the given
+   * query, along with counts of the results by collection, genre, decade,
+   * contributor, and audio.
+   * The filter narrows results down to particular values of those facets.
+   */
+  readonly searchDocuments: DocumentSearchResults
+  /**
+   * Search for words that match the given query, along with counts of the
+   * results by collection, genre, decade, contributor, and audio.
+   * The filter narrows results down to particular values of those facets.
+   */
+  readonly searchWords: WordSearchResults
   /**
    * Search for words with the exact same syllabary string, or with very
    * similar looking characters.
    */
   readonly syllabarySearch: ReadonlyArray<AnnotatedForm>
+  /**
+   * Search for words with syllabary similar to the given query, one page
+   * at a time
+   */
+  readonly syllabarySearchConnection: AnnotatedFormConnection
   /** Basic information about the currently authenticated user, if any. */
   readonly userInfo: Maybe<UserInfo>
   /** Get a single word given the word ID */
@@ -842,10 +2178,22 @@ export type Query = {
    * Each query may match against multiple fields of a word.
    */
   readonly wordSearch: ReadonlyArray<AnnotatedForm>
+  /**
+   * Search for words that match any one of the given fields, one page at a
+   * time
+   */
+  readonly wordSearchConnection: AnnotatedFormConnection
+}
+
+export type QueryAllDocumentsConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
 }
 
 export type QueryAllTagsArgs = {
-  system: CherokeeOrthography
+  system: InputMaybe<CherokeeOrthography>
 }
 
 export type QueryChapterArgs = {
@@ -857,6 +2205,10 @@ export type QueryCollectionArgs = {
   slug: Scalars["String"]
 }
 
+export type QueryContributorArgs = {
+  id: Scalars["UUID"]
+}
+
 export type QueryDocumentArgs = {
   slug: Scalars["String"]
 }
@@ -869,9 +2221,14 @@ export type QueryEditedCollectionArgs = {
   slug: Scalars["String"]
 }
 
+export type QueryMorphemePatternSearchArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
+  pattern: Scalars["String"]
+}
+
 export type QueryMorphemeTagArgs = {
   id: Scalars["String"]
-  system: CherokeeOrthography
+  system: InputMaybe<CherokeeOrthography>
 }
 
 export type QueryMorphemeTimeClustersArgs = {
@@ -884,11 +2241,23 @@ export type QueryMorphemesByDocumentArgs = {
   morphemeGloss: Scalars["String"]
 }
 
+export type QueryMorphemesByDocumentConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
+  morphemeGloss: Scalars["String"]
+}
+
 export type QueryMorphemesByShapeArgs = {
   compareBy: InputMaybe<CherokeeOrthography>
   gloss: Scalars["String"]
 }
 
+export type QueryOpenSuggestionsArgs = {
+  documentId: Scalars["UUID"]
+}
+
 export type QueryPageArgs = {
   id: Scalars["String"]
 }
@@ -897,10 +2266,37 @@ export type QueryParagraphByIdArgs = {
   id: Scalars["UUID"]
 }
 
+export type QueryPermissionGrantsArgs = {
+  userId: InputMaybe<Scalars["UUID"]>
+}
+
+export type QueryReverseLookupArgs = {
+  english: Scalars["String"]
+}
+
+export type QuerySearchDocumentsArgs = {
+  filter: InputMaybe<SearchFilter>
+  query: Scalars["String"]
+}
+
+export type QuerySearchWordsArgs = {
+  filter: InputMaybe<SearchFilter>
+  query: Scalars["String"]
+  syllabary?: Scalars["Boolean"]
+}
+
 export type QuerySyllabarySearchArgs = {
   query: Scalars["String"]
 }
 
+export type QuerySyllabarySearchConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
+  query: Scalars["String"]
+}
+
 export type QueryWordByIdArgs = {
   id: Scalars["UUID"]
 }
@@ -909,6 +2305,125 @@ export type QueryWordSearchArgs = {
   query: Scalars["String"]
 }
 
+export type QueryWordSearchConnectionArgs = {
+  after: InputMaybe<Scalars["String"]>
+  before: InputMaybe<Scalars["String"]>
+  first: InputMaybe<Scalars["Int"]>
+  last: InputMaybe<Scalars["Int"]>
+  query: Scalars["String"]
+}
+
+/** Which part of a word find and replace should change */
+export enum ReplaceField {
+  Commentary = "COMMENTARY",
+  Gloss = "GLOSS",
+  Source = "SOURCE",
+}
+
+/** One word changed by find and replace, or that would be in a dry run */
+export type Replacement = {
+  readonly __typename?: "Replacement"
+  /** The field after replacing */
+  readonly after: Scalars["String"]
+  /** The field before replacing. Glosses are shown separated by dashes. */
+  readonly before: Scalars["String"]
+  /** Document containing the word */
+  readonly documentId: Scalars["UUID"]
+  /** ID of the changed word */
+  readonly wordId: Scalars["UUID"]
+}
+
+/** Attachment of an annotation that replies to another one */
+export type Reply = {
+  readonly __typename?: "Reply"
+  /** ID of the annotation being replied to */
+  readonly to: Scalars["UUID"]
+}
+
+/** A group of results from a reverse lookup that all share the same root. */
+export type ReverseLookupEntry = {
+  readonly __typename?: "ReverseLookupEntry"
+  /**
+   * Attested forms of the root whose English matches the lookup, best
+   * matches first
+   */
+  readonly forms: ReadonlyArray<AnnotatedForm>
+  /** Headword for the shared root, if the results have a known root */
+  readonly headword: Maybe<DictionaryHeadword>
+  /** How well this entry matches the lookup, where higher is better */
+  readonly score: Scalars["Float"]
+}
+
+/** Someone who will be put in a user group when they confirm their account. */
+export type RoleInvitation = {
+  readonly __typename?: "RoleInvitation"
+  /** When they confirmed their account and were put in the group */
+  readonly acceptedAt: Maybe<DateTime>
+  /** Email address the person will sign up with, in lowercase */
+  readonly email: Scalars["String"]
+  /** When they were invited */
+  readonly invitedAt: DateTime
+  /** The editor who invited them */
+  readonly invitedBy: Maybe<Scalars["UUID"]>
+  /** The group to put them in */
+  readonly role: UserGroup
+}
+
+/**
+ * Aggregate counts of search results for each value of each facet.
+ * Counts are taken after any [`SearchFilter`] has been applied.
+ */
+export type SearchFacets = {
+  readonly __typename?: "SearchFacets"
+  /** Results with and without audio */
+  readonly audio: AudioCount
+  /** Results per document collection, most results first */
+  readonly collections: ReadonlyArray<FacetCount>
+  /** Results per contributor to the containing document, most results first */
+  readonly contributors: ReadonlyArray<FacetCount>
+  /** Results per decade the containing document was written in, oldest first */
+  readonly decades: ReadonlyArray<DecadeCount>
+  /** Results per document genre, most results first */
+  readonly genres: ReadonlyArray<FacetCount>
+}
+
+/**
+ * Narrows a search down to results matching every given facet.
+ * Facets that are omitted or empty don't restrict the results at all.
+ */
+export type SearchFilter = {
+  /** Only include results from these document collections, by slug */
+  readonly collections: InputMaybe<ReadonlyArray<Scalars["String"]>>
+  /**
+   * Only include results from documents these people contributed to, by
+   * full name
+   */
+  readonly contributors: InputMaybe<ReadonlyArray<Scalars["String"]>>
+  /**
+   * Only include results from documents written in these decades, each
+   * given as its first year, like 1830
+   */
+  readonly decades: InputMaybe<ReadonlyArray<Scalars["Int"]>>
+  /** Only include results from documents of these genres */
+  readonly genres: InputMaybe<ReadonlyArray<Scalars["String"]>>
+  /** When given, only include results which do or do not have audio */
+  readonly hasAudio: InputMaybe<Scalars["Boolean"]>
+}
+
+/** One segment of a word at one point in time */
+export type SegmentSnapshot = {
+  readonly __typename?: "SegmentSnapshot"
+  /**
+   * Gloss of the morpheme, as stored in the database, or empty if the
+   * segment has no gloss
+   */
+  readonly gloss: Scalars["String"]
+  /** Phonemic representation of the morpheme */
+  readonly morpheme: Scalars["String"]
+  /** Role of the segment within the word */
+  readonly role: WordSegmentRole
+}
+
 /**
  * Attribution for a particular source, whether an institution or an individual.
  * Most commonly, this will represent the details of a library or archive that
@@ -922,6 +2437,95 @@ export type SourceAttribution = {
   readonly name: Scalars["String"]
 }
 
+/**
+ * Break one paragraph into two. The new second paragraph starts with no
+ * translation.
+ */
+export type SplitParagraphInput = {
+  /** ID of the first word to move into the new paragraph */
+  readonly beforeWordId: Scalars["UUID"]
+  /** ID of the paragraph to split */
+  readonly paragraphId: Scalars["UUID"]
+}
+
+/**
+ * Break one word into two. The first keeps all of its annotations, and the
+ * second has only source text.
+ */
+export type SplitWordInput = {
+  /** Number of characters of source text to keep in the first word */
+  readonly at: Scalars["Int"]
+  /** Version of the word this edit is based on */
+  readonly version: Scalars["Int"]
+  /** ID of the word to split */
+  readonly wordId: Scalars["UUID"]
+}
+
+export type Subscription = {
+  readonly __typename?: "Subscription"
+  /** New comments on words and paragraphs in the given document */
+  readonly commentPosted: Comment
+  /**
+   * The given document each time its pages, paragraphs or words are added,
+   * removed, split or merged
+   */
+  readonly documentRestructured: AnnotatedDoc
+  /** Paragraphs in the given document as they are edited */
+  readonly paragraphUpdated: DocumentParagraph
+  /** Words in the given document as they are edited */
+  readonly wordUpdated: AnnotatedForm
+}
+
+export type SubscriptionCommentPostedArgs = {
+  documentId: Scalars["UUID"]
+}
+
+export type SubscriptionDocumentRestructuredArgs = {
+  documentId: Scalars["UUID"]
+}
+
+export type SubscriptionParagraphUpdatedArgs = {
+  documentId: Scalars["UUID"]
+}
+
+export type SubscriptionWordUpdatedArgs = {
+  documentId: Scalars["UUID"]
+}
+
+/** Change an existing annotation */
+export type UpdateAnnotationInput = {
+  /** New text content, if it should change */
+  readonly content: InputMaybe<Scalars["String"]>
+  /** ID of the annotation to change */
+  readonly id: Scalars["UUID"]
+  /**
+   * New region of the page image, or null to annotate the whole page.
+   * Only page annotations may have a region.
+   */
+  readonly region: InputMaybe<GeometryInput>
+}
+
+/** Change the details of a chapter. Fields that aren't given stay the same. */
+export type UpdateChapterInput = {
+  /** New content of the chapter, replacing all of its blocks */
+  readonly body: InputMaybe<ReadonlyArray<ContentBlockInput>>
+  /** ID of the document to show in the chapter, or null to show none */
+  readonly documentId: InputMaybe<Scalars["UUID"]>
+  /** ID of the chapter to change */
+  readonly id: Scalars["UUID"]
+  /** New section of the collection for the chapter */
+  readonly section: InputMaybe<CollectionSection>
+  /**
+   * New URL slug for the chapter, which also changes the paths of its
+   * subchapters
+   */
+  readonly slug: InputMaybe<Scalars["String"]>
+  /** New title of the chapter */
+  readonly title: InputMaybe<Scalars["String"]>
+  /** ID of WordPress page with text of the chapter, or null for none */
+  readonly wordpressId: InputMaybe<Scalars["Int"]>
+}
+
 /** Update the contributor attribution for a document */
 export type UpdateContributorAttribution = {
   readonly contributionRole: Scalars["String"]
@@ -929,6 +2533,66 @@ export type UpdateContributorAttribution = {
   readonly documentId: Scalars["UUID"]
 }
 
+/**
+ * Change the details of a contributor. Fields that aren't given stay the
+ * same, and fields given as null are cleared.
+ */
+export type UpdateContributorInput = {
+  /** Tribal nation or community that the person belongs to */
+  readonly affiliation: InputMaybe<Scalars["String"]>
+  /** Alternate name of the person */
+  readonly alternateName: InputMaybe<Scalars["String"]>
+  /** Short biography of the person */
+  readonly biography: InputMaybe<Scalars["String"]>
+  /** The date that the person was born on */
+  readonly birthDate: InputMaybe<DateInput>
+  /** The date that the person died on */
+  readonly deathDate: InputMaybe<DateInput>
+  /** New full name of the person */
+  readonly fullName: InputMaybe<Scalars["String"]>
+  /** ID of the contributor to change */
+  readonly id: Scalars["UUID"]
+  /** ORCID of the person */
+  readonly orcid: InputMaybe<Scalars["String"]>
+  /** Kinds of work the person does, replacing any they had */
+  readonly roles: InputMaybe<ReadonlyArray<Scalars["String"]>>
+  /** Identifier in the Virtual International Authority File */
+  readonly viafId: InputMaybe<Scalars["String"]>
+  /** Identifier of the person on Wikidata */
+  readonly wikidataId: InputMaybe<Scalars["String"]>
+}
+
+/** Change an existing page. Fields that aren't given stay the same. */
+export type UpdatePageInput = {
+  /** New content of the page, replacing all existing blocks */
+  readonly body: InputMaybe<ReadonlyArray<ContentBlockInput>>
+  /** The path of the page to change */
+  readonly id: Scalars["String"]
+  /** Publish the page, or make it a draft again */
+  readonly status: InputMaybe<PageStatus>
+  /** New title of the page */
+  readonly title: InputMaybe<Scalars["String"]>
+}
+
+/**
+ * Change the profile of the signed-in user. Fields that aren't given stay the
+ * same, and fields given as null are cleared.
+ */
+export type UpdateProfileInput = {
+  /** URL of an image to show next to the user's name */
+  readonly avatarUrl: InputMaybe<Scalars["String"]>
+  /** New name to show for the user */
+  readonly displayName: InputMaybe<Scalars["String"]>
+  /** Which language to show the website in */
+  readonly interfaceLanguage: InputMaybe<InterfaceLanguage>
+  /** Whether to email the user when someone replies to their comments */
+  readonly notifyOnCommentReplies: InputMaybe<Scalars["Boolean"]>
+  /** Whether to email the user when an editor reviews their proposed edits */
+  readonly notifyOnEditReviews: InputMaybe<Scalars["Boolean"]>
+  /** How to write Cherokee words when a query doesn't pick a system */
+  readonly preferredOrthography: InputMaybe<CherokeeOrthography>
+}
+
 /** A user record, for a contributor, editor, etc. */
 export type User = {
   readonly __typename?: "User"
@@ -947,10 +2611,93 @@ export enum UserGroup {
 /** Auth metadata on the user making the current request. */
 export type UserInfo = {
   readonly __typename?: "UserInfo"
+  /** API keys this user has made, including revoked ones */
+  readonly apiKeys: ReadonlyArray<ApiKey>
+  /** Email address the user signed in with */
   readonly email: Scalars["String"]
   readonly groups: ReadonlyArray<UserGroup>
   /** Unique ID for the User. Should be an AWS Cognito Sub. */
   readonly id: Scalars["UUID"]
+  /** Roles this user has been given on single collections or documents */
+  readonly permissions: ReadonlyArray<PermissionGrant>
+  /** Profile details and reading preferences of this user */
+  readonly profile: Maybe<UserProfile>
+}
+
+/**
+ * Profile details and reading preferences of the signed-in user, which they
+ * can change themselves.
+ */
+export type UserProfile = {
+  readonly __typename?: "UserProfile"
+  /** URL of an image to show next to the user's name */
+  readonly avatarUrl: Maybe<Scalars["String"]>
+  /** User-facing name for this contributor/curator */
+  readonly displayName: Scalars["String"]
+  /** Id of the user, which must be a AWS Cognito `sub` claim */
+  readonly id: Scalars["String"]
+  /** Which language to show the website in */
+  readonly interfaceLanguage: InterfaceLanguage
+  /** Whether to email the user when someone replies to their comments */
+  readonly notifyOnCommentReplies: Scalars["Boolean"]
+  /** Whether to email the user when an editor reviews their proposed edits */
+  readonly notifyOnEditReviews: Scalars["Boolean"]
+  /**
+   * How to write Cherokee words for this user when a query doesn't pick a
+   * system
+   */
+  readonly preferredOrthography: Maybe<CherokeeOrthography>
+}
+
+/** Attachment of an annotation on a word */
+export type WordAttachment = {
+  readonly __typename?: "WordAttachment"
+  /** ID of the annotated word */
+  readonly to: Scalars["UUID"]
+  /** The annotated word */
+  readonly word: AnnotatedForm
+}
+
+/** A word from our collection embedded in a page, shown with its audio. */
+export type WordEmbed = {
+  readonly __typename?: "WordEmbed"
+  /** The embedded word, unless it has since been deleted */
+  readonly word: Maybe<AnnotatedForm>
+  /** ID of the word to show */
+  readonly wordId: Scalars["UUID"]
+}
+
+/** A word from our collection embedded in a page, shown with its audio. */
+export type WordEmbedInput = {
+  /** ID of the word to show */
+  readonly wordId: Scalars["UUID"]
+}
+
+/** One change made to a word or paragraph. */
+export type WordRevision = {
+  readonly __typename?: "WordRevision"
+  /** Editable fields after the change */
+  readonly after: WordSnapshot
+  /** Editable fields before the change */
+  readonly before: WordSnapshot
+  /** When the edit was made */
+  readonly editedAt: DateTime
+  /** Who made the edit, unless their account has since been deleted */
+  readonly editedBy: Maybe<User>
+  /** Unique identifier of this revision */
+  readonly id: Scalars["UUID"]
+}
+
+/** Words matching a search, along with facet counts to narrow it by. */
+export type WordSearchResults = {
+  readonly __typename?: "WordSearchResults"
+  /**
+   * Counts of the matching words by collection, genre, decade, contributor,
+   * and audio
+   */
+  readonly facets: SearchFacets
+  /** Words matching the search and filter */
+  readonly forms: ReadonlyArray<AnnotatedForm>
 }
 
 export type WordSegment = {
@@ -983,6 +2730,35 @@ export enum WordSegmentRole {
   Morpheme = "MORPHEME",
 }
 
+/** The editable fields of a word at one point in time */
+export type WordSnapshot = {
+  readonly __typename?: "WordSnapshot"
+  /** Further details about the annotation layers */
+  readonly commentary: Maybe<Scalars["String"]>
+  /** Morphemic segmentation of the word */
+  readonly segments: ReadonlyArray<SegmentSnapshot>
+  /** Original source text */
+  readonly source: Scalars["String"]
+}
+
+/** Outcome of one update in a batch of word updates */
+export type WordUpdateResult = {
+  readonly __typename?: "WordUpdateResult"
+  /** Current version of the word, if the update was based on an older one */
+  readonly currentVersion: Maybe<Scalars["Int"]>
+  /** Why this update can't be saved, if it can't */
+  readonly error: Maybe<Scalars["String"]>
+  /**
+   * Whether this update was saved. Batches are saved all at once or not at
+   * all, so if any update has an error then none are saved.
+   */
+  readonly saved: Scalars["Boolean"]
+  /** The word as it is now */
+  readonly word: AnnotatedForm
+  /** ID of the word this update was for */
+  readonly wordId: Scalars["UUID"]
+}
+
 /** A list of words grouped by the document that contains them. */
 export type WordsInDocument = {
   readonly __typename?: "WordsInDocument"
@@ -994,6 +2770,25 @@ export type WordsInDocument = {
   readonly forms: ReadonlyArray<AnnotatedForm>
 }
 
+export type WordsInDocumentConnection = {
+  readonly __typename?: "WordsInDocumentConnection"
+  /** A list of edges. */
+  readonly edges: ReadonlyArray<WordsInDocumentEdge>
+  /** A list of nodes. */
+  readonly nodes: ReadonlyArray<WordsInDocument>
+  /** Information to aid in pagination. */
+  readonly pageInfo: PageInfo
+}
+
+/** An edge in a connection. */
+export type WordsInDocumentEdge = {
+  readonly __typename?: "WordsInDocumentEdge"
+  /** A cursor for use in pagination */
+  readonly cursor: Scalars["String"]
+  /** The item at the end of the edge */
+  readonly node: WordsInDocument
+}
+
 export type CollectionsListingQueryVariables = Exact<{ [key: string]: never }>
 
 export type CollectionsListingQuery = { readonly __typename?: "Query" } & {
@@ -1024,7 +2819,7 @@ export type AnnotatedDocumentQuery = { readonly __typename?: "Query" } & {
   readonly document: Maybe<
     { readonly __typename?: "AnnotatedDoc" } & Pick<
       AnnotatedDoc,
-      "id" | "title" | "slug" | "isReference"
+      "id" | "version" | "title" | "slug" | "isReference"
     > & {
         readonly date: Maybe<
           { readonly __typename?: "Date" } & Pick<Date, "year">
@@ -1141,6 +2936,7 @@ export type DocumentContentsQuery = { readonly __typename?: "Query" } & {
                         | ({ readonly __typename: "AnnotatedForm" } & Pick<
                             AnnotatedForm,
                             | "id"
+                            | "version"
                             | "index"
                             | "source"
                             | "romanizedSource"
@@ -1230,6 +3026,7 @@ export type DocumentContentsQuery = { readonly __typename?: "Query" } & {
           { readonly __typename: "AnnotatedForm" } & Pick<
             AnnotatedForm,
             | "id"
+            | "version"
             | "index"
             | "source"
             | "romanizedSource"
@@ -1316,7 +3113,7 @@ export type AudioSliceFieldsFragment = {
 
 export type DocFormFieldsFragment = {
   readonly __typename?: "AnnotatedDoc"
-} & Pick<AnnotatedDoc, "id" | "title"> & {
+} & Pick<AnnotatedDoc, "id" | "version" | "title"> & {
     readonly date: Maybe<
       { readonly __typename?: "Date" } & Pick<Date, "day" | "month" | "year">
     >
@@ -1324,11 +3121,12 @@ export type DocFormFieldsFragment = {
 
 export type ParagraphFormFieldsFragment = {
   readonly __typename?: "DocumentParagraph"
-} & Pick<DocumentParagraph, "id" | "index" | "translation"> & {
+} & Pick<DocumentParagraph, "id" | "version" | "index" | "translation"> & {
     readonly source: ReadonlyArray<
       | ({ readonly __typename: "AnnotatedForm" } & Pick<
           AnnotatedForm,
           | "id"
+          | "version"
           | "index"
           | "source"
           | "romanizedSource"
@@ -1408,6 +3206,7 @@ export type FormFieldsFragment = {
 } & Pick<
   AnnotatedForm,
   | "id"
+  | "version"
   | "index"
   | "source"
   | "romanizedSource"
@@ -1656,8 +3455,12 @@ export type EditablePageQuery = { readonly __typename?: "Query" } & {
   readonly page: Maybe<
     { readonly __typename?: "Page" } & Pick<Page, "id" | "title"> & {
         readonly body: ReadonlyArray<
+          | { readonly __typename: "AudioPlayer" }
+          | { readonly __typename: "DocumentExcerpt" }
           | { readonly __typename: "Gallery" }
+          | { readonly __typename: "GlossaryEntry" }
           | ({ readonly __typename: "Markdown" } & Pick<Markdown, "content">)
+          | { readonly __typename: "WordEmbed" }
         >
       }
   >
@@ -1717,13 +3520,12 @@ export type UserInfoQuery = { readonly __typename?: "Query" } & {
 }
 
 export type NewPageMutationVariables = Exact<{
-  data: Scalars["JSON"]
+  input: CreatePageInput
 }>
 
-export type NewPageMutation = { readonly __typename?: "Mutation" } & Pick<
-  Mutation,
-  "updatePage"
->
+export type NewPageMutation = { readonly __typename?: "Mutation" } & {
+  readonly createPage: { readonly __typename?: "Page" } & Pick<Page, "id">
+}
 
 export type DocSliceQueryVariables = Exact<{
   slug: Scalars["String"]
@@ -1745,6 +3547,7 @@ export type DocSliceQuery = { readonly __typename?: "Query" } & {
           { readonly __typename: "AnnotatedForm" } & Pick<
             AnnotatedForm,
             | "id"
+            | "version"
             | "index"
             | "source"
             | "romanizedSource"
@@ -2022,6 +3825,7 @@ export type UpdateWordMutation = { readonly __typename?: "Mutation" } & {
   readonly updateWord: { readonly __typename: "AnnotatedForm" } & Pick<
     AnnotatedForm,
     | "id"
+    | "version"
     | "index"
     | "source"
     | "romanizedSource"
@@ -2253,7 +4057,7 @@ export type UpdateParagraphMutationVariables = Exact<{
 export type UpdateParagraphMutation = { readonly __typename?: "Mutation" } & {
   readonly updateParagraph: {
     readonly __typename?: "DocumentParagraph"
-  } & Pick<DocumentParagraph, "id" | "translation">
+  } & Pick<DocumentParagraph, "id" | "version" | "translation">
 }
 
 export type UpdateContributorAttributionMutationVariables = Exact<{
@@ -2308,6 +4112,7 @@ export type PostCommentMutation = { readonly __typename?: "Mutation" } & {
               }
           >
         })
+    | { readonly __typename?: "Comment" }
     | ({ readonly __typename: "DocumentParagraph" } & Pick<
         DocumentParagraph,
         "id"
@@ -2338,6 +4143,7 @@ export type PostCommentMutation = { readonly __typename?: "Mutation" } & {
 export const DocFormFieldsFragmentDoc = gql`
   fragment DocFormFields on AnnotatedDoc {
     id
+    version
     title
     date {
       day
@@ -2360,6 +4166,7 @@ export const FormFieldsFragmentDoc = gql`
   fragment FormFields on AnnotatedForm {
     __typename
     id
+    version
     index
     source
     romanizedSource(system: $morphemeSystem)
@@ -2400,6 +4207,7 @@ export const FormFieldsFragmentDoc = gql`
 export const ParagraphFormFieldsFragmentDoc = gql`
   fragment ParagraphFormFields on DocumentParagraph {
     id
+    version
     index
     translation
     source {
@@ -2469,6 +4277,7 @@ export const AnnotatedDocumentDocument = gql`
   query AnnotatedDocument($slug: String!) {
     document(slug: $slug) {
       id
+      version
       title
       slug
       isReference
@@ -2881,8 +4690,10 @@ export function useUserInfoQuery(
   })
 }
 export const NewPageDocument = gql`
-  mutation NewPage($data: JSON!) {
-    updatePage(data: $data)
+  mutation NewPage($input: CreatePageInput!) {
+    createPage(input: $input) {
+      id
+    }
   }
 `
 
@@ -3202,6 +5013,7 @@ export const UpdateParagraphDocument = gql`
   mutation UpdateParagraph($paragraph: ParagraphUpdate!) {
     updateParagraph(paragraph: $paragraph) {
       id
+      version
       translation
     }
   }